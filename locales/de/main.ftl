@@ -0,0 +1,426 @@
+## Datumsangaben, als chrono-strftime-Muster
+
+date-time-format = %-d. %b %Y um %H:%M
+date-long-format = %A, %-d. %B %Y
+
+## Allgemein
+
+field-required = Pflichtfeld
+back-to-login = Zurück zur Anmeldung
+lockout-retry = Erneut versuchen in { $time }
+error-invalid-email = Keine gültige E-Mail-Adresse
+error-passwords-differ = Die Passwörter stimmen nicht überein.
+
+## Validierung
+
+validation-username-length = Muss zwischen { $min } und { $max } Zeichen lang sein
+validation-password-length = Muss zwischen { $min } und { $max } Zeichen lang sein
+
+## Fehler
+
+error-network = Netzwerkfehler: { $details }
+error-rate-limited = Zu viele Versuche, bitte etwas langsamer.
+error-account-locked = Dieses Konto ist vorübergehend gesperrt.
+error-unreadable-error = Fehlerantwort konnte nicht gelesen werden: { $details }
+error-unreadable-response = Antwort konnte nicht gelesen werden: { $details }
+
+## Fehlercodes des Servers
+
+server-error-invalid-credentials = Benutzername, E-Mail oder Passwort ist falsch.
+server-error-username-taken = Dieser Benutzername ist bereits vergeben.
+server-error-email-taken = Es gibt bereits ein Konto mit dieser E-Mail-Adresse.
+server-error-invalid-mfa-code = Der Code hat nicht funktioniert. Prüfe ihn und versuche es erneut.
+server-error-invalid-reset-code = Der Code ist ungültig oder abgelaufen.
+server-error-unauthorized = Deine Sitzung ist abgelaufen. Bitte melde dich erneut an.
+server-error-not-found = Das existiert nicht mehr.
+server-error-internal = Bei uns ist etwas schiefgelaufen. Versuche es gleich noch einmal.
+
+## Anmeldung
+
+login-title = Anmelden
+login-identifier = Benutzername oder E-Mail
+login-password = Passwort
+login-forgot-password = Passwort vergessen?
+login-submit = Anmelden
+login-submit-busy = Wird angemeldet…
+login-no-account = Noch kein Konto?{" "}
+login-register = Registrieren
+
+mfa-title = Zwei-Faktor-Authentifizierung
+mfa-hint-app = Gib den 6-stelligen Code aus deiner Authenticator-App ein.
+mfa-hint-backup = Gib einen der Backup-Codes ein, die du beim Einrichten der Zwei-Faktor-Authentifizierung gespeichert hast.
+mfa-code-app = Authentifizierungscode
+mfa-code-backup = Backup-Code
+mfa-submit = Bestätigen
+mfa-submit-busy = Wird geprüft…
+mfa-use-backup = Stattdessen einen Backup-Code verwenden
+mfa-use-app = Stattdessen die Authenticator-App verwenden
+
+## Registrierung
+
+register-title = Konto erstellen
+register-username = Benutzername
+register-email = E-Mail
+register-password = Passwort
+register-password-confirm = Passwort bestätigen
+register-submit = Registrieren
+register-submit-busy = Wird registriert…
+register-have-account = Du hast schon ein Konto?{" "}
+register-login = Anmelden
+
+## Passwort vergessen
+
+forgot-title = Passwort vergessen?
+forgot-hint = Gib die E-Mail-Adresse deines Kontos ein und wir schicken dir einen Code zum Zurücksetzen.
+forgot-email = E-Mail
+forgot-submit = Code senden
+forgot-submit-busy = Wird gesendet…
+
+reset-title = Passwort zurücksetzen
+reset-hint = Wir haben einen Code an { $email } geschickt. Er ist nur kurz gültig.
+reset-code = Code
+reset-no-email = Nichts angekommen?{" "}
+reset-resend = Erneut senden
+reset-password = Neues Passwort
+reset-password-confirm = Neues Passwort bestätigen
+reset-submit = Passwort zurücksetzen
+reset-submit-busy = Wird zurückgesetzt…
+reset-done = Dein Passwort wurde zurückgesetzt. Melde dich mit dem neuen Passwort an.
+
+## Passwortstärke
+
+password-strength = Passwortstärke:{" "}
+password-strength-0 = Sehr schwach
+password-strength-1 = Schwach
+password-strength-2 = Mittel
+password-strength-3 = Stark
+password-strength-4 = Sehr stark
+
+## App
+
+verify-email-banner = Bitte bestätige deine E-Mail-Adresse. Wir haben einen Link an { $email } geschickt.
+verify-email-resend = Erneut senden
+verify-email-sending = Wird gesendet…
+verify-email-sent = E-Mail gesendet!
+app-search = Unterhaltung suchen oder beginnen
+composer-placeholder = Nachricht
+composer-emoji = Emoji
+preview-member-you = Du
+preview-channel = allgemein
+
+## Benachrichtigungen
+
+toast-undo = Rückgängig
+toast-retry = Erneut versuchen
+inbox-title = Benachrichtigungen
+inbox-mark-read = Alle als gelesen markieren
+inbox-clear = Leeren
+inbox-empty = Noch nichts da. Erwähnungen, Freundschaftsanfragen und Hinweise der App erscheinen hier.
+notify-verification-failed = Die Bestätigungs-E-Mail konnte nicht gesendet werden: { $details }
+notify-keybindings-reset = Die Tastenkürzel wurden auf die Standardwerte zurückgesetzt.
+notify-settings-reloaded = Die Einstellungen wurden aus der Einstellungsdatei neu geladen.
+notify-settings-invalid = Die Einstellungsdatei konnte nicht gelesen werden, die aktuellen Einstellungen bleiben: { $details }
+notify-settings-save-failed = Die Einstellungen konnten nicht gespeichert werden: { $details }
+notify-link-failed = { $url } konnte nicht geöffnet werden
+
+## Codeblöcke
+
+code-copy = Kopieren
+code-copied = Code in die Zwischenablage kopiert
+code-show-all = Alle { $count } Zeilen anzeigen
+code-show-less = Weniger anzeigen
+
+## Erwähnungen
+
+mention-unknown-user = unbekannter-nutzer
+mention-unknown-role = gelöschte-rolle
+mention-unknown-channel = gelöschter-kanal
+
+## Emoji und Reaktionen
+
+emoji-search = Emoji suchen
+emoji-none = Keine Emoji gefunden
+reaction-who = { $others ->
+    [0] { $names }
+   *[other] { $names } und { $others } weitere
+}
+notify-reaction-failed = Deine Reaktion konnte nicht gespeichert werden.
+
+## Nachrichtenaktionen
+
+message-you = Du
+message-reply = Antworten
+message-react = Reaktion hinzufügen
+message-edit = Bearbeiten
+message-delete = Löschen
+message-more = Mehr
+message-copy-text = Text kopieren
+message-copy-link = Nachrichtenlink kopieren
+message-copy-id = Nachrichten-ID kopieren
+message-mark-unread = Als ungelesen markieren
+message-remove-embeds = Einbettungen entfernen
+message-text-copied = Nachricht in die Zwischenablage kopiert
+message-link-copied = Link in die Zwischenablage kopiert
+message-id-copied = ID in die Zwischenablage kopiert
+message-edited = (bearbeitet)
+message-edited-at = Bearbeitet { $date }
+message-edit-hint = Enter zum Speichern, Escape zum Abbrechen
+message-delete-confirm = Diese Nachricht löschen? Das kann nicht rückgängig gemacht werden.
+message-delete-cancel = Abbrechen
+message-delete-hint = Halte beim Löschen Umschalt gedrückt, um das zu überspringen.
+message-unread-divider = Neu
+reply-to = Antwort an { $author }
+notify-edit-failed = Deine Änderung konnte nicht gespeichert werden.
+notify-delete-failed = Die Nachricht konnte nicht gelöscht werden.
+notify-embeds-failed = Die Linkvorschauen konnten nicht entfernt werden.
+
+## Angeheftete Nachrichten
+
+message-pin = Nachricht anheften
+message-unpin = Nachricht lösen
+pins-title = Angeheftete Nachrichten
+pins-empty = In diesem Kanal sind noch keine Nachrichten angeheftet.
+pins-jump = Hinspringen
+pin-notice = { $author } hat
+pin-notice-message = eine Nachricht angeheftet.
+pin-notice-all = Alle angehefteten Nachrichten ansehen.
+notify-pin-failed = Die angehefteten Nachrichten konnten nicht geändert werden.
+
+## Threads
+
+message-start-thread = Thread starten
+thread-untitled = Neuer Thread
+threads-title = Threads
+threads-active = Aktiv
+threads-archived = Archiviert
+threads-empty = In diesem Kanal gibt es noch keine Threads. Starte einen von einer beliebigen Nachricht aus.
+thread-messages = { $count ->
+    [one] 1 Nachricht
+   *[other] { $count } Nachrichten
+}
+thread-archive = Archivieren
+thread-unarchive = Wiederherstellen
+thread-archived-notice = Dieser Thread ist archiviert. Eine neue Nachricht holt ihn zurück.
+thread-auto-archive = Archivieren nach Inaktivität
+thread-archive-hour = 1 Stunde
+thread-archive-day = 24 Stunden
+thread-archive-three-days = 3 Tage
+thread-archive-week = 1 Woche
+thread-composer-placeholder = Nachricht an { $name }
+notify-thread-failed = Der Thread konnte nicht aktualisiert werden.
+
+## Anhänge
+
+attachment-attach = Dateien anhängen
+attachment-pick-title = Dateien anhängen
+attachment-too-large = { $name } ist zu groß, Dateien dürfen höchstens { $limit } groß sein.
+attachment-unreadable = { $name } konnte nicht gelesen werden: { $details }
+attachment-spoiler = Spoiler
+attachment-download = Herunterladen
+attachment-loading = Wird geladen…
+attachment-unavailable = Dieses Bild konnte nicht geladen werden.
+attachment-failed = Hochladen fehlgeschlagen
+attachment-drop-hint = Dateien hier ablegen, um sie anzuhängen
+attachment-open-original = Original öffnen
+attachment-close = Schließen
+notify-upload-failed = { $name } konnte nicht hochgeladen werden: { $details }
+notify-attachments-uploading = Warte, bis deine Dateien hochgeladen sind, bevor du sendest.
+
+## Einstellungen
+
+settings-title = Einstellungen
+settings-close = Schließen
+settings-section-security = Sicherheit
+settings-section-devices = Geräte
+settings-section-appearance = Darstellung
+settings-section-theme-editor = Theme-Editor
+settings-section-keybindings = Tastenkürzel
+settings-section-language = Sprache
+settings-section-messages = Nachrichten
+
+devices-title = Geräte
+devices-hint = Diese Geräte sind bei deinem Konto angemeldet. Melde alle ab, die du nicht kennst.
+devices-count =
+    { $count ->
+        [one] Ein Gerät ist angemeldet.
+       *[other] { $count } Geräte sind angemeldet.
+    }
+devices-this-device = Dieses Gerät
+devices-loading = Wird geladen…
+devices-log-out = Abmelden
+devices-logging-out = Wird abgemeldet…
+devices-active-now = Gerade aktiv
+devices-last-active = Zuletzt aktiv am { $date }
+
+language-title = Sprache
+language-system = Systemstandard ({ $language })
+language-hint = Datumsangaben verwenden die Monats- und Tagesnamen der gewählten Sprache.
+language-preview = Heute ist { $date }.
+
+settings-link-previews = Linkvorschauen anzeigen
+settings-link-previews-hint = Zeigt unter Nachrichten mit Links eine Vorschau mit Titel, Beschreibung und Bild der Seite.
+settings-link-preview-proxy = Proxy für Linkvorschauen
+settings-link-preview-proxy-hint = Seiten ohne Vorschau vom Server werden über diesen Proxy abgerufen, damit die Seiten deine IP-Adresse nicht erfahren.
+settings-link-preview-proxy-invalid = Das ist keine URL, Links werden nicht in der Vorschau angezeigt.
+settings-link-preview-proxy-reset = Zurücksetzen
+
+security-title = Zwei-Faktor-Authentifizierung
+security-hint = Schütze dein Konto zusätzlich zu deinem Passwort mit einem Code aus einer Authenticator-App.
+security-loading = Wird geladen…
+security-enable = Authenticator-App aktivieren
+security-scan-step = 1. Scanne diesen QR-Code mit deiner Authenticator-App.
+security-manual-key = Scannen klappt nicht? Gib diesen Schlüssel von Hand ein:
+security-copy = Kopieren
+security-code-step = 2. Gib den 6-stelligen Code ein, den die App anzeigt.
+security-code = Authentifizierungscode
+security-code-invalid = Muss aus 6 Ziffern bestehen
+security-verify = Prüfen und aktivieren
+security-cancel = Abbrechen
+security-enabled = Die Authenticator-App ist aktiviert.
+security-backup-codes-remaining =
+    { $count ->
+        [one] Ein Backup-Code übrig
+       *[other] { $count } Backup-Codes übrig
+    }
+security-view-backup-codes = Backup-Codes anzeigen
+security-regenerate-backup-codes = Backup-Codes neu erzeugen
+security-disable = Deaktivieren
+security-confirm-view = Bestätige dein Passwort, um die Backup-Codes anzuzeigen
+security-view = Anzeigen
+security-confirm-regenerate = Beim Neuerzeugen werden deine bisherigen Backup-Codes ungültig
+security-regenerate = Neu erzeugen
+security-confirm-disable = Ohne Zwei-Faktor-Authentifizierung ist dein Konto weniger sicher
+security-backup-codes = Backup-Codes
+security-backup-codes-hint = Mit jedem Code kannst du dich einmal anmelden, falls du keinen Zugriff mehr auf deine Authenticator-App hast. Bewahre sie sicher auf.
+security-done = Fertig
+security-qr-code-failed = Der QR-Code konnte nicht erstellt werden: { $details }
+
+appearance-title = Darstellung
+appearance-mode = Modus
+appearance-light = Hell
+appearance-dark = Dunkel
+appearance-system = Wie das System
+appearance-system-scheme = Dein Desktop bevorzugt gerade { $scheme }.
+appearance-no-system-scheme = Dein Desktop meldet keine Vorliebe, deshalb wird stattdessen das hier verwendet.
+appearance-scheme-light = Hell
+appearance-scheme-dark = Dunkel
+appearance-light-theme = Helles Theme
+appearance-dark-theme = Dunkles Theme
+appearance-ui-font = Schrift der Oberfläche
+appearance-message-font = Schrift der Nachrichten
+appearance-code-font = Schrift für Code
+appearance-accessibility = Barrierefreiheit
+appearance-zoom = Zoom: { $percent } %
+appearance-zoom-hint = Strg+= und Strg+- zoomen hinein und heraus, Strg+0 setzt zurück.
+appearance-font-size = Schriftgröße: { $size } px
+appearance-density = Nachrichtendichte
+appearance-density-compact = Kompakt
+appearance-density-cozy = Gemütlich
+appearance-reduced-motion = Bewegung reduzieren
+appearance-reduced-motion-hint = Schaltet Animationen und Übergänge aus.
+appearance-high-contrast = Hoher Kontrast
+appearance-high-contrast-hint = Verwendet die kontrastreiche Palette statt deiner hellen und dunklen Themes.
+appearance-saved-hint = Änderungen werden automatisch gespeichert und gelten sofort.
+appearance-themes-hint = Lege eigene Paletten als .toml-Dateien in { $dir } ab und starte die App neu.
+appearance-theme-custom = { $name } (eigenes)
+appearance-font-system-sans = Systemschrift, serifenlos
+appearance-font-system-mono = Systemschrift, dicktengleich
+
+theme-editor-title = Theme-Editor
+theme-editor-start-from = Ausgehend von
+theme-editor-pick-theme = Theme wählen…
+theme-editor-name = Name
+theme-editor-edited-name = { $name } (bearbeitet)
+theme-editor-dark = Dunkles Theme
+theme-editor-hex = Hex
+theme-editor-hex-invalid = Verwende #rrggbb
+theme-editor-contrast = Kontrast
+theme-editor-contrast-ok = Alle geprüften Paare aus Text und Hintergrund erfüllen den WCAG-AA-Kontrast.
+theme-editor-contrast-issue = ⚠ { $label }: { $ratio }:1, mindestens { $minimum }:1 nötig
+theme-editor-preview = Vorschau
+theme-editor-preview-category = ALLGEMEIN
+theme-editor-preview-time = Heute um 12:04
+theme-editor-preview-warning = Bestätige deine E-Mail-Adresse, damit dein Konto sicher bleibt.
+theme-editor-preview-question = Hat schon jemand den neuen Theme-Editor ausprobiert?
+theme-editor-preview-answer = Ja, er zeigt jede Farbe sofort in der Vorschau.
+theme-editor-preview-failure = Nachricht konnte nicht gesendet werden.
+theme-editor-preview-success = Gespeichert.
+theme-editor-preview-composer = Nachricht an #welcome
+theme-editor-preview-send = Senden
+theme-editor-save = Theme speichern
+theme-editor-export = Exportieren…
+theme-editor-import = Importieren…
+theme-editor-name-required = Gib dem Theme zuerst einen Namen.
+theme-editor-saved = „{ $name }“ gespeichert und angewendet.
+theme-editor-exported = Nach { $path } exportiert.
+theme-editor-imported = „{ $name }“ importiert. Speichere es, um es zu verwenden.
+theme-editor-export-title = Theme exportieren
+theme-editor-import-title = Theme importieren
+theme-editor-file-type = Theme
+theme-editor-write-failed = { $path } konnte nicht geschrieben werden: { $details }
+theme-editor-not-text = { $name } ist keine Textdatei
+
+color-group-main = Haupt
+color-group-status = Status
+color-group-background = Hintergrund
+color-group-foreground = Vordergrund
+color-main-primary = Primär
+color-main-secondary = Sekundär
+color-main-tertiary = Tertiär
+color-main-accent-primary = Akzent primär
+color-main-accent-secondary = Akzent sekundär
+color-main-accent-tertiary = Akzent tertiär
+color-status-success = Erfolg
+color-status-warning = Warnung
+color-status-failure = Fehler
+color-background-primary = Primär
+color-background-secondary = Sekundär
+color-background-tertiary = Tertiär
+color-background-surface-primary = Fläche primär
+color-background-surface-secondary = Fläche sekundär
+color-background-surface-tertiary = Fläche tertiär
+color-foreground-primary = Primär
+color-foreground-secondary = Sekundär
+color-foreground-tertiary = Tertiär
+color-foreground-subtext-primary = Nebentext primär
+color-foreground-subtext-secondary = Nebentext sekundär
+color-foreground-subtext-tertiary = Nebentext tertiär
+contrast-text-on-window = Text im Fenster
+contrast-text-on-sidebar = Text in der Seitenleiste
+contrast-input-text = Eingabetext
+contrast-tertiary-text = Tertiärer Text
+contrast-subtext-on-sidebar = Nebentext in der Seitenleiste
+contrast-hints = Hinweise
+contrast-placeholders = Platzhalter
+contrast-links = Links
+contrast-error-messages = Fehlermeldungen
+contrast-primary-button = Primäre Schaltfläche
+contrast-danger-button = Gefährliche Schaltfläche
+contrast-secondary-button = Sekundäre Schaltfläche
+
+keybindings-title = Tastenkürzel
+keybindings-reset-all = Alle zurücksetzen
+keybindings-hint = Klicke auf eine Taste, um sie zu entfernen. Tab und Umschalt+Tab wechseln immer zwischen Feldern.
+keybindings-recording-hint = Drücke die Tasten, die du verwenden möchtest. Klicke erneut auf die Schaltfläche, um abzubrechen.
+keybindings-not-bound = Nicht belegt
+keybindings-press-key = Taste drücken…
+keybindings-add-key = Taste hinzufügen
+keybindings-reset = Zurücksetzen
+keybindings-shares-key = Teilt sich eine Taste mit { $actions }
+keybindings-conflict = { $key } wird schon für „{ $action }“ verwendet.
+keybindings-use-here = Hier verwenden
+keybindings-cancel = Abbrechen
+keybindings-reserved = { $key } wechselt den Fokus und kann nicht neu belegt werden.
+
+action-next-channel = Nächster Kanal
+action-previous-channel = Vorheriger Kanal
+action-next-unread = Nächster ungelesener Kanal
+action-jump-to-mentions = Zu Erwähnungen springen
+action-mark-read = Kanal als gelesen markieren
+action-open-settings = Einstellungen öffnen
+action-toggle-mute = Kanal stumm schalten oder Stummschaltung aufheben
+action-edit-last-message = Deine letzte Nachricht bearbeiten
+action-close-modal = Dialog schließen
+action-zoom-in = Hineinzoomen
+action-zoom-out = Herauszoomen
+action-reset-zoom = Zoom zurücksetzen
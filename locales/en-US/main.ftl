@@ -0,0 +1,426 @@
+## Dates, as chrono strftime patterns
+
+date-time-format = %b %-d, %Y at %H:%M
+date-long-format = %A, %B %-d, %Y
+
+## Shared
+
+field-required = Required
+back-to-login = Back to login
+lockout-retry = Try again in { $time }
+error-invalid-email = Not a valid email address
+error-passwords-differ = Passwords need to match.
+
+## Validation, keyed by the code given to the validator
+
+validation-username-length = Must be between { $min } and { $max } characters
+validation-password-length = Must be between { $min } and { $max } characters
+
+## Errors
+
+error-network = Network error: { $details }
+error-rate-limited = Too many attempts, please slow down.
+error-account-locked = This account is temporarily locked.
+error-unreadable-error = Failed to parse error response: { $details }
+error-unreadable-response = Failed to parse response: { $details }
+
+## Server error codes
+
+server-error-invalid-credentials = Wrong username, email or password.
+server-error-username-taken = That username is already taken.
+server-error-email-taken = An account with that email already exists.
+server-error-invalid-mfa-code = That code didn't work. Check it and try again.
+server-error-invalid-reset-code = That reset code is invalid or has expired.
+server-error-unauthorized = Your session has expired. Log in again.
+server-error-not-found = That doesn't exist anymore.
+server-error-internal = Something went wrong on our end. Try again in a moment.
+
+## Login
+
+login-title = Log in
+login-identifier = Username or Email
+login-password = Password
+login-forgot-password = Forgot password?
+login-submit = Login
+login-submit-busy = Logging in…
+login-no-account = Don't have an account?{" "}
+login-register = Register
+
+mfa-title = Two-factor authentication
+mfa-hint-app = Enter the 6-digit code from your authenticator app.
+mfa-hint-backup = Enter one of the backup codes you saved when enabling two-factor authentication.
+mfa-code-app = Authentication code
+mfa-code-backup = Backup code
+mfa-submit = Verify
+mfa-submit-busy = Verifying…
+mfa-use-backup = Use a backup code instead
+mfa-use-app = Use your authenticator app instead
+
+## Register
+
+register-title = Create your account
+register-username = Username
+register-email = Email
+register-password = Password
+register-password-confirm = Confirm Password
+register-submit = Register
+register-submit-busy = Registering…
+register-have-account = Have an account already?{" "}
+register-login = Login
+
+## Forgot password
+
+forgot-title = Forgot your password?
+forgot-hint = Enter the email address on your account and we'll send you a code to reset it.
+forgot-email = Email
+forgot-submit = Send reset code
+forgot-submit-busy = Sending…
+
+reset-title = Reset your password
+reset-hint = We sent a code to { $email }. It expires shortly.
+reset-code = Reset code
+reset-no-email = Didn't get it?{" "}
+reset-resend = Resend email
+reset-password = New Password
+reset-password-confirm = Confirm New Password
+reset-submit = Reset password
+reset-submit-busy = Resetting…
+reset-done = Your password was reset. Log in with your new password.
+
+## Password strength
+
+password-strength = Password strength:{" "}
+password-strength-0 = Very weak
+password-strength-1 = Weak
+password-strength-2 = Fair
+password-strength-3 = Strong
+password-strength-4 = Very strong
+
+## App
+
+verify-email-banner = Please verify your email address. We sent a link to { $email }.
+verify-email-resend = Resend email
+verify-email-sending = Sending…
+verify-email-sent = Email sent!
+app-search = Find or start a conversation
+composer-placeholder = Message
+composer-emoji = Emoji
+preview-member-you = You
+preview-channel = general
+
+## Notifications
+
+toast-undo = Undo
+toast-retry = Retry
+inbox-title = Notifications
+inbox-mark-read = Mark all read
+inbox-clear = Clear
+inbox-empty = Nothing here yet. Mentions, friend requests and anything the app tells you about show up here.
+notify-verification-failed = Couldn't send the verification email: { $details }
+notify-keybindings-reset = Keybindings were reset to their defaults.
+notify-settings-reloaded = Settings were reloaded from the settings file.
+notify-settings-invalid = The settings file couldn't be read, keeping the current settings: { $details }
+notify-settings-save-failed = Couldn't save your settings: { $details }
+notify-link-failed = Couldn't open { $url }
+
+## Code blocks
+
+code-copy = Copy
+code-copied = Code copied to clipboard
+code-show-all = Show all { $count } lines
+code-show-less = Show less
+
+## Mentions
+
+mention-unknown-user = unknown-user
+mention-unknown-role = deleted-role
+mention-unknown-channel = deleted-channel
+
+## Emoji and reactions
+
+emoji-search = Find an emoji
+emoji-none = No emoji found
+reaction-who = { $others ->
+    [0] { $names }
+   *[other] { $names } and { $others } more
+}
+notify-reaction-failed = Couldn't save your reaction.
+
+## Message actions
+
+message-you = You
+message-reply = Reply
+message-react = Add reaction
+message-edit = Edit
+message-delete = Delete
+message-more = More
+message-copy-text = Copy text
+message-copy-link = Copy message link
+message-copy-id = Copy message ID
+message-mark-unread = Mark unread
+message-remove-embeds = Remove embeds
+message-text-copied = Message copied to clipboard
+message-link-copied = Link copied to clipboard
+message-id-copied = ID copied to clipboard
+message-edited = (edited)
+message-edited-at = Edited { $date }
+message-edit-hint = Enter to save, Escape to cancel
+message-delete-confirm = Delete this message? This can't be undone.
+message-delete-cancel = Cancel
+message-delete-hint = Hold Shift while deleting to skip this.
+message-unread-divider = New
+reply-to = Replying to { $author }
+notify-edit-failed = Couldn't save your edit.
+notify-delete-failed = Couldn't delete the message.
+notify-embeds-failed = Couldn't remove the link previews.
+
+## Pinned messages
+
+message-pin = Pin message
+message-unpin = Unpin message
+pins-title = Pinned messages
+pins-empty = No pinned messages in this channel yet.
+pins-jump = Jump
+pin-notice = { $author } pinned
+pin-notice-message = a message
+pin-notice-all = See all pinned messages.
+notify-pin-failed = Couldn't change the pinned messages.
+
+## Threads
+
+message-start-thread = Start thread
+thread-untitled = New thread
+threads-title = Threads
+threads-active = Active
+threads-archived = Archived
+threads-empty = No threads in this channel yet. Start one from any message.
+thread-messages = { $count ->
+    [one] 1 message
+   *[other] { $count } messages
+}
+thread-archive = Archive
+thread-unarchive = Unarchive
+thread-archived-notice = This thread is archived. Sending a message brings it back.
+thread-auto-archive = Archive after inactivity
+thread-archive-hour = 1 hour
+thread-archive-day = 24 hours
+thread-archive-three-days = 3 days
+thread-archive-week = 1 week
+thread-composer-placeholder = Message { $name }
+notify-thread-failed = Couldn't update the thread.
+
+## Attachments
+
+attachment-attach = Attach files
+attachment-pick-title = Attach files
+attachment-too-large = { $name } is too large, files can be up to { $limit }.
+attachment-unreadable = Couldn't read { $name }: { $details }
+attachment-spoiler = Spoiler
+attachment-download = Download
+attachment-loading = Loading…
+attachment-unavailable = Couldn't load this image.
+attachment-failed = Upload failed
+attachment-drop-hint = Drop files to attach them
+attachment-open-original = Open original
+attachment-close = Close
+notify-upload-failed = Couldn't upload { $name }: { $details }
+notify-attachments-uploading = Wait for your files to finish uploading before sending.
+
+## Settings
+
+settings-title = Settings
+settings-close = Close
+settings-section-security = Security
+settings-section-devices = Devices
+settings-section-appearance = Appearance
+settings-section-theme-editor = Theme editor
+settings-section-keybindings = Keybindings
+settings-section-language = Language
+settings-section-messages = Messages
+
+devices-title = Devices
+devices-hint = These devices are logged in to your account. Log out any you don't recognize.
+devices-count =
+    { $count ->
+        [one] One device is logged in.
+       *[other] { $count } devices are logged in.
+    }
+devices-this-device = This device
+devices-loading = Loading…
+devices-log-out = Log out
+devices-logging-out = Logging out…
+devices-active-now = Active now
+devices-last-active = Last active { $date }
+
+language-title = Language
+language-system = System default ({ $language })
+language-hint = Dates use the month and day names of the language you pick.
+language-preview = Today is { $date }.
+
+settings-link-previews = Show link previews
+settings-link-previews-hint = Shows a preview under messages with links, with the page's title, description and image.
+settings-link-preview-proxy = Link preview proxy
+settings-link-preview-proxy-hint = Pages without a preview from the server are fetched through this proxy, so the sites you see previews of don't learn your IP address.
+settings-link-preview-proxy-invalid = This isn't a URL, links won't be previewed.
+settings-link-preview-proxy-reset = Reset
+
+security-title = Two-factor authentication
+security-hint = Protect your account with a code from an authenticator app on top of your password.
+security-loading = Loading…
+security-enable = Enable authenticator app
+security-scan-step = 1. Scan this QR code with your authenticator app.
+security-manual-key = Can't scan it? Enter this key manually:
+security-copy = Copy
+security-code-step = 2. Enter the 6-digit code the app shows.
+security-code = Authentication code
+security-code-invalid = Must be 6 digits
+security-verify = Verify and enable
+security-cancel = Cancel
+security-enabled = Authenticator app is enabled.
+security-backup-codes-remaining =
+    { $count ->
+        [one] One backup code remaining
+       *[other] { $count } backup codes remaining
+    }
+security-view-backup-codes = View backup codes
+security-regenerate-backup-codes = Regenerate backup codes
+security-disable = Disable
+security-confirm-view = Confirm your password to view backup codes
+security-view = View
+security-confirm-regenerate = Regenerating invalidates your current backup codes
+security-regenerate = Regenerate
+security-confirm-disable = Disabling two-factor authentication makes your account less secure
+security-backup-codes = Backup codes
+security-backup-codes-hint = Each code can be used once to log in if you lose access to your authenticator app. Keep them somewhere safe.
+security-done = Done
+security-qr-code-failed = Couldn't build the QR code: { $details }
+
+appearance-title = Appearance
+appearance-mode = Mode
+appearance-light = Light
+appearance-dark = Dark
+appearance-system = Follow system
+appearance-system-scheme = Your desktop currently prefers { $scheme }.
+appearance-no-system-scheme = Your desktop doesn't report a preference, so this is used instead.
+appearance-scheme-light = Light
+appearance-scheme-dark = Dark
+appearance-light-theme = Light theme
+appearance-dark-theme = Dark theme
+appearance-ui-font = Interface font
+appearance-message-font = Message font
+appearance-code-font = Code font
+appearance-accessibility = Accessibility
+appearance-zoom = Zoom: { $percent }%
+appearance-zoom-hint = Ctrl+= and Ctrl+- zoom in and out, Ctrl+0 resets.
+appearance-font-size = Font size: { $size }px
+appearance-density = Message density
+appearance-density-compact = Compact
+appearance-density-cozy = Cozy
+appearance-reduced-motion = Reduce motion
+appearance-reduced-motion-hint = Turns off animations and transitions.
+appearance-high-contrast = High contrast
+appearance-high-contrast-hint = Uses the high contrast palette instead of your light and dark themes.
+appearance-saved-hint = Changes are saved automatically and apply right away.
+appearance-themes-hint = Add your own palettes as .toml files in { $dir } and restart the app.
+appearance-theme-custom = { $name } (custom)
+appearance-font-system-sans = System sans-serif
+appearance-font-system-mono = System monospace
+
+theme-editor-title = Theme editor
+theme-editor-start-from = Start from
+theme-editor-pick-theme = Pick a theme…
+theme-editor-name = Name
+theme-editor-edited-name = { $name } (edited)
+theme-editor-dark = Dark theme
+theme-editor-hex = Hex
+theme-editor-hex-invalid = Use #rrggbb
+theme-editor-contrast = Contrast
+theme-editor-contrast-ok = All checked text and background pairs meet WCAG AA contrast.
+theme-editor-contrast-issue = ⚠ { $label }: { $ratio }:1, needs at least { $minimum }:1
+theme-editor-preview = Preview
+theme-editor-preview-category = GENERAL
+theme-editor-preview-time = Today at 12:04
+theme-editor-preview-warning = Verify your email to keep your account safe.
+theme-editor-preview-question = Has anyone tried the new theme editor?
+theme-editor-preview-answer = Yes, it previews every color live.
+theme-editor-preview-failure = Message failed to send.
+theme-editor-preview-success = Saved.
+theme-editor-preview-composer = Message #welcome
+theme-editor-preview-send = Send
+theme-editor-save = Save theme
+theme-editor-export = Export…
+theme-editor-import = Import…
+theme-editor-name-required = Give the theme a name first.
+theme-editor-saved = Saved and applied "{ $name }".
+theme-editor-exported = Exported to { $path }.
+theme-editor-imported = Imported "{ $name }". Save it to start using it.
+theme-editor-export-title = Export theme
+theme-editor-import-title = Import theme
+theme-editor-file-type = Theme
+theme-editor-write-failed = Couldn't write { $path }: { $details }
+theme-editor-not-text = { $name } is not a text file
+
+color-group-main = Main
+color-group-status = Status
+color-group-background = Background
+color-group-foreground = Foreground
+color-main-primary = Primary
+color-main-secondary = Secondary
+color-main-tertiary = Tertiary
+color-main-accent-primary = Accent primary
+color-main-accent-secondary = Accent secondary
+color-main-accent-tertiary = Accent tertiary
+color-status-success = Success
+color-status-warning = Warning
+color-status-failure = Failure
+color-background-primary = Primary
+color-background-secondary = Secondary
+color-background-tertiary = Tertiary
+color-background-surface-primary = Surface primary
+color-background-surface-secondary = Surface secondary
+color-background-surface-tertiary = Surface tertiary
+color-foreground-primary = Primary
+color-foreground-secondary = Secondary
+color-foreground-tertiary = Tertiary
+color-foreground-subtext-primary = Subtext primary
+color-foreground-subtext-secondary = Subtext secondary
+color-foreground-subtext-tertiary = Subtext tertiary
+contrast-text-on-window = Text on window
+contrast-text-on-sidebar = Text on sidebar
+contrast-input-text = Input text
+contrast-tertiary-text = Tertiary text
+contrast-subtext-on-sidebar = Subtext on sidebar
+contrast-hints = Hints
+contrast-placeholders = Placeholders
+contrast-links = Links
+contrast-error-messages = Error messages
+contrast-primary-button = Primary button
+contrast-danger-button = Danger button
+contrast-secondary-button = Secondary button
+
+keybindings-title = Keybindings
+keybindings-reset-all = Reset all
+keybindings-hint = Click a key to remove it. Tab and Shift+Tab always move between fields.
+keybindings-recording-hint = Press the keys you want to use. Click the button again to cancel.
+keybindings-not-bound = Not bound
+keybindings-press-key = Press a key…
+keybindings-add-key = Add key
+keybindings-reset = Reset
+keybindings-shares-key = Shares a key with { $actions }
+keybindings-conflict = { $key } is already used for "{ $action }".
+keybindings-use-here = Use it here
+keybindings-cancel = Cancel
+keybindings-reserved = { $key } moves focus and can't be rebound.
+
+action-next-channel = Next channel
+action-previous-channel = Previous channel
+action-next-unread = Next unread channel
+action-jump-to-mentions = Jump to mentions
+action-mark-read = Mark channel as read
+action-open-settings = Open settings
+action-toggle-mute = Mute or unmute channel
+action-edit-last-message = Edit your last message
+action-close-modal = Close dialog
+action-zoom-in = Zoom in
+action-zoom-out = Zoom out
+action-reset-zoom = Reset zoom
@@ -0,0 +1,429 @@
+## תאריכים, כתבניות strftime של chrono
+
+date-time-format = %-d ב%B %Y בשעה %H:%M
+date-long-format = יום %A, %-d ב%B %Y
+
+## כללי
+
+field-required = שדה חובה
+back-to-login = חזרה להתחברות
+lockout-retry = אפשר לנסות שוב בעוד { $time }
+error-invalid-email = כתובת האימייל אינה תקינה
+error-passwords-differ = הסיסמאות אינן תואמות.
+
+## אימות שדות
+
+validation-username-length = צריך להכיל בין { $min } ל־{ $max } תווים
+validation-password-length = צריך להכיל בין { $min } ל־{ $max } תווים
+
+## שגיאות
+
+error-network = שגיאת רשת: { $details }
+error-rate-limited = יותר מדי ניסיונות, כדאי להאט קצת.
+error-account-locked = החשבון נעול זמנית.
+error-unreadable-error = לא ניתן לקרוא את תשובת השגיאה: { $details }
+error-unreadable-response = לא ניתן לקרוא את התשובה: { $details }
+
+## קודי שגיאה מהשרת
+
+server-error-invalid-credentials = שם המשתמש, האימייל או הסיסמה שגויים.
+server-error-username-taken = שם המשתמש הזה כבר תפוס.
+server-error-email-taken = כבר קיים חשבון עם האימייל הזה.
+server-error-invalid-mfa-code = הקוד לא עבד. כדאי לבדוק אותו ולנסות שוב.
+server-error-invalid-reset-code = קוד האיפוס שגוי או שפג תוקפו.
+server-error-unauthorized = פג תוקף ההתחברות. יש להתחבר מחדש.
+server-error-not-found = זה כבר לא קיים.
+server-error-internal = משהו השתבש אצלנו. כדאי לנסות שוב בעוד רגע.
+
+## התחברות
+
+login-title = התחברות
+login-identifier = שם משתמש או אימייל
+login-password = סיסמה
+login-forgot-password = שכחת את הסיסמה?
+login-submit = התחברות
+login-submit-busy = מתחברים…
+login-no-account = אין לך חשבון?{" "}
+login-register = להרשמה
+
+mfa-title = אימות דו־שלבי
+mfa-hint-app = יש להזין את הקוד בן 6 הספרות מאפליקציית האימות.
+mfa-hint-backup = יש להזין אחד מקודי הגיבוי ששמרת כשהפעלת אימות דו־שלבי.
+mfa-code-app = קוד אימות
+mfa-code-backup = קוד גיבוי
+mfa-submit = אימות
+mfa-submit-busy = מאמתים…
+mfa-use-backup = שימוש בקוד גיבוי במקום
+mfa-use-app = שימוש באפליקציית האימות במקום
+
+## הרשמה
+
+register-title = יצירת חשבון
+register-username = שם משתמש
+register-email = אימייל
+register-password = סיסמה
+register-password-confirm = אישור סיסמה
+register-submit = הרשמה
+register-submit-busy = נרשמים…
+register-have-account = כבר יש לך חשבון?{" "}
+register-login = להתחברות
+
+## שכחתי סיסמה
+
+forgot-title = שכחת את הסיסמה?
+forgot-hint = יש להזין את כתובת האימייל של החשבון ונשלח אליה קוד לאיפוס הסיסמה.
+forgot-email = אימייל
+forgot-submit = שליחת קוד איפוס
+forgot-submit-busy = שולחים…
+
+reset-title = איפוס הסיסמה
+reset-hint = שלחנו קוד אל { $email }. הוא בתוקף לזמן קצר.
+reset-code = קוד איפוס
+reset-no-email = לא הגיע?{" "}
+reset-resend = שליחה חוזרת
+reset-password = סיסמה חדשה
+reset-password-confirm = אישור הסיסמה החדשה
+reset-submit = איפוס הסיסמה
+reset-submit-busy = מאפסים…
+reset-done = הסיסמה אופסה. אפשר להתחבר עם הסיסמה החדשה.
+
+## חוזק הסיסמה
+
+password-strength = חוזק הסיסמה:{" "}
+password-strength-0 = חלשה מאוד
+password-strength-1 = חלשה
+password-strength-2 = סבירה
+password-strength-3 = חזקה
+password-strength-4 = חזקה מאוד
+
+## אפליקציה
+
+verify-email-banner = יש לאמת את כתובת האימייל. שלחנו קישור אל { $email }.
+verify-email-resend = שליחה חוזרת
+verify-email-sending = שולחים…
+verify-email-sent = האימייל נשלח!
+app-search = חיפוש או התחלת שיחה
+composer-placeholder = הודעה
+composer-emoji = אימוג'י
+preview-member-you = את/ה
+preview-channel = כללי
+
+## התראות
+
+toast-undo = ביטול
+toast-retry = ניסיון חוזר
+inbox-title = התראות
+inbox-mark-read = סימון הכול כנקרא
+inbox-clear = ניקוי
+inbox-empty = אין כאן עדיין כלום. אזכורים, בקשות חברות והודעות מהאפליקציה יופיעו כאן.
+notify-verification-failed = לא ניתן היה לשלוח את אימייל האימות: { $details }
+notify-keybindings-reset = קיצורי המקלדת אופסו לברירת המחדל.
+notify-settings-reloaded = ההגדרות נטענו מחדש מקובץ ההגדרות.
+notify-settings-invalid = לא ניתן היה לקרוא את קובץ ההגדרות, ההגדרות הנוכחיות נשמרות: { $details }
+notify-settings-save-failed = לא ניתן היה לשמור את ההגדרות: { $details }
+notify-link-failed = לא ניתן היה לפתוח את { $url }
+
+## בלוקי קוד
+
+code-copy = העתקה
+code-copied = הקוד הועתק ללוח
+code-show-all = הצגת כל { $count } השורות
+code-show-less = הצגת פחות
+
+## אזכורים
+
+mention-unknown-user = משתמש-לא-ידוע
+mention-unknown-role = תפקיד-שנמחק
+mention-unknown-channel = ערוץ-שנמחק
+
+## אימוג'י ותגובות
+
+emoji-search = חיפוש אימוג'י
+emoji-none = לא נמצאו אימוג'י
+reaction-who = { $others ->
+    [0] { $names }
+   *[other] { $names } ועוד { $others }
+}
+notify-reaction-failed = לא ניתן היה לשמור את התגובה שלך.
+
+## פעולות על הודעות
+
+message-you = את/ה
+message-reply = תשובה
+message-react = הוספת תגובה
+message-edit = עריכה
+message-delete = מחיקה
+message-more = עוד
+message-copy-text = העתקת הטקסט
+message-copy-link = העתקת קישור להודעה
+message-copy-id = העתקת מזהה ההודעה
+message-mark-unread = סימון כלא נקראה
+message-remove-embeds = הסרת התצוגות המקדימות
+message-text-copied = ההודעה הועתקה ללוח
+message-link-copied = הקישור הועתק ללוח
+message-id-copied = המזהה הועתק ללוח
+message-edited = (נערכה)
+message-edited-at = נערכה ב־{ $date }
+message-edit-hint = Enter לשמירה, Escape לביטול
+message-delete-confirm = למחוק את ההודעה? אי אפשר לבטל את זה.
+message-delete-cancel = ביטול
+message-delete-hint = אפשר להחזיק Shift בזמן המחיקה כדי לדלג על השאלה הזו.
+message-unread-divider = חדש
+reply-to = תשובה ל־{ $author }
+notify-edit-failed = לא ניתן היה לשמור את העריכה.
+notify-delete-failed = לא ניתן היה למחוק את ההודעה.
+notify-embeds-failed = לא ניתן היה להסיר את התצוגות המקדימות של הקישורים.
+
+## הודעות נעוצות
+
+message-pin = נעיצת ההודעה
+message-unpin = ביטול הנעיצה
+pins-title = הודעות נעוצות
+pins-empty = אין עדיין הודעות נעוצות בערוץ הזה.
+pins-jump = מעבר
+pin-notice = { $author } נעץ/ה
+pin-notice-message = הודעה
+pin-notice-all = לכל ההודעות הנעוצות.
+notify-pin-failed = לא ניתן היה לשנות את ההודעות הנעוצות.
+
+## שרשורים
+
+message-start-thread = פתיחת שרשור
+thread-untitled = שרשור חדש
+threads-title = שרשורים
+threads-active = פעילים
+threads-archived = בארכיון
+threads-empty = אין עדיין שרשורים בערוץ הזה. אפשר לפתוח שרשור מכל הודעה.
+thread-messages = { $count ->
+    [one] הודעה אחת
+    [two] שתי הודעות
+   *[other] { $count } הודעות
+}
+thread-archive = העברה לארכיון
+thread-unarchive = הוצאה מהארכיון
+thread-archived-notice = השרשור הזה בארכיון. שליחת הודעה תחזיר אותו.
+thread-auto-archive = העברה לארכיון אחרי חוסר פעילות של
+thread-archive-hour = שעה
+thread-archive-day = 24 שעות
+thread-archive-three-days = 3 ימים
+thread-archive-week = שבוע
+thread-composer-placeholder = הודעה ב־{ $name }
+notify-thread-failed = לא ניתן היה לעדכן את השרשור.
+
+## קבצים מצורפים
+
+attachment-attach = צירוף קבצים
+attachment-pick-title = צירוף קבצים
+attachment-too-large = { $name } גדול מדי, אפשר לצרף קבצים עד { $limit }.
+attachment-unreadable = לא ניתן היה לקרוא את { $name }: { $details }
+attachment-spoiler = ספוילר
+attachment-download = הורדה
+attachment-loading = בטעינה…
+attachment-unavailable = לא ניתן היה לטעון את התמונה.
+attachment-failed = ההעלאה נכשלה
+attachment-drop-hint = אפשר לשחרר כאן קבצים כדי לצרף אותם
+attachment-open-original = פתיחת המקור
+attachment-close = סגירה
+notify-upload-failed = לא ניתן היה להעלות את { $name }: { $details }
+notify-attachments-uploading = יש לחכות שהעלאת הקבצים תסתיים לפני השליחה.
+
+## הגדרות
+
+settings-title = הגדרות
+settings-close = סגירה
+settings-section-security = אבטחה
+settings-section-devices = מכשירים
+settings-section-appearance = מראה
+settings-section-theme-editor = עורך ערכות נושא
+settings-section-keybindings = קיצורי מקלדת
+settings-section-language = שפה
+settings-section-messages = הודעות
+
+devices-title = מכשירים
+devices-hint = המכשירים האלה מחוברים לחשבון שלך. כדאי לנתק כל מכשיר שאינך מזהה.
+devices-count =
+    { $count ->
+        [one] מכשיר אחד מחובר.
+        [two] שני מכשירים מחוברים.
+       *[other] { $count } מכשירים מחוברים.
+    }
+devices-this-device = המכשיר הזה
+devices-loading = טוען…
+devices-log-out = ניתוק
+devices-logging-out = מנתקים…
+devices-active-now = פעיל עכשיו
+devices-last-active = פעיל לאחרונה ב־{ $date }
+
+language-title = שפה
+language-system = ברירת המחדל של המערכת ({ $language })
+language-hint = התאריכים מוצגים עם שמות החודשים והימים של השפה שנבחרה.
+language-preview = היום { $date }.
+
+settings-link-previews = הצגת תצוגה מקדימה של קישורים
+settings-link-previews-hint = מציג מתחת להודעות עם קישורים תצוגה מקדימה עם הכותרת, התיאור והתמונה של הדף.
+settings-link-preview-proxy = שרת מתווך לתצוגה מקדימה
+settings-link-preview-proxy-hint = דפים שאין להם תצוגה מקדימה מהשרת נטענים דרך השרת המתווך הזה, כדי שהאתרים לא ידעו את כתובת ה־IP שלך.
+settings-link-preview-proxy-invalid = זו לא כתובת URL, לא תוצג תצוגה מקדימה לקישורים.
+settings-link-preview-proxy-reset = איפוס
+
+security-title = אימות דו־שלבי
+security-hint = אפשר להגן על החשבון בקוד מאפליקציית אימות בנוסף לסיסמה.
+security-loading = טוען…
+security-enable = הפעלת אפליקציית אימות
+security-scan-step = 1. יש לסרוק את קוד ה־QR הזה באפליקציית האימות.
+security-manual-key = לא מצליחים לסרוק? אפשר להזין את המפתח הזה ידנית:
+security-copy = העתקה
+security-code-step = 2. יש להזין את הקוד בן 6 הספרות שהאפליקציה מציגה.
+security-code = קוד אימות
+security-code-invalid = חייב להיות בן 6 ספרות
+security-verify = אימות והפעלה
+security-cancel = ביטול
+security-enabled = אפליקציית האימות מופעלת.
+security-backup-codes-remaining =
+    { $count ->
+        [one] נשאר קוד גיבוי אחד
+        [two] נשארו שני קודי גיבוי
+       *[other] נשארו { $count } קודי גיבוי
+    }
+security-view-backup-codes = הצגת קודי גיבוי
+security-regenerate-backup-codes = יצירת קודי גיבוי חדשים
+security-disable = השבתה
+security-confirm-view = יש לאשר את הסיסמה כדי להציג את קודי הגיבוי
+security-view = הצגה
+security-confirm-regenerate = יצירת קודים חדשים מבטלת את קודי הגיבוי הנוכחיים
+security-regenerate = יצירה מחדש
+security-confirm-disable = השבתת האימות הדו־שלבי הופכת את החשבון לפחות מאובטח
+security-backup-codes = קודי גיבוי
+security-backup-codes-hint = כל קוד אפשר להשתמש בו פעם אחת כדי להתחבר אם אין גישה לאפליקציית האימות. כדאי לשמור אותם במקום בטוח.
+security-done = סיום
+security-qr-code-failed = לא ניתן היה ליצור את קוד ה־QR: { $details }
+
+appearance-title = מראה
+appearance-mode = מצב
+appearance-light = בהיר
+appearance-dark = כהה
+appearance-system = לפי המערכת
+appearance-system-scheme = שולחן העבודה מעדיף כרגע מצב { $scheme }.
+appearance-no-system-scheme = שולחן העבודה לא מדווח על העדפה, לכן נעשה שימוש בזה במקום.
+appearance-scheme-light = בהיר
+appearance-scheme-dark = כהה
+appearance-light-theme = ערכת נושא בהירה
+appearance-dark-theme = ערכת נושא כהה
+appearance-ui-font = גופן הממשק
+appearance-message-font = גופן ההודעות
+appearance-code-font = גופן הקוד
+appearance-accessibility = נגישות
+appearance-zoom = זום: { $percent }%
+appearance-zoom-hint = Ctrl+= ו־Ctrl+- מגדילים ומקטינים, Ctrl+0 מאפס.
+appearance-font-size = גודל גופן: { $size }px
+appearance-density = צפיפות ההודעות
+appearance-density-compact = דחוסה
+appearance-density-cozy = מרווחת
+appearance-reduced-motion = צמצום תנועה
+appearance-reduced-motion-hint = מכבה אנימציות ומעברים.
+appearance-high-contrast = ניגודיות גבוהה
+appearance-high-contrast-hint = משתמש בפלטה בניגודיות גבוהה במקום ערכות הנושא הבהירה והכהה.
+appearance-saved-hint = השינויים נשמרים אוטומטית וחלים מיד.
+appearance-themes-hint = אפשר להוסיף פלטות משלך כקובצי ‎.toml בתיקייה { $dir } ולהפעיל מחדש את האפליקציה.
+appearance-theme-custom = { $name } (מותאמת אישית)
+appearance-font-system-sans = גופן המערכת ללא תגים
+appearance-font-system-mono = גופן המערכת ברוחב קבוע
+
+theme-editor-title = עורך ערכות נושא
+theme-editor-start-from = להתחיל מ־
+theme-editor-pick-theme = בחירת ערכת נושא…
+theme-editor-name = שם
+theme-editor-edited-name = { $name } (ערוך)
+theme-editor-dark = ערכת נושא כהה
+theme-editor-hex = Hex
+theme-editor-hex-invalid = יש להשתמש בתבנית ‎#rrggbb
+theme-editor-contrast = ניגודיות
+theme-editor-contrast-ok = כל זוגות הטקסט והרקע שנבדקו עומדים בניגודיות WCAG AA.
+theme-editor-contrast-issue = ⚠ { $label }: ‏{ $ratio }:1, נדרש לפחות { $minimum }:1
+theme-editor-preview = תצוגה מקדימה
+theme-editor-preview-category = כללי
+theme-editor-preview-time = היום ב־12:04
+theme-editor-preview-warning = כדאי לאמת את כתובת האימייל כדי לשמור על החשבון.
+theme-editor-preview-question = מישהו כבר ניסה את עורך ערכות הנושא החדש?
+theme-editor-preview-answer = כן, הוא מציג כל צבע בזמן אמת.
+theme-editor-preview-failure = שליחת ההודעה נכשלה.
+theme-editor-preview-success = נשמר.
+theme-editor-preview-composer = הודעה ל־#welcome
+theme-editor-preview-send = שליחה
+theme-editor-save = שמירת ערכת הנושא
+theme-editor-export = ייצוא…
+theme-editor-import = ייבוא…
+theme-editor-name-required = קודם צריך לתת שם לערכת הנושא.
+theme-editor-saved = ‏"{ $name }" נשמרה והוחלה.
+theme-editor-exported = יוצא אל { $path }.
+theme-editor-imported = ‏"{ $name }" יובאה. יש לשמור אותה כדי להשתמש בה.
+theme-editor-export-title = ייצוא ערכת נושא
+theme-editor-import-title = ייבוא ערכת נושא
+theme-editor-file-type = ערכת נושא
+theme-editor-write-failed = לא ניתן היה לכתוב את { $path }: { $details }
+theme-editor-not-text = { $name } אינו קובץ טקסט
+
+color-group-main = ראשי
+color-group-status = מצב
+color-group-background = רקע
+color-group-foreground = חזית
+color-main-primary = ראשי
+color-main-secondary = משני
+color-main-tertiary = שלישוני
+color-main-accent-primary = הדגשה ראשית
+color-main-accent-secondary = הדגשה משנית
+color-main-accent-tertiary = הדגשה שלישונית
+color-status-success = הצלחה
+color-status-warning = אזהרה
+color-status-failure = כישלון
+color-background-primary = ראשי
+color-background-secondary = משני
+color-background-tertiary = שלישוני
+color-background-surface-primary = משטח ראשי
+color-background-surface-secondary = משטח משני
+color-background-surface-tertiary = משטח שלישוני
+color-foreground-primary = ראשי
+color-foreground-secondary = משני
+color-foreground-tertiary = שלישוני
+color-foreground-subtext-primary = טקסט משנה ראשי
+color-foreground-subtext-secondary = טקסט משנה משני
+color-foreground-subtext-tertiary = טקסט משנה שלישוני
+contrast-text-on-window = טקסט על החלון
+contrast-text-on-sidebar = טקסט על הסרגל הצדדי
+contrast-input-text = טקסט בשדות קלט
+contrast-tertiary-text = טקסט שלישוני
+contrast-subtext-on-sidebar = טקסט משנה על הסרגל הצדדי
+contrast-hints = רמזים
+contrast-placeholders = טקסט ממלא מקום
+contrast-links = קישורים
+contrast-error-messages = הודעות שגיאה
+contrast-primary-button = כפתור ראשי
+contrast-danger-button = כפתור מסוכן
+contrast-secondary-button = כפתור משני
+
+keybindings-title = קיצורי מקלדת
+keybindings-reset-all = איפוס הכול
+keybindings-hint = לחיצה על מקש מסירה אותו. Tab ו־Shift+Tab תמיד עוברים בין שדות.
+keybindings-recording-hint = יש ללחוץ על המקשים הרצויים. לחיצה נוספת על הכפתור מבטלת.
+keybindings-not-bound = לא מוגדר
+keybindings-press-key = יש ללחוץ על מקש…
+keybindings-add-key = הוספת מקש
+keybindings-reset = איפוס
+keybindings-shares-key = חולק מקש עם { $actions }
+keybindings-conflict = { $key } כבר משמש עבור "{ $action }".
+keybindings-use-here = להשתמש בו כאן
+keybindings-cancel = ביטול
+keybindings-reserved = { $key } מעביר את המיקוד ואי אפשר להגדיר אותו מחדש.
+
+action-next-channel = הערוץ הבא
+action-previous-channel = הערוץ הקודם
+action-next-unread = הערוץ הבא שלא נקרא
+action-jump-to-mentions = מעבר לאזכורים
+action-mark-read = סימון הערוץ כנקרא
+action-open-settings = פתיחת ההגדרות
+action-toggle-mute = השתקה או ביטול השתקה של הערוץ
+action-edit-last-message = עריכת ההודעה האחרונה שלך
+action-close-modal = סגירת החלון
+action-zoom-in = הגדלה
+action-zoom-out = הקטנה
+action-reset-zoom = איפוס הזום
@@ -0,0 +1,264 @@
+use iced::{
+    advanced::image::Bytes,
+    futures::{SinkExt, Stream, StreamExt, stream},
+    task,
+    widget::image,
+};
+use reqwest::{Body, multipart};
+use serde::Deserialize;
+use std::{io::Cursor, path::PathBuf};
+
+use crate::{constants::API_URL, i18n::tr, media, utils::send_json};
+
+/// Larger files are turned away before they're read, the server wouldn't take them.
+pub const MAX_SIZE: u64 = 25 * 1024 * 1024;
+/// Uploads are streamed in pieces this big, their progress moves on with each.
+const CHUNK_SIZE: usize = 64 * 1024;
+const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "gif", "webp"];
+/// Attached images are shown scaled down to fit this, see [`media::Key::thumbnail`].
+const THUMBNAIL_SIZE: u32 = 800;
+/// What an image pasted from the clipboard is called, it has no name of its own.
+const PASTED_NAME: &str = "image.png";
+
+/// A file attached to a message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attachment {
+    /// The server's ID, or one made up here for a file that isn't on the server.
+    pub id: String,
+    pub name: String,
+    pub size: u64,
+    /// Where the file is downloaded from, `None` for files that aren't on the server.
+    pub url: Option<String>,
+    /// Covered until clicked, like a spoiler in the text.
+    pub spoiler: bool,
+    /// The image as it was attached here, shown instead of downloading it.
+    pub preview: Option<image::Handle>,
+}
+
+impl Attachment {
+    pub fn is_image(&self) -> bool {
+        is_image(&self.name)
+    }
+
+    /// The image scaled down to show in the conversation, `None` for other files and those
+    /// that aren't on the server.
+    pub fn thumbnail(&self) -> Option<media::Key> {
+        let url = self.url.clone().filter(|_| self.is_image())?;
+        Some(media::Key::thumbnail(url, THUMBNAIL_SIZE))
+    }
+}
+
+/// Whether a file is shown as an image, going by its extension.
+fn is_image(name: &str) -> bool {
+    name.rsplit_once('.')
+        .is_some_and(|(_, extension)| IMAGE_EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+}
+
+/// A file read to be attached.
+#[derive(Debug, Clone)]
+pub struct File {
+    pub name: String,
+    pub bytes: Bytes,
+}
+
+/// A file attached in the composer, until it's sent with the next message.
+#[derive(Debug, Clone)]
+pub struct Pending {
+    /// Made up here, to tell the files apart while they upload.
+    pub id: String,
+    pub name: String,
+    pub bytes: Bytes,
+    pub spoiler: bool,
+    /// Shown in the composer for images.
+    pub preview: Option<image::Handle>,
+    pub upload: Upload,
+}
+
+impl Pending {
+    pub fn new(id: String, file: File) -> Self {
+        let preview = is_image(&file.name).then(|| image::Handle::from_bytes(file.bytes.clone()));
+        Self {
+            id,
+            name: file.name,
+            bytes: file.bytes,
+            spoiler: false,
+            preview,
+            upload: Upload::Local,
+        }
+    }
+
+    pub fn size(&self) -> u64 {
+        self.bytes.len() as u64
+    }
+
+    pub fn is_uploading(&self) -> bool {
+        matches!(self.upload, Upload::Sending { .. })
+    }
+
+    pub fn has_failed(&self) -> bool {
+        matches!(self.upload, Upload::Failed(_))
+    }
+
+    /// The file as it goes with the message, under the server's ID once it's uploaded.
+    pub fn into_attachment(self) -> Attachment {
+        let size = self.size();
+        let (id, url) = match self.upload {
+            Upload::Uploaded(uploaded) => (uploaded.id, Some(uploaded.url)),
+            _ => (self.id, None),
+        };
+        Attachment {
+            id,
+            name: self.name,
+            size,
+            url,
+            spoiler: self.spoiler,
+            preview: self.preview,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Upload {
+    /// There's no channel to upload to, the file stays here.
+    Local,
+    /// With how many bytes were sent so far, and the handle to cancel the upload.
+    Sending {
+        sent: u64,
+        handle: task::Handle,
+    },
+    Uploaded(Uploaded),
+    Failed(String),
+}
+
+/// Where the server keeps an uploaded file until the message it goes with is sent.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Uploaded {
+    pub id: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum Progress {
+    /// How many bytes have been sent so far.
+    Sent(u64),
+    Finished(Result<Uploaded, String>),
+}
+
+/// A file size for people, e.g. `1.4 MB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{bytes} {}", UNITS[0]),
+        _ => format!("{size:.1} {}", UNITS[unit]),
+    }
+}
+
+fn check_size(name: &str, size: u64) -> Result<(), String> {
+    if size > MAX_SIZE {
+        let limit = format_size(MAX_SIZE);
+        return Err(tr!("attachment-too-large", name = name, limit = limit));
+    }
+    Ok(())
+}
+
+/// Files picked from the file dialog, none when it's closed.
+pub async fn pick_files() -> Vec<PathBuf> {
+    rfd::AsyncFileDialog::new()
+        .set_title(tr!("attachment-pick-title"))
+        .pick_files()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|file| file.path().to_path_buf())
+        .collect()
+}
+
+/// Reads a file to attach, checking its size before reading it all.
+pub async fn read(path: PathBuf) -> Result<File, String> {
+    let name = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    let unreadable = |e: std::io::Error| {
+        tr!(
+            "attachment-unreadable",
+            name = name.as_str(),
+            details = e.to_string()
+        )
+    };
+    let size = tokio::fs::metadata(&path).await.map_err(unreadable)?.len();
+    check_size(&name, size)?;
+    let bytes = tokio::fs::read(&path).await.map_err(unreadable)?;
+    Ok(File {
+        name,
+        bytes: bytes.into(),
+    })
+}
+
+/// The image on the clipboard as a PNG, `None` when there's no image, e.g. for text, which
+/// the composer pastes by itself.
+pub async fn paste_image() -> Option<Result<File, String>> {
+    let png = tokio::task::spawn_blocking(|| {
+        let pasted = arboard::Clipboard::new().ok()?.get_image().ok()?;
+        let width = u32::try_from(pasted.width).ok()?;
+        let height = u32::try_from(pasted.height).ok()?;
+        let pixels = ::image::RgbaImage::from_raw(width, height, pasted.bytes.into_owned())?;
+        let mut png = Vec::new();
+        pixels
+            .write_to(&mut Cursor::new(&mut png), ::image::ImageFormat::Png)
+            .ok()?;
+        Some(png)
+    })
+    .await
+    .ok()
+    .flatten()?;
+
+    let file = check_size(PASTED_NAME, png.len() as u64).map(|()| File {
+        name: PASTED_NAME.to_string(),
+        bytes: png.into(),
+    });
+    Some(file)
+}
+
+/// Uploads a file to a channel, to be sent with the next message there, telling how far along
+/// it is as it goes.
+pub fn upload(
+    access_token: String,
+    channel_id: String,
+    name: String,
+    bytes: Bytes,
+) -> impl Stream<Item = Progress> {
+    iced::stream::channel(16, move |mut output| async move {
+        let size = bytes.len() as u64;
+        let chunks: Vec<Bytes> = (0..bytes.len())
+            .step_by(CHUNK_SIZE)
+            .map(|start| bytes.slice(start..bytes.len().min(start + CHUNK_SIZE)))
+            .collect();
+        let mut progress = output.clone();
+        let mut sent = 0;
+        let body = stream::iter(chunks).map(move |chunk| {
+            sent += chunk.len() as u64;
+            // Dropped while the UI is behind, the next one catches up.
+            let _ = progress.try_send(Progress::Sent(sent));
+            Ok::<_, std::io::Error>(chunk)
+        });
+
+        let part =
+            multipart::Part::stream_with_length(Body::wrap_stream(body), size).file_name(name);
+        let client = reqwest::Client::new();
+        let result = send_json(
+            client
+                .post(format!("{API_URL}/channels/{channel_id}/attachments"))
+                .bearer_auth(access_token)
+                .multipart(multipart::Form::new().part("file", part)),
+        )
+        .await;
+        let _ = output.send(Progress::Finished(result)).await;
+    })
+}
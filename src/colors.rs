@@ -1,5 +1,7 @@
 use iced::{Background, Color};
 
+use crate::{i18n::tr, theme};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AppColorMain {
     Primary,
@@ -39,18 +41,31 @@ pub enum AppColorForeground {
 
 impl From<AppColorMain> for Color {
     fn from(value: AppColorMain) -> Self {
-        match value {
-            AppColorMain::Primary => Color::from_rgb8(180, 190, 254),
-            AppColorMain::Secondary => Color::from_rgb8(137, 180, 250),
-            AppColorMain::Tertiary => Color::from_rgb8(116, 199, 236),
-            AppColorMain::AccentPrimary => Color::from_rgb8(203, 166, 247),
-            AppColorMain::AccentSecondary => Color::from_rgb8(245, 194, 231),
-            AppColorMain::AccentTertiary => Color::from_rgb8(242, 205, 205),
-        }
+        theme::with_active(|palette| palette.main(value))
     }
 }
 
 impl AppColorMain {
+    pub const ALL: [AppColorMain; 6] = [
+        AppColorMain::Primary,
+        AppColorMain::Secondary,
+        AppColorMain::Tertiary,
+        AppColorMain::AccentPrimary,
+        AppColorMain::AccentSecondary,
+        AppColorMain::AccentTertiary,
+    ];
+
+    pub fn label(self) -> String {
+        tr!(match self {
+            AppColorMain::Primary => "color-main-primary",
+            AppColorMain::Secondary => "color-main-secondary",
+            AppColorMain::Tertiary => "color-main-tertiary",
+            AppColorMain::AccentPrimary => "color-main-accent-primary",
+            AppColorMain::AccentSecondary => "color-main-accent-secondary",
+            AppColorMain::AccentTertiary => "color-main-accent-tertiary",
+        })
+    }
+
     pub fn to_bg(self) -> Background {
         Background::Color(Color::from(self))
     }
@@ -58,15 +73,25 @@ impl AppColorMain {
 
 impl From<AppColorStatus> for Color {
     fn from(value: AppColorStatus) -> Self {
-        match value {
-            AppColorStatus::Success => Color::from_rgb8(166, 227, 161),
-            AppColorStatus::Warning => Color::from_rgb8(249, 226, 175),
-            AppColorStatus::Failure => Color::from_rgb8(243, 139, 168),
-        }
+        theme::with_active(|palette| palette.status(value))
     }
 }
 
 impl AppColorStatus {
+    pub const ALL: [AppColorStatus; 3] = [
+        AppColorStatus::Success,
+        AppColorStatus::Warning,
+        AppColorStatus::Failure,
+    ];
+
+    pub fn label(self) -> String {
+        tr!(match self {
+            AppColorStatus::Success => "color-status-success",
+            AppColorStatus::Warning => "color-status-warning",
+            AppColorStatus::Failure => "color-status-failure",
+        })
+    }
+
     pub fn to_bg(self) -> Background {
         Background::Color(Color::from(self))
     }
@@ -74,18 +99,31 @@ impl AppColorStatus {
 
 impl From<AppColorBackground> for Color {
     fn from(value: AppColorBackground) -> Self {
-        match value {
-            AppColorBackground::Primary => Color::from_rgb8(17, 17, 27),
-            AppColorBackground::Secondary => Color::from_rgb8(24, 24, 37),
-            AppColorBackground::Tertiary => Color::from_rgb8(30, 30, 46),
-            AppColorBackground::SurfacePrimary => Color::from_rgb8(49, 50, 68),
-            AppColorBackground::SurfaceSecondary => Color::from_rgb8(69, 71, 90),
-            AppColorBackground::SurfaceTertiary => Color::from_rgb8(88, 91, 112),
-        }
+        theme::with_active(|palette| palette.background(value))
     }
 }
 
 impl AppColorBackground {
+    pub const ALL: [AppColorBackground; 6] = [
+        AppColorBackground::Primary,
+        AppColorBackground::Secondary,
+        AppColorBackground::Tertiary,
+        AppColorBackground::SurfacePrimary,
+        AppColorBackground::SurfaceSecondary,
+        AppColorBackground::SurfaceTertiary,
+    ];
+
+    pub fn label(self) -> String {
+        tr!(match self {
+            AppColorBackground::Primary => "color-background-primary",
+            AppColorBackground::Secondary => "color-background-secondary",
+            AppColorBackground::Tertiary => "color-background-tertiary",
+            AppColorBackground::SurfacePrimary => "color-background-surface-primary",
+            AppColorBackground::SurfaceSecondary => "color-background-surface-secondary",
+            AppColorBackground::SurfaceTertiary => "color-background-surface-tertiary",
+        })
+    }
+
     pub fn to_bg(self) -> Background {
         Background::Color(Color::from(self))
     }
@@ -93,18 +131,31 @@ impl AppColorBackground {
 
 impl From<AppColorForeground> for Color {
     fn from(value: AppColorForeground) -> Self {
-        match value {
-            AppColorForeground::Primary => Color::from_rgb8(205, 214, 244),
-            AppColorForeground::Secondary => Color::from_rgb8(186, 194, 222),
-            AppColorForeground::Tertiary => Color::from_rgb8(166, 173, 200),
-            AppColorForeground::SubtextPrimary => Color::from_rgb8(147, 153, 178),
-            AppColorForeground::SubtextSecondary => Color::from_rgb8(127, 132, 156),
-            AppColorForeground::SubtextTertiary => Color::from_rgb8(108, 112, 134),
-        }
+        theme::with_active(|palette| palette.foreground(value))
     }
 }
 
 impl AppColorForeground {
+    pub const ALL: [AppColorForeground; 6] = [
+        AppColorForeground::Primary,
+        AppColorForeground::Secondary,
+        AppColorForeground::Tertiary,
+        AppColorForeground::SubtextPrimary,
+        AppColorForeground::SubtextSecondary,
+        AppColorForeground::SubtextTertiary,
+    ];
+
+    pub fn label(self) -> String {
+        tr!(match self {
+            AppColorForeground::Primary => "color-foreground-primary",
+            AppColorForeground::Secondary => "color-foreground-secondary",
+            AppColorForeground::Tertiary => "color-foreground-tertiary",
+            AppColorForeground::SubtextPrimary => "color-foreground-subtext-primary",
+            AppColorForeground::SubtextSecondary => "color-foreground-subtext-secondary",
+            AppColorForeground::SubtextTertiary => "color-foreground-subtext-tertiary",
+        })
+    }
+
     pub fn to_bg(self) -> Background {
         Background::Color(Color::from(self))
     }
@@ -0,0 +1,221 @@
+use iced::{
+    Alignment, Border, Color, ContentFit, Element,
+    Length::Fill,
+    widget::{Column, button, column, container, image, progress_bar, text},
+};
+use std::collections::BTreeSet;
+
+use crate::{
+    attachments::{Attachment, Pending, Upload, format_size},
+    colors::{AppColorBackground, AppColorForeground, AppColorMain, AppColorStatus},
+    components::directional_row,
+    i18n::tr,
+    media::{self, Lookup},
+    styles::button_secondary_style,
+    typography::{TypeScale, caption, label},
+};
+
+const IMAGE_MAX_WIDTH: f32 = 400.0;
+const IMAGE_MAX_HEIGHT: f32 = 300.0;
+const TRAY_CARD_WIDTH: f32 = 160.0;
+const TRAY_PREVIEW_HEIGHT: f32 = 80.0;
+
+/// The files attached to a message: images inline, with a placeholder while they load, other
+/// files as a card to download them. Spoilers are covered until clicked, images open in the
+/// lightbox.
+pub fn attachment_list<'a, M: Clone + 'a>(
+    attachments: &'a [Attachment],
+    media: &'a media::Cache,
+    revealed: &BTreeSet<String>,
+    on_reveal: impl Fn(String) -> M,
+    on_open: impl Fn(String) -> M,
+    on_download: impl Fn(String) -> M,
+) -> Column<'a, M> {
+    let items = attachments.iter().map(|attachment| {
+        if attachment.spoiler && !revealed.contains(&attachment.id) {
+            return spoiler_cover(on_reveal(attachment.id.clone()));
+        }
+        let lookup = match (&attachment.preview, attachment.thumbnail()) {
+            (Some(preview), _) => Some(Lookup::Loaded(preview)),
+            (None, Some(thumbnail)) => Some(media.get(&thumbnail)),
+            (None, None) => None,
+        };
+        match lookup {
+            Some(Lookup::Loaded(handle)) => {
+                let image = container(image(handle.clone()).content_fit(ContentFit::ScaleDown))
+                    .max_width(IMAGE_MAX_WIDTH)
+                    .max_height(IMAGE_MAX_HEIGHT);
+                button(image)
+                    .padding(0)
+                    .on_press(on_open(attachment.id.clone()))
+                    .style(|_t, _s| button::Style::default())
+                    .into()
+            }
+            Some(Lookup::Loading) => placeholder(),
+            Some(Lookup::Failed) | None => {
+                file_card(attachment, attachment.url.clone().map(&on_download))
+            }
+        }
+    });
+    column(items).spacing(4)
+}
+
+/// Where an image goes while it's downloaded and decoded.
+fn placeholder<'a, M: 'a>() -> Element<'a, M> {
+    let loading =
+        caption(tr!("attachment-loading")).color(Color::from(AppColorForeground::SubtextPrimary));
+    container(loading)
+        .center_x(IMAGE_MAX_WIDTH / 2.0)
+        .center_y(IMAGE_MAX_HEIGHT / 2.0)
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+            border: Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+fn spoiler_cover<'a, M: Clone + 'a>(on_reveal: M) -> Element<'a, M> {
+    button(
+        text(tr!("attachment-spoiler"))
+            .size(TypeScale::Label.size())
+            .width(Fill)
+            .height(Fill)
+            .center(),
+    )
+    .width(IMAGE_MAX_WIDTH / 2.0)
+    .height(IMAGE_MAX_HEIGHT / 3.0)
+    .on_press(on_reveal)
+    .style(|_t, _s| button::Style {
+        background: Some(AppColorBackground::SurfaceSecondary.to_bg()),
+        text_color: AppColorForeground::Primary.into(),
+        border: Border {
+            radius: 8.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+/// A file's name and size, with a button to download it for files on the server.
+fn file_card<'a, M: Clone + 'a>(
+    attachment: &'a Attachment,
+    on_download: Option<M>,
+) -> Element<'a, M> {
+    let details = column![
+        label(attachment.name.as_str()),
+        caption(format_size(attachment.size))
+            .color(Color::from(AppColorForeground::SubtextPrimary)),
+    ]
+    .spacing(2);
+    let download: Option<Element<'a, M>> = on_download.map(|on_press| {
+        button(text(tr!("attachment-download")).size(TypeScale::Caption.size()))
+            .padding([2, 8])
+            .on_press(on_press)
+            .style(button_secondary_style)
+            .into()
+    });
+
+    let mut content: Vec<Element<'a, M>> = vec![text("📄").size(TypeScale::Title.size()).into()];
+    content.push(details.into());
+    content.extend(download);
+    container(
+        directional_row(content)
+            .spacing(10)
+            .align_y(Alignment::Center),
+    )
+    .max_width(IMAGE_MAX_WIDTH)
+    .padding([8, 12])
+    .style(|_t| container::Style {
+        background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+        border: Border {
+            radius: 8.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+/// The files attached in the composer, each with how far its upload is, a switch to cover it
+/// as a spoiler and a button to remove it, which cancels its upload.
+pub fn upload_tray<'a, M: Clone + 'a>(
+    pending: &'a [Pending],
+    on_spoiler: impl Fn(String) -> M,
+    on_remove: impl Fn(String) -> M,
+) -> Element<'a, M> {
+    let cards = pending.iter().map(|file| {
+        let preview: Element<'a, M> = match &file.preview {
+            Some(handle) => image(handle.clone())
+                .content_fit(ContentFit::Cover)
+                .width(Fill)
+                .height(TRAY_PREVIEW_HEIGHT)
+                .into(),
+            None => text("📄")
+                .size(TypeScale::Title.size())
+                .width(Fill)
+                .height(TRAY_PREVIEW_HEIGHT)
+                .center()
+                .into(),
+        };
+        let subtext = Color::from(AppColorForeground::SubtextPrimary);
+        let status: Element<'a, M> = match &file.upload {
+            Upload::Sending { sent, .. } => progress_bar(0.0..=file.size() as f32, *sent as f32)
+                .height(4)
+                .into(),
+            Upload::Failed(_) => caption(tr!("attachment-failed"))
+                .color(Color::from(AppColorStatus::Failure))
+                .into(),
+            Upload::Local | Upload::Uploaded(_) => {
+                caption(format_size(file.size())).color(subtext).into()
+            }
+        };
+
+        let spoiler = file.spoiler;
+        let actions = directional_row([
+            button(text(tr!("attachment-spoiler")).size(TypeScale::Caption.size()))
+                .padding([2, 6])
+                .on_press(on_spoiler(file.id.clone()))
+                .style(move |theme, status| {
+                    if spoiler {
+                        button::Style {
+                            background: Some(AppColorMain::AccentPrimary.to_bg()),
+                            text_color: AppColorBackground::Primary.into(),
+                            border: Border {
+                                radius: 4.0.into(),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        }
+                    } else {
+                        button_secondary_style(theme, status)
+                    }
+                })
+                .into(),
+            button(text("×").size(TypeScale::Label.size()))
+                .padding([0, 6])
+                .on_press(on_remove(file.id.clone()))
+                .style(button_secondary_style)
+                .into(),
+        ])
+        .spacing(4);
+
+        container(column![preview, caption(file.name.as_str()), status, actions].spacing(4))
+            .width(TRAY_CARD_WIDTH)
+            .padding(6)
+            .style(|_t| container::Style {
+                background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+                border: Border {
+                    radius: 8.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .into()
+    });
+    directional_row(cards).spacing(8).into()
+}
@@ -0,0 +1,55 @@
+use iced::{
+    Border, Element,
+    Length::Fill,
+    widget::{button, column, container, text},
+};
+
+use crate::{
+    colors::{AppColorBackground, AppColorForeground},
+    mentions::Suggestion,
+    typography::{TypeScale, message_font},
+};
+
+/// Suggestions shown above the composer while typing a mention, channel or emoji. The first
+/// one is marked, it's the one Enter picks.
+pub fn autocomplete<'a, M: Clone + 'a>(
+    suggestions: &[Suggestion],
+    on_pick: impl Fn(usize) -> M,
+) -> Element<'a, M> {
+    let rows = suggestions.iter().enumerate().map(|(index, suggestion)| {
+        button(
+            text(suggestion.label.clone())
+                .font(message_font())
+                .size(TypeScale::Body.size()),
+        )
+        .width(Fill)
+        .padding([4, 8])
+        .on_press(on_pick(index))
+        .style(move |_t, status| {
+            let highlighted = index == 0 || matches!(status, button::Status::Hovered);
+            button::Style {
+                background: highlighted.then(|| AppColorBackground::SurfaceSecondary.to_bg()),
+                text_color: AppColorForeground::Primary.into(),
+                border: Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        })
+        .into()
+    });
+
+    container(column(rows).spacing(2))
+        .width(Fill)
+        .padding(4)
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+            border: Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
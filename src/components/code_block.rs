@@ -0,0 +1,136 @@
+use iced::{
+    Alignment, Border, Color, Element,
+    Length::Fill,
+    widget::{
+        Column, button, column, container, horizontal_space, rich_text, row, scrollable, span,
+        text,
+        text::{Rich, Wrapping},
+    },
+};
+
+use crate::{
+    colors::{AppColorBackground, AppColorForeground},
+    constants::BORDER_RADIUS_SMALL,
+    highlight::{Highlighted, TokenKind},
+    i18n::tr,
+    styles::button_secondary_style,
+    typography::{TypeScale, caption, code, mono_font},
+};
+
+/// Blocks with at least this many lines get line numbers.
+const NUMBERED_FROM: usize = 6;
+/// Blocks with at least this many lines are collapsed until expanded.
+const COLLAPSE_FROM: usize = 21;
+/// How many lines a collapsed block still shows.
+const COLLAPSED_LINES: usize = 10;
+
+/// A fenced code block with a copy button. Lines never wrap, the code scrolls sideways
+/// instead, and long blocks are numbered and collapsed until `expanded`.
+///
+/// `highlighted` is `None` until highlighting finishes, the code is shown plain until then.
+pub fn code_block<'a, M: Clone + 'static>(
+    language: Option<&str>,
+    source: &str,
+    highlighted: Option<&Highlighted>,
+    expanded: bool,
+    on_copy: M,
+    on_toggle: M,
+) -> Element<'a, M> {
+    let plain: Vec<Vec<(TokenKind, String)>>;
+    let lines = match highlighted {
+        Some(highlighted) => &highlighted.lines,
+        None => {
+            plain = source
+                .lines()
+                .map(|line| vec![(TokenKind::Plain, line.to_string())])
+                .collect();
+            &plain
+        }
+    };
+
+    let collapsible = lines.len() >= COLLAPSE_FROM;
+    let shown = if collapsible && !expanded {
+        COLLAPSED_LINES
+    } else {
+        lines.len()
+    };
+
+    let header = row![
+        caption(language.unwrap_or_default().to_string())
+            .color(Color::from(AppColorForeground::SubtextPrimary)),
+        horizontal_space(),
+        button(text(tr!("code-copy")).size(TypeScale::Caption.size()))
+            .on_press(on_copy)
+            .padding([2, 8])
+            .style(button_secondary_style),
+    ]
+    .align_y(Alignment::Center);
+
+    let code_lines = column(lines[..shown].iter().map(|line| code_line(line).into()));
+    let scrolling = scrollable(code_lines)
+        .direction(scrollable::Direction::Horizontal(
+            scrollable::Scrollbar::new().width(4).scroller_width(4),
+        ))
+        .spacing(4)
+        .width(Fill);
+
+    // Numbers stay put while the code scrolls sideways next to them.
+    let body: Element<'a, M> = if lines.len() >= NUMBERED_FROM {
+        row![line_numbers(shown, lines.len()), scrolling]
+            .spacing(12)
+            .into()
+    } else {
+        scrolling.into()
+    };
+
+    let toggle = collapsible.then(|| {
+        let label = if expanded {
+            tr!("code-show-less")
+        } else {
+            tr!("code-show-all", count = lines.len())
+        };
+        button(text(label).size(TypeScale::Caption.size()))
+            .on_press(on_toggle)
+            .padding([2, 8])
+            .style(button_secondary_style)
+    });
+
+    container(column![header, body].push_maybe(toggle).spacing(6))
+        .width(Fill)
+        .padding(8)
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+            border: Border {
+                radius: BORDER_RADIUS_SMALL.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Numbers padded to the width of the last one, so the code beside them lines up.
+fn line_numbers<'a, M: 'a>(shown: usize, total: usize) -> Column<'a, M> {
+    let width = total.to_string().len();
+    column((1..=shown).map(|number| {
+        code(format!("{number:>width$}"))
+            .color(Color::from(AppColorForeground::SubtextTertiary))
+            .into()
+    }))
+}
+
+fn code_line<'a, M: Clone + 'static>(tokens: &[(TokenKind, String)]) -> Rich<'a, M> {
+    // An empty line still needs its height, or the numbers beside it go out of step.
+    let spans: Vec<_> = if tokens.is_empty() {
+        vec![span(" ")]
+    } else {
+        tokens
+            .iter()
+            .map(|(kind, token)| span(token.clone()).color(kind.color()))
+            .collect()
+    };
+    rich_text(spans)
+        .font(mono_font())
+        .size(TypeScale::Label.size())
+        .wrapping(Wrapping::None)
+}
@@ -0,0 +1,18 @@
+use iced::{
+    Element,
+    widget::{Row, row},
+};
+
+use crate::i18n::{Direction, layout_direction};
+
+/// A row whose children run right to left while the UI is in a right-to-left language, so
+/// sidebars and navigation end up on the side the reader starts from.
+pub fn directional_row<'a, M: 'a>(
+    children: impl IntoIterator<Item = Element<'a, M>>,
+) -> Row<'a, M> {
+    let mut children: Vec<_> = children.into_iter().collect();
+    if layout_direction() == Direction::Rtl {
+        children.reverse();
+    }
+    row(children)
+}
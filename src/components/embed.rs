@@ -0,0 +1,105 @@
+use iced::{
+    Alignment, Border, Color, ContentFit, Element,
+    Length::Fill,
+    Padding,
+    widget::{Column, button, container, horizontal_space, image},
+};
+
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorMain},
+    components::directional_row,
+    embeds::Embed,
+    i18n::{Direction, layout_direction},
+    media::{self, Lookup},
+    typography::{caption, label},
+};
+
+const EMBED_WIDTH: f32 = 440.0;
+const THUMBNAIL_WIDTH: f32 = 80.0;
+
+/// A link preview: the site, the page's title linking to it, its description and image, with
+/// a bar on its start side in the page's color.
+pub fn embed_card<'a, M: Clone + 'a>(
+    embed: &'a Embed,
+    media: &'a media::Cache,
+    on_open: impl Fn(String) -> M,
+) -> Element<'a, M> {
+    let subtext = Color::from(AppColorForeground::SubtextPrimary);
+    let title = embed.title.as_deref().map(|title| {
+        button(label(title))
+            .padding(0)
+            .on_press(on_open(embed.url.clone()))
+            .style(|_t, _s| button::Style {
+                text_color: AppColorMain::AccentPrimary.into(),
+                ..Default::default()
+            })
+    });
+    let details = Column::new()
+        .push_maybe(
+            embed
+                .site_name
+                .as_deref()
+                .map(|site| caption(site).color(subtext)),
+        )
+        .push_maybe(title)
+        .push_maybe(embed.description.as_deref().map(caption))
+        .spacing(4);
+
+    let thumbnail = embed.thumbnail_key().and_then(|key| match media.get(&key) {
+        Lookup::Loaded(handle) => Some(
+            image(handle.clone())
+                .content_fit(ContentFit::Cover)
+                .width(THUMBNAIL_WIDTH)
+                .height(THUMBNAIL_WIDTH)
+                .into(),
+        ),
+        Lookup::Loading => Some(
+            container(horizontal_space())
+                .width(THUMBNAIL_WIDTH)
+                .height(THUMBNAIL_WIDTH)
+                .style(|_t| container::Style {
+                    background: Some(AppColorBackground::SurfaceSecondary.to_bg()),
+                    ..Default::default()
+                })
+                .into(),
+        ),
+        Lookup::Failed => None,
+    });
+    let mut content: Vec<Element<'a, M>> = vec![details.width(Fill).into()];
+    content.extend(thumbnail);
+
+    // The bar is the outer background showing through the padding, like a quote's.
+    let bar = match layout_direction() {
+        Direction::Ltr => Padding::ZERO.left(4.0),
+        Direction::Rtl => Padding::ZERO.right(4.0),
+    };
+    let bar_color =
+        embed
+            .color
+            .map_or(Color::from(AppColorBackground::SurfaceSecondary), |color| {
+                let [_, r, g, b] = color.to_be_bytes();
+                Color::from_rgb8(r, g, b)
+            });
+    let card = container(
+        directional_row(content)
+            .spacing(12)
+            .align_y(Alignment::Start),
+    )
+    .padding([10, 12])
+    .style(|_t| container::Style {
+        background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+        ..Default::default()
+    });
+    container(card)
+        .max_width(EMBED_WIDTH)
+        .padding(bar)
+        .style(move |_t| container::Style {
+            background: Some(bar_color.into()),
+            border: Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
@@ -0,0 +1,198 @@
+use iced::{
+    Border, Color, Element,
+    Length::Fill,
+    widget::{button, column, container, image, row, scrollable, text, text_input},
+};
+
+use crate::{
+    colors::{AppColorBackground, AppColorForeground},
+    emoji::{self, Category, CustomEmoji, SkinTone},
+    i18n::tr,
+    reactions::ReactionEmoji,
+    typography::{TypeScale, caption},
+};
+
+const COLUMNS: usize = 8;
+const CELL_SIZE: f32 = 36.0;
+const GRID_HEIGHT: f32 = 240.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Tab {
+    #[default]
+    Recent,
+    Category(Category),
+    /// The guild's own emoji.
+    Custom,
+}
+
+/// Unicode emoji by category with search, a skin tone choice, the recently used ones, and
+/// the guild's custom emoji. Used both to add emoji to the composer and to react.
+#[derive(Debug, Clone, Default)]
+pub struct EmojiPicker {
+    query: String,
+    tab: Tab,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    QueryChanged(String),
+    TabSelected(Tab),
+    SkinToneSelected(SkinTone),
+    Picked(ReactionEmoji),
+}
+
+/// What the picker asks of whoever shows it.
+#[derive(Debug, Clone)]
+pub enum Event {
+    Picked(ReactionEmoji),
+    /// The new tone should be saved, it applies to every emoji picked after.
+    SkinToneChanged(SkinTone),
+}
+
+impl EmojiPicker {
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::QueryChanged(query) => {
+                self.query = query;
+                None
+            }
+            Message::TabSelected(tab) => {
+                self.query.clear();
+                self.tab = tab;
+                None
+            }
+            Message::SkinToneSelected(tone) => Some(Event::SkinToneChanged(tone)),
+            Message::Picked(emoji) => Some(Event::Picked(emoji)),
+        }
+    }
+
+    pub fn view<'a>(
+        &'a self,
+        tone: SkinTone,
+        recent: &'a [String],
+        custom: &'a [CustomEmoji],
+    ) -> Element<'a, Message> {
+        let search = text_input(&tr!("emoji-search"), &self.query)
+            .on_input(Message::QueryChanged)
+            .size(TypeScale::Label.size())
+            .padding([6, 8]);
+
+        let categories = Category::ALL.map(|category| (Tab::Category(category), category.icon()));
+        let tabs = std::iter::once((Tab::Recent, "🕘"))
+            .chain(categories)
+            .chain((!custom.is_empty()).then_some((Tab::Custom, "⭐")))
+            .map(|(tab, icon)| {
+                cell(
+                    text(icon).size(16),
+                    Message::TabSelected(tab),
+                    tab == self.tab,
+                )
+            });
+
+        let tones = SkinTone::ALL.map(|option| {
+            let sample = text(option.sample()).size(14);
+            cell(sample, Message::SkinToneSelected(option), option == tone)
+        });
+
+        let cells: Vec<Element<'a, Message>> = if !self.query.is_empty() {
+            let unicode = emoji::search(&self.query)
+                .into_iter()
+                .map(|emoji| unicode_cell(emoji.glyph(tone)));
+            let query = self.query.to_lowercase();
+            let custom = custom
+                .iter()
+                .filter(|emoji| emoji.name.to_lowercase().contains(&query))
+                .map(custom_cell);
+            unicode.chain(custom).collect()
+        } else {
+            match self.tab {
+                Tab::Recent => recent
+                    .iter()
+                    .map(|glyph| unicode_cell(glyph.clone()))
+                    .collect(),
+                Tab::Category(category) => emoji::in_category(category)
+                    .map(|emoji| unicode_cell(emoji.glyph(tone)))
+                    .collect(),
+                Tab::Custom => custom.iter().map(custom_cell).collect(),
+            }
+        };
+
+        let grid: Element<'a, Message> = if cells.is_empty() {
+            caption(tr!("emoji-none"))
+                .color(Color::from(AppColorForeground::SubtextPrimary))
+                .into()
+        } else {
+            let mut cells = cells.into_iter().peekable();
+            let mut rows: Vec<Element<'a, Message>> = Vec::new();
+            while cells.peek().is_some() {
+                rows.push(row(cells.by_ref().take(COLUMNS)).into());
+            }
+            scrollable(column(rows)).height(GRID_HEIGHT).into()
+        };
+
+        container(column![search, row(tabs), grid, row(tones)].spacing(8))
+            .width(CELL_SIZE * COLUMNS as f32 + 16.0)
+            .padding(8)
+            .style(|_t| container::Style {
+                background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+                border: Border {
+                    radius: 8.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .into()
+    }
+}
+
+fn unicode_cell<'a>(glyph: String) -> Element<'a, Message> {
+    let label = text(glyph.clone()).size(22);
+    cell(label, Message::Picked(ReactionEmoji::Unicode(glyph)), false)
+}
+
+fn custom_cell(emoji: &CustomEmoji) -> Element<'_, Message> {
+    let on_press = Message::Picked(ReactionEmoji::Custom {
+        id: emoji.id.clone(),
+        name: emoji.name.clone(),
+    });
+    let content = custom_emoji_image(&emoji.name, emoji.image.as_ref(), 24.0);
+    cell(content, on_press, false)
+}
+
+/// A custom emoji `size` pixels wide, shown by name until its image is downloaded.
+pub fn custom_emoji_image<'a, M: 'a>(
+    name: &str,
+    handle: Option<&image::Handle>,
+    size: f32,
+) -> Element<'a, M> {
+    match handle {
+        Some(handle) => image(handle.clone()).width(size).height(size).into(),
+        None => text(format!(":{name}:"))
+            .size(TypeScale::Small.size())
+            .into(),
+    }
+}
+
+fn cell<'a>(
+    content: impl Into<Element<'a, Message>>,
+    on_press: Message,
+    selected: bool,
+) -> Element<'a, Message> {
+    button(container(content).center(Fill))
+        .width(CELL_SIZE)
+        .height(CELL_SIZE)
+        .padding(0)
+        .on_press(on_press)
+        .style(move |_t, status| {
+            let highlighted = selected || matches!(status, button::Status::Hovered);
+            button::Style {
+                background: highlighted.then(|| AppColorBackground::SurfaceSecondary.to_bg()),
+                border: Border {
+                    radius: 6.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        })
+        .into()
+}
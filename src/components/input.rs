@@ -1,40 +1,45 @@
 use iced::{
     Color, Element,
     Length::Fill,
-    widget::{column, row, text, text_input},
+    widget::{column, row, text_input},
 };
 
-use crate::{RUBIK, colors::AppColorStatus, styles::input_style};
+use crate::{
+    colors::AppColorStatus,
+    styles::input_style,
+    typography::{self, ui_font},
+};
 
 pub fn styled_input<'a, M>(
     label: &str,
     value: &str,
     error: Option<&str>,
-    description: Option<&str>,
     on_input: impl Fn(String) -> M + 'a + Clone,
     //on_submit: impl Fn() -> M + 'a + Clone,
     is_secure: Option<bool>,
     is_required: Option<bool>,
+    is_disabled: Option<bool>,
 ) -> Element<'a, M>
 where
     M: Clone + 'a,
 {
     let password_confirm_input = text_input(label, value)
-        .on_input(on_input)
+        .on_input_maybe((!is_disabled.unwrap_or(false)).then_some(on_input))
         //.on_submit(on_submit)
         .line_height(1.2)
+        .font(ui_font())
         .width(Fill)
         .secure(is_secure.unwrap_or(false))
         .style(input_style)
         .padding(10);
 
-    let show_star = is_required.unwrap_or(false) && !error.is_some();
+    let show_star = is_required.unwrap_or(false) && error.is_none();
     let show_dash = error.is_some();
 
     column![
         row![
-            text!("{}", label).size(13).font(RUBIK),
-            text!(
+            typography::label(label.to_string()),
+            typography::label(format!(
                 "{} {}",
                 if show_star {
                     "*"
@@ -43,10 +48,9 @@ where
                 } else {
                     ""
                 },
-                if let Some(err) = error { err } else { "" }
-            )
+                error.unwrap_or_default()
+            ))
             .color(Color::from(AppColorStatus::Failure))
-            .size(13)
         ]
         .spacing(4),
         password_confirm_input
@@ -0,0 +1,290 @@
+use std::collections::BTreeSet;
+
+use iced::{
+    Border, Color, Element,
+    Length::Fill,
+    Padding,
+    font::{self, Weight},
+    widget::{
+        Column, column, container, rich_text, span,
+        text::{self, Rich},
+    },
+};
+
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorMain},
+    components::{code_block, directional_row},
+    constants::BORDER_RADIUS_SMALL,
+    highlight,
+    i18n::{Direction, layout_direction, tr},
+    markdown::{self, Block, Document, ListMarker, Mention},
+    mentions::Directory,
+    typography::{TypeScale, message, message_font, mono_font},
+};
+
+/// What clicking part of a message does.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageAction {
+    OpenUrl(String),
+    /// Reveals the spoiler with this index, see [`markdown::Span::spoiler`].
+    RevealSpoiler(usize),
+    CopyCode(String),
+    /// Expands or collapses the code block with this key, see [`Block::Code`].
+    ToggleCode(u64),
+}
+
+/// What's been clicked open in a message.
+#[derive(Debug, Clone, Default)]
+pub struct MessageState {
+    /// Spoilers stay hidden until clicked.
+    pub revealed_spoilers: BTreeSet<usize>,
+    /// Long code blocks stay collapsed until expanded.
+    pub expanded_code: BTreeSet<u64>,
+    /// Attachments marked as spoilers, by ID, stay covered until clicked.
+    pub revealed_attachments: BTreeSet<String>,
+}
+
+/// What every block of a message needs to draw itself.
+struct Context<'s, M> {
+    state: &'s MessageState,
+    highlights: &'s highlight::Cache,
+    directory: &'s Directory,
+    on_action: &'s dyn Fn(MessageAction) -> M,
+}
+
+/// The text of a chat message, rendered from its markdown. Each paragraph starts on the side
+/// of its own base direction, so a Hebrew or Arabic paragraph lines up on the right even in
+/// an English UI. Paragraphs with only neutral characters (numbers, emoji) follow the
+/// paragraph before them.
+///
+/// Reordering inside a line, e.g. English words or code in a Hebrew sentence, is done by the
+/// text shaper once the base direction is right.
+pub fn message_body<'a, M: Clone + 'static>(
+    document: &Document,
+    state: &MessageState,
+    highlights: &highlight::Cache,
+    directory: &Directory,
+    on_action: impl Fn(MessageAction) -> M,
+) -> Column<'a, M> {
+    let context = Context {
+        state,
+        highlights,
+        directory,
+        on_action: &on_action,
+    };
+    blocks(&document.blocks, &context)
+}
+
+fn blocks<'a, M: Clone + 'static>(blocks: &[Block], context: &Context<M>) -> Column<'a, M> {
+    let mut direction = layout_direction();
+    column(blocks.iter().map(|block| {
+        direction = block_direction(block).unwrap_or(direction);
+        block_view(block, direction, context)
+    }))
+    .spacing(2)
+}
+
+fn block_direction(block: &Block) -> Option<Direction> {
+    match block {
+        Block::Paragraph(content)
+        | Block::Heading { content, .. }
+        | Block::ListItem { content, .. } => {
+            let text: String = content.iter().map(|span| span.text.as_str()).collect();
+            Direction::of(&text)
+        }
+        Block::Quote(blocks) => blocks.iter().find_map(block_direction),
+        Block::Code { .. } => None,
+    }
+}
+
+/// Space on the side a line starts from.
+fn indent(amount: f32, direction: Direction) -> Padding {
+    match direction {
+        Direction::Ltr => Padding::ZERO.left(amount),
+        Direction::Rtl => Padding::ZERO.right(amount),
+    }
+}
+
+fn block_view<'a, M: Clone + 'static>(
+    block: &Block,
+    direction: Direction,
+    context: &Context<M>,
+) -> Element<'a, M> {
+    match block {
+        Block::Paragraph(content) => paragraph(content, false, context)
+            .width(Fill)
+            .align_x(direction.start())
+            .into(),
+        Block::Heading { level, content } => {
+            let size = match level {
+                1 => TypeScale::Heading,
+                2 => TypeScale::Title,
+                _ => TypeScale::Body,
+            };
+            paragraph(content, true, context)
+                .size(size.size())
+                .width(Fill)
+                .align_x(direction.start())
+                .into()
+        }
+        Block::ListItem {
+            marker,
+            depth,
+            content,
+        } => {
+            let marker = match marker {
+                ListMarker::Bullet => "•".to_string(),
+                ListMarker::Number(number) => format!("{number}."),
+            };
+            let step = TypeScale::Body.size();
+            container(
+                directional_row([
+                    message(marker).into(),
+                    paragraph(content, false, context)
+                        .width(Fill)
+                        .align_x(direction.start())
+                        .into(),
+                ])
+                .spacing(6),
+            )
+            .padding(indent(step * (*depth as f32 + 0.5), direction))
+            .into()
+        }
+        Block::Quote(quoted) => {
+            // The bar is the outer container's background showing through its padding, a
+            // `Fill` height rule would collapse to nothing inside a row sized by its text.
+            let inner = container(blocks(quoted, context))
+                .width(Fill)
+                .padding(indent(10.0, direction))
+                .style(|_t| container::Style {
+                    background: Some(AppColorBackground::Tertiary.to_bg()),
+                    ..Default::default()
+                });
+            container(inner)
+                .width(Fill)
+                .padding(indent(4.0, direction))
+                .style(|_t| container::Style {
+                    background: Some(AppColorBackground::SurfaceTertiary.to_bg()),
+                    border: Border {
+                        radius: 2.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .into()
+        }
+        // Code reads left to right whatever language the message is in.
+        Block::Code {
+            language,
+            code,
+            key,
+        } => code_block(
+            language.as_deref(),
+            code,
+            context.highlights.get(*key),
+            context.state.expanded_code.contains(key),
+            (context.on_action)(MessageAction::CopyCode(code.clone())),
+            (context.on_action)(MessageAction::ToggleCode(*key)),
+        ),
+    }
+}
+
+fn paragraph<'a, M: Clone + 'static>(
+    content: &[markdown::Span],
+    bold: bool,
+    context: &Context<M>,
+) -> Rich<'a, M> {
+    let spans: Vec<_> = content
+        .iter()
+        .map(|content| styled_span(content, bold, context))
+        .collect();
+    rich_text(spans)
+        .font(message_font())
+        .size(TypeScale::Body.size())
+}
+
+fn styled_span<M>(
+    content: &markdown::Span,
+    bold: bool,
+    context: &Context<M>,
+) -> text::Span<'static, M> {
+    let style = content.style;
+    let mut font = if style.code {
+        mono_font()
+    } else {
+        message_font()
+    };
+    if bold || style.bold {
+        font.weight = Weight::Bold;
+    }
+    if style.italic {
+        font.style = font::Style::Italic;
+    }
+
+    let text = match &content.mention {
+        Some(mention) => mention_label(mention, context.directory),
+        None => content.text.clone(),
+    };
+    let mut result = span(text)
+        .font(font)
+        .underline(style.underline || content.link.is_some())
+        .strikethrough(style.strikethrough);
+
+    if style.code {
+        result = result
+            .size(TypeScale::Label.size())
+            .background(AppColorBackground::SurfacePrimary.to_bg())
+            .border(Border {
+                radius: BORDER_RADIUS_SMALL.into(),
+                ..Default::default()
+            })
+            .padding([0, 3]);
+    }
+
+    if let Some(url) = &content.link {
+        result = result
+            .color(AppColorMain::AccentPrimary)
+            .link((context.on_action)(MessageAction::OpenUrl(url.clone())));
+    }
+
+    if content.mention.is_some() {
+        result = result
+            .color(AppColorMain::AccentPrimary)
+            .background(Color::from(AppColorMain::AccentPrimary).scale_alpha(0.2))
+            .border(Border {
+                radius: BORDER_RADIUS_SMALL.into(),
+                ..Default::default()
+            })
+            .padding([0, 2]);
+    }
+
+    match content.spoiler {
+        // Hidden text is drawn in the color of its background, clicking it reveals it.
+        Some(spoiler) if !context.state.revealed_spoilers.contains(&spoiler) => result
+            .color(AppColorBackground::SurfaceTertiary)
+            .background(AppColorBackground::SurfaceTertiary.to_bg())
+            .link((context.on_action)(MessageAction::RevealSpoiler(spoiler))),
+        Some(_) => result.background(Color::from(AppColorForeground::Primary).scale_alpha(0.1)),
+        None => result,
+    }
+}
+
+/// The name a mention is shown with, in place of its token.
+fn mention_label(mention: &Mention, directory: &Directory) -> String {
+    match mention {
+        Mention::User(id) => match directory.member(id) {
+            Some(member) => format!("@{}", member.name),
+            None => format!("@{}", tr!("mention-unknown-user")),
+        },
+        Mention::Role(id) => match directory.role(id) {
+            Some(role) => format!("@{}", role.name),
+            None => format!("@{}", tr!("mention-unknown-role")),
+        },
+        Mention::Channel(id) => match directory.channel(id) {
+            Some(channel) => format!("#{}", channel.name),
+            None => format!("#{}", tr!("mention-unknown-channel")),
+        },
+        Mention::Everyone => "@everyone".to_string(),
+        Mention::Here => "@here".to_string(),
+    }
+}
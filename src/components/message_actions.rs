@@ -0,0 +1,152 @@
+use iced::{
+    Border, Element,
+    Length::Fill,
+    Theme,
+    widget::{button, column, container, text, tooltip},
+};
+
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorStatus},
+    components::directional_row,
+    i18n::tr,
+    typography::TypeScale,
+};
+
+const MENU_WIDTH: f32 = 200.0;
+
+/// Something to do with a message, picked from its toolbar or context menu.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MessageCommand {
+    Reply,
+    React,
+    /// Opens the thread started from the message, starting one if there's none yet.
+    StartThread,
+    Edit,
+    Delete,
+    Pin,
+    Unpin,
+    CopyText,
+    CopyLink,
+    CopyId,
+    /// Marks the message and everything after it as unread.
+    MarkUnread,
+    /// Hides the message's link previews, for everyone when it's on the server.
+    RemoveEmbeds,
+    /// Opens the context menu without right-clicking.
+    OpenMenu,
+}
+
+impl MessageCommand {
+    fn label(self) -> String {
+        match self {
+            MessageCommand::Reply => tr!("message-reply"),
+            MessageCommand::React => tr!("message-react"),
+            MessageCommand::StartThread => tr!("message-start-thread"),
+            MessageCommand::Edit => tr!("message-edit"),
+            MessageCommand::Delete => tr!("message-delete"),
+            MessageCommand::Pin => tr!("message-pin"),
+            MessageCommand::Unpin => tr!("message-unpin"),
+            MessageCommand::CopyText => tr!("message-copy-text"),
+            MessageCommand::CopyLink => tr!("message-copy-link"),
+            MessageCommand::CopyId => tr!("message-copy-id"),
+            MessageCommand::MarkUnread => tr!("message-mark-unread"),
+            MessageCommand::RemoveEmbeds => tr!("message-remove-embeds"),
+            MessageCommand::OpenMenu => tr!("message-more"),
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            MessageCommand::Reply => "↩",
+            MessageCommand::React => "😀",
+            MessageCommand::StartThread => "🧵",
+            MessageCommand::Edit => "✏",
+            MessageCommand::Delete => "🗑",
+            MessageCommand::Pin | MessageCommand::Unpin => "📌",
+            MessageCommand::CopyText | MessageCommand::CopyLink | MessageCommand::CopyId => "📋",
+            MessageCommand::MarkUnread => "✉",
+            MessageCommand::RemoveEmbeds => "🔗",
+            MessageCommand::OpenMenu => "⋯",
+        }
+    }
+}
+
+/// The buttons on a hovered message, each labelled by a tooltip. Which commands there are is
+/// up to the caller, e.g. only the author may edit a message.
+pub fn message_toolbar<'a, M: Clone + 'a>(
+    commands: impl IntoIterator<Item = MessageCommand>,
+    on_command: impl Fn(MessageCommand) -> M,
+) -> Element<'a, M> {
+    let buttons = commands.into_iter().map(|command| {
+        let icon = button(text(command.icon()).size(TypeScale::Label.size()))
+            .padding([2, 6])
+            .on_press(on_command(command))
+            .style(|_t, status| item_style(status, false));
+        let label = container(text(command.label()).size(TypeScale::Caption.size()))
+            .padding([4, 8])
+            .style(|_t| container::Style {
+                background: Some(AppColorBackground::Primary.to_bg()),
+                text_color: Some(AppColorForeground::Primary.into()),
+                border: Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        tooltip(icon, label, tooltip::Position::Top).into()
+    });
+
+    container(directional_row(buttons).spacing(2))
+        .padding(2)
+        .style(surface_style)
+        .into()
+}
+
+/// Everything that can be done with a message, opened by right-clicking it.
+pub fn context_menu<'a, M: Clone + 'a>(
+    commands: impl IntoIterator<Item = MessageCommand>,
+    on_command: impl Fn(MessageCommand) -> M,
+) -> Element<'a, M> {
+    let items = commands.into_iter().map(|command| {
+        let danger = command == MessageCommand::Delete;
+        button(text(command.label()).size(TypeScale::Label.size()))
+            .width(Fill)
+            .padding([6, 10])
+            .on_press(on_command(command))
+            .style(move |_t, status| item_style(status, danger))
+            .into()
+    });
+
+    container(column(items).width(MENU_WIDTH))
+        .padding(4)
+        .style(surface_style)
+        .into()
+}
+
+fn surface_style(_theme: &Theme) -> container::Style {
+    container::Style {
+        background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+        border: Border {
+            radius: 6.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn item_style(status: button::Status, danger: bool) -> button::Style {
+    let hovered = matches!(status, button::Status::Hovered);
+    button::Style {
+        background: hovered.then(|| AppColorBackground::SurfaceSecondary.to_bg()),
+        text_color: if danger {
+            AppColorStatus::Failure.into()
+        } else {
+            AppColorForeground::Primary.into()
+        },
+        border: Border {
+            radius: 4.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
@@ -1,3 +1,23 @@
+pub mod attachments;
+pub mod autocomplete;
+pub mod code_block;
+pub mod directional_row;
+pub mod embed;
+pub mod emoji_picker;
 pub mod input;
+pub mod message;
+pub mod message_actions;
+pub mod password_strength;
+pub mod reactions;
 
+pub use attachments::{attachment_list, upload_tray};
+pub use autocomplete::autocomplete;
+pub use code_block::code_block;
+pub use directional_row::directional_row;
+pub use embed::embed_card;
+pub use emoji_picker::EmojiPicker;
 pub use input::styled_input;
+pub use message::{MessageAction, MessageState, message_body};
+pub use message_actions::{MessageCommand, context_menu, message_toolbar};
+pub use password_strength::{password_score, password_strength};
+pub use reactions::reaction_bar;
@@ -0,0 +1,72 @@
+use iced::{
+    Border, Color, Element,
+    Length::Fill,
+    widget::{column, container, row, text},
+};
+
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorStatus},
+    constants::BORDER_RADIUS_SMALL,
+    i18n::tr,
+    typography::TypeScale,
+};
+
+/// Scores a password from 0 (very weak) to 4 (very strong), or `None` when it is empty.
+pub fn password_score(password: &str) -> Option<u8> {
+    if password.is_empty() {
+        return None;
+    }
+    Some(zxcvbn::zxcvbn(password, &[]).score() as u8)
+}
+
+pub fn password_strength<'a, M>(score: Option<u8>) -> Element<'a, M>
+where
+    M: 'a,
+{
+    let Some(score) = score else {
+        return column![].into();
+    };
+
+    let score = score.min(4);
+    let status = match score {
+        0 | 1 => AppColorStatus::Failure,
+        2 => AppColorStatus::Warning,
+        _ => AppColorStatus::Success,
+    };
+    let filled = score.max(1);
+
+    let bars = row((1..=4).map(|segment| {
+        let background = if segment <= filled {
+            status.to_bg()
+        } else {
+            AppColorBackground::SurfaceSecondary.to_bg()
+        };
+        container(column![])
+            .width(Fill)
+            .height(4)
+            .style(move |_t| container::Style {
+                background: Some(background),
+                border: Border {
+                    radius: BORDER_RADIUS_SMALL.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .into()
+    }))
+    .spacing(4);
+
+    column![
+        bars,
+        row![
+            text(tr!("password-strength"))
+                .size(TypeScale::Caption.size())
+                .color(Color::from(AppColorForeground::SubtextPrimary)),
+            text(tr!(&format!("password-strength-{}", score)))
+                .size(TypeScale::Caption.size())
+                .color(Color::from(status)),
+        ]
+    ]
+    .spacing(6)
+    .into()
+}
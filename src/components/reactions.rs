@@ -0,0 +1,113 @@
+use iced::{
+    Alignment, Border, Color, Element,
+    widget::{Row, button, container, row, text, tooltip},
+};
+
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorMain},
+    components::{directional_row, emoji_picker::custom_emoji_image},
+    i18n::tr,
+    mentions::Directory,
+    reactions::{Reaction, ReactionEmoji, Reactions},
+    styles::button_secondary_style,
+    typography::TypeScale,
+};
+
+/// How many names the tooltip of a reaction lists before summing up the rest.
+const NAMED_USERS: usize = 3;
+
+/// The reactions under a message with their counts, followed by a button to add one. The
+/// ones the user added are outlined, clicking a reaction adds or takes back the user's own,
+/// and hovering it lists who reacted.
+pub fn reaction_bar<'a, M: Clone + 'a>(
+    reactions: &Reactions,
+    user_id: Option<&str>,
+    directory: &Directory,
+    on_toggle: impl Fn(ReactionEmoji) -> M,
+    on_add: M,
+) -> Row<'a, M> {
+    let chips = reactions.iter().map(|reaction| {
+        let mine = user_id.is_some_and(|id| reaction.includes(id));
+        let chip = button(
+            row![
+                emoji(&reaction.emoji, directory),
+                text(reaction.count().to_string()).size(TypeScale::Label.size()),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+        )
+        .padding([2, 8])
+        .on_press(on_toggle(reaction.emoji.clone()))
+        .style(move |_t, _s| button::Style {
+            background: Some(if mine {
+                Color::from(AppColorMain::AccentPrimary)
+                    .scale_alpha(0.2)
+                    .into()
+            } else {
+                AppColorBackground::SurfacePrimary.to_bg()
+            }),
+            text_color: AppColorForeground::Primary.into(),
+            border: Border {
+                color: if mine {
+                    AppColorMain::AccentPrimary.into()
+                } else {
+                    Color::TRANSPARENT
+                },
+                width: 1.0,
+                radius: 8.0.into(),
+            },
+            ..Default::default()
+        });
+
+        let names = text(who_reacted(reaction, directory)).size(TypeScale::Caption.size());
+        let who = container(names)
+            .padding([4, 8])
+            .style(|_t| container::Style {
+                background: Some(AppColorBackground::Primary.to_bg()),
+                text_color: Some(AppColorForeground::Primary.into()),
+                border: Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        tooltip(chip, who, tooltip::Position::Top).into()
+    });
+
+    let add = button(text("+").size(TypeScale::Label.size()))
+        .padding([2, 8])
+        .on_press(on_add)
+        .style(button_secondary_style);
+
+    directional_row(chips.chain(std::iter::once(add.into())))
+        .spacing(4)
+        .align_y(Alignment::Center)
+}
+
+fn emoji<'a, M: 'a>(emoji: &ReactionEmoji, directory: &Directory) -> Element<'a, M> {
+    match emoji {
+        ReactionEmoji::Unicode(glyph) => text(glyph.clone()).size(16).into(),
+        ReactionEmoji::Custom { id, name } => {
+            let handle = directory
+                .emoji
+                .iter()
+                .find(|custom| &custom.id == id)
+                .and_then(|custom| custom.image.as_ref());
+            custom_emoji_image(name, handle, 16.0)
+        }
+    }
+}
+
+fn who_reacted(reaction: &Reaction, directory: &Directory) -> String {
+    let names: Vec<String> = reaction
+        .users
+        .iter()
+        .take(NAMED_USERS)
+        .map(|id| match directory.member(id) {
+            Some(member) => member.name.clone(),
+            None => tr!("mention-unknown-user"),
+        })
+        .collect();
+    let others = reaction.count().saturating_sub(NAMED_USERS);
+    tr!("reaction-who", names = names.join(", "), others = others)
+}
@@ -0,0 +1,379 @@
+use iced::{
+    Subscription,
+    futures::{SinkExt, Stream},
+    stream,
+};
+use notify::{RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    path::PathBuf,
+    sync::{Mutex, PoisonError},
+    time::Duration,
+};
+
+use crate::{
+    constants::PREVIEW_PROXY_URL,
+    emoji::SkinTone,
+    i18n::{self, Language, tr},
+    keybindings::Keybindings,
+    theme::{ColorScheme, DEFAULT_DARK_THEME, DEFAULT_LIGHT_THEME},
+    threads::AutoArchive,
+    typography::{DEFAULT_BASE_SIZE, FontChoice, MAX_BASE_SIZE, MIN_BASE_SIZE, Typography},
+};
+
+pub const MIN_UI_SCALE: f64 = 0.5;
+pub const MAX_UI_SCALE: f64 = 2.0;
+pub const UI_SCALE_STEP: f64 = 0.1;
+
+/// Bumped whenever a field is renamed or changes meaning, see [`migrate`].
+pub const SETTINGS_VERSION: u32 = 2;
+/// Settings typed into a text input are saved once typing pauses for this long.
+pub const TYPING_SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// The app's own writes to the settings file, so the watcher can tell them from edits made
+/// elsewhere and doesn't reload settings that are older than the ones in memory.
+struct Saves {
+    /// What was written last.
+    last: Option<String>,
+    /// Saves still writing, the file changes under them until they're done.
+    writing: usize,
+}
+
+static SAVES: Mutex<Saves> = Mutex::new(Saves {
+    last: None,
+    writing: 0,
+});
+
+/// User preferences stored in `settings.toml` in the config directory.
+/// Missing fields fall back to their defaults so older files keep loading.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    pub appearance: Appearance,
+    /// Id of a bundled or user palette, see [`crate::theme::ThemeEntry`].
+    pub light_theme: String,
+    pub dark_theme: String,
+    /// Used with [`Appearance::System`] when the desktop doesn't report a preference.
+    pub system_fallback: ColorScheme,
+    pub ui_font: FontChoice,
+    /// Used for the text of chat messages.
+    pub message_font: FontChoice,
+    /// Used for inline code and code blocks.
+    pub mono_font: FontChoice,
+    /// Multiplies every size in the UI, changed with Ctrl+= / Ctrl+- / Ctrl+0.
+    pub ui_scale: f64,
+    /// Size of body text in logical pixels, other text styles scale with it.
+    pub base_font_size: u16,
+    pub reduced_motion: bool,
+    pub high_contrast: bool,
+    pub density: Density,
+    pub keybindings: Keybindings,
+    /// Id of one of [`i18n::LANGUAGES`], `None` follows the system language.
+    pub language: Option<String>,
+    pub skin_tone: SkinTone,
+    /// Emoji picked from the picker, most recent first, see [`crate::emoji::remember`].
+    pub recent_emoji: Vec<String>,
+    /// How soon new threads archive themselves, whatever was last picked for a thread.
+    pub thread_auto_archive: AutoArchive,
+    /// Shows previews of links in messages, the server's embeds and those made here.
+    pub link_previews: bool,
+    /// Fetches linked pages for the previews made here, see [`crate::embeds`].
+    pub link_preview_proxy: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            appearance: Appearance::default(),
+            light_theme: DEFAULT_LIGHT_THEME.to_string(),
+            dark_theme: DEFAULT_DARK_THEME.to_string(),
+            system_fallback: ColorScheme::default(),
+            ui_font: Typography::default().ui,
+            message_font: Typography::default().message,
+            mono_font: Typography::default().mono,
+            ui_scale: 1.0,
+            base_font_size: DEFAULT_BASE_SIZE,
+            reduced_motion: false,
+            high_contrast: false,
+            density: Density::default(),
+            keybindings: Keybindings::default(),
+            language: None,
+            skin_tone: SkinTone::default(),
+            recent_emoji: Vec::new(),
+            thread_auto_archive: AutoArchive::default(),
+            link_previews: true,
+            link_preview_proxy: PREVIEW_PROXY_URL.to_string(),
+        }
+    }
+}
+
+impl Settings {
+    /// The scheme to draw with, given what the desktop currently prefers.
+    pub fn color_scheme(&self, system: Option<ColorScheme>) -> ColorScheme {
+        match self.appearance {
+            Appearance::Light => ColorScheme::Light,
+            Appearance::Dark => ColorScheme::Dark,
+            Appearance::System => system.unwrap_or(self.system_fallback),
+        }
+    }
+
+    pub fn typography(&self) -> Typography {
+        Typography {
+            ui: self.ui_font,
+            message: self.message_font,
+            mono: self.mono_font,
+            base_size: self.base_font_size.clamp(MIN_BASE_SIZE, MAX_BASE_SIZE),
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        i18n::language(self.language.as_deref())
+    }
+
+    /// The scale factor handed to iced, clamped in case the file holds an unusable value.
+    pub fn scale_factor(&self) -> f64 {
+        if self.ui_scale.is_finite() {
+            self.ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+        } else {
+            1.0
+        }
+    }
+
+    /// Steps the UI scale up or down, rounding so repeated zooming doesn't drift.
+    pub fn zoom(&mut self, steps: i32) {
+        let scale = self.scale_factor() + f64::from(steps) * UI_SCALE_STEP;
+        self.ui_scale = ((scale * 10.0).round() / 10.0).clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+    }
+
+    pub fn theme_for(&self, scheme: ColorScheme) -> &str {
+        match scheme {
+            ColorScheme::Light => &self.light_theme,
+            ColorScheme::Dark => &self.dark_theme,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Appearance {
+    Light,
+    Dark,
+    #[default]
+    System,
+}
+
+impl Appearance {
+    pub const ALL: [Appearance; 3] = [Appearance::Light, Appearance::Dark, Appearance::System];
+}
+
+impl fmt::Display for Appearance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Appearance::Light => tr!("appearance-light"),
+            Appearance::Dark => tr!("appearance-dark"),
+            Appearance::System => tr!("appearance-system"),
+        };
+        f.write_str(&label)
+    }
+}
+
+/// How much room each chat message takes up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Density {
+    Compact,
+    #[default]
+    Cozy,
+}
+
+impl Density {
+    pub const ALL: [Density; 2] = [Density::Compact, Density::Cozy];
+
+    /// Vertical space between two messages.
+    pub fn message_spacing(self) -> f32 {
+        match self {
+            Density::Compact => 2.0,
+            Density::Cozy => 12.0,
+        }
+    }
+
+    /// Padding around the message list.
+    pub fn message_padding(self) -> f32 {
+        match self {
+            Density::Compact => 8.0,
+            Density::Cozy => 16.0,
+        }
+    }
+}
+
+impl fmt::Display for Density {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Density::Compact => tr!("appearance-density-compact"),
+            Density::Cozy => tr!("appearance-density-cozy"),
+        };
+        f.write_str(&label)
+    }
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join("fictional-potato"))
+}
+
+pub fn settings_path() -> Option<PathBuf> {
+    Some(config_dir()?.join("settings.toml"))
+}
+
+/// Brings a parsed settings file up to [`SETTINGS_VERSION`] one version at a time.
+fn migrate(table: &mut toml::Table) {
+    let mut version = table
+        .get("version")
+        .and_then(toml::Value::as_integer)
+        .unwrap_or(0) as u32;
+
+    if version > SETTINGS_VERSION {
+        tracing::warn!(
+            version,
+            supported = SETTINGS_VERSION,
+            "Settings file is newer than this build, unknown fields will be ignored"
+        );
+        return;
+    }
+
+    while version < SETTINGS_VERSION {
+        match version {
+            // Files written before versioning only lack the `version` key.
+            0 => {}
+            // `theme` was split into a light and a dark theme chosen by `appearance`.
+            1 => {
+                if let Some(theme) = table.remove("theme") {
+                    let (key, appearance) = match theme.as_str() {
+                        Some("latte") => ("light_theme", "light"),
+                        _ => ("dark_theme", "dark"),
+                    };
+                    table.insert(key.to_string(), theme);
+                    table.insert("appearance".to_string(), appearance.into());
+                }
+            }
+            _ => unreachable!("no migration from settings version {version}"),
+        }
+        version += 1;
+        tracing::info!(version, "Migrated settings file");
+    }
+
+    table.insert("version".to_string(), toml::Value::Integer(version.into()));
+}
+
+pub fn parse(contents: &str) -> Result<Settings, String> {
+    let mut table = contents
+        .parse::<toml::Table>()
+        .map_err(|e| format!("Invalid settings file: {}", e))?;
+    migrate(&mut table);
+    toml::Value::Table(table)
+        .try_into()
+        .map_err(|e| format!("Invalid settings file: {}", e))
+}
+
+/// Reads the settings file, falling back to the defaults when it does not exist yet.
+pub async fn load() -> Result<Settings, String> {
+    let path = settings_path().ok_or("No config directory on this system")?;
+    match tokio::fs::read_to_string(&path).await {
+        Ok(contents) => parse(&contents),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Settings::default()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// Writes to a temporary file first so a crash never leaves a truncated settings file.
+pub async fn save(settings: Settings) -> Result<(), String> {
+    let path = settings_path().ok_or("No config directory on this system")?;
+    let contents = toml::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+    {
+        let mut saves = SAVES.lock().unwrap_or_else(PoisonError::into_inner);
+        saves.last = Some(contents.clone());
+        saves.writing += 1;
+    }
+    let result = write(path, contents).await;
+    SAVES.lock().unwrap_or_else(PoisonError::into_inner).writing -= 1;
+    result
+}
+
+async fn write(path: PathBuf, contents: String) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let temp = path.with_extension("toml.tmp");
+    tokio::fs::write(&temp, contents)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", temp.display(), e))?;
+    tokio::fs::rename(&temp, &path)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn watch_settings_file() -> impl Stream<Item = Result<Settings, String>> {
+    stream::channel(10, |mut output| async move {
+        let (Some(dir), Some(path)) = (config_dir(), settings_path()) else {
+            return;
+        };
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            tracing::warn!(error = %e, "Not watching the settings file");
+            return;
+        }
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = sender.send(event);
+        });
+        // The watcher stops when dropped, so it has to live as long as the loop below.
+        let mut watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                tracing::warn!(error = %e, "Not watching the settings file");
+                return;
+            }
+        };
+        // Editors often replace the file instead of writing to it, so watch the directory.
+        if let Err(e) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+            tracing::warn!(error = %e, "Not watching the settings file");
+            return;
+        }
+
+        while let Some(event) = receiver.recv().await {
+            let Ok(event) = event else {
+                continue;
+            };
+            if !event.paths.iter().any(|changed| changed == &path) {
+                continue;
+            }
+            let contents = match tokio::fs::read_to_string(&path).await {
+                Ok(contents) => contents,
+                Err(_) => continue,
+            };
+            if is_own_write(&contents) {
+                continue;
+            }
+            let _ = output.send(parse(&contents)).await;
+        }
+    })
+}
+
+/// Whether the settings file changed because of [`save`]: while a save is writing, or when it
+/// holds what was saved last.
+fn is_own_write(contents: &str) -> bool {
+    let saves = SAVES.lock().unwrap_or_else(PoisonError::into_inner);
+    saves.writing > 0 || saves.last.as_deref() == Some(contents)
+}
+
+/// Emits the freshly parsed settings whenever the file changes on disk, except for the app's
+/// own saves.
+pub fn watch() -> Subscription<Result<Settings, String>> {
+    Subscription::run(watch_settings_file)
+}
@@ -1,4 +1,12 @@
 pub const BORDER_RADIUS_SMALL: f32 = 2.0;
 pub const BORDER_RADIUS: f32 = 4.0;
-pub const BORDER_RADIUS_BIG: f32 = 8.0;
 pub const BORDER_RADIUS_FULL: f32 = 255.0;
+
+/// Width of the login, register and password reset forms.
+pub const FORM_WIDTH: f32 = 550.0;
+
+pub const API_URL: &str = "http://localhost:8585";
+/// Where links to messages point, for sharing them outside the app.
+pub const WEB_URL: &str = "http://localhost:8080";
+/// Fetches linked pages for link previews, so the sites see it instead of the user.
+pub const PREVIEW_PROXY_URL: &str = "http://localhost:8585/link-preview";
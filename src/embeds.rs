@@ -0,0 +1,419 @@
+use iced::Task;
+use reqwest::Url;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::{markdown::Document, media};
+
+/// Longer descriptions are cut short, a preview isn't the page.
+const DESCRIPTION_LENGTH: usize = 300;
+/// Link preview thumbnails are scaled down to fit this.
+const THUMBNAIL_SIZE: u32 = 160;
+
+/// A preview of a link in a message, sent by the server or read from the page's OpenGraph
+/// tags.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Embed {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub site_name: Option<String>,
+    /// An image of the page, fetched through the proxy for previews made here.
+    pub thumbnail: Option<String>,
+    /// Of the bar on its side, as `0xRRGGBB`.
+    pub color: Option<u32>,
+}
+
+impl Embed {
+    /// The page's image scaled down to show in the preview.
+    pub fn thumbnail_key(&self) -> Option<media::Key> {
+        let url = self.thumbnail.clone()?;
+        Some(media::Key::thumbnail(url, THUMBNAIL_SIZE))
+    }
+}
+
+/// Link previews made here for links the server didn't send an embed for, keyed by URL so a
+/// page is fetched once however often it's linked. `None` while the page is fetched, and for
+/// pages without anything to preview.
+#[derive(Debug, Clone, Default)]
+pub struct Previews {
+    entries: HashMap<String, Option<Embed>>,
+}
+
+impl Previews {
+    pub fn get(&self, url: &str) -> Option<&Embed> {
+        self.entries.get(url)?.as_ref()
+    }
+
+    pub fn insert(&mut self, url: String, embed: Option<Embed>) {
+        self.entries.insert(url, embed);
+    }
+
+    /// Fetches the pages linked in `document` that aren't previewed yet through `proxy`, so
+    /// the sites see the proxy instead of the user. Links in `skip` already have an embed.
+    pub fn request(
+        &mut self,
+        proxy: &str,
+        document: &Document,
+        skip: &[Embed],
+    ) -> Task<(String, Option<Embed>)> {
+        let links = document
+            .links()
+            .into_iter()
+            .filter(|url| !skip.iter().any(|embed| embed.url == *url));
+        let tasks = links.filter_map(|url| {
+            if self.entries.contains_key(url) {
+                return None;
+            }
+            let url = url.to_string();
+            self.entries.insert(url.clone(), None);
+            let preview = fetch_preview(proxy.to_string(), url.clone());
+            Some(Task::perform(preview, move |embed| (url.clone(), embed)))
+        });
+        Task::batch(tasks)
+    }
+}
+
+/// Where `url` is fetched from through the proxy, `None` when the proxy isn't a URL.
+fn proxied(proxy: &str, url: &str) -> Option<String> {
+    Url::parse_with_params(proxy, [("url", url)])
+        .ok()
+        .map(String::from)
+}
+
+async fn fetch_preview(proxy: String, url: String) -> Option<Embed> {
+    let page = proxied(&proxy, &url)?;
+    let fetched = async {
+        reqwest::get(page)
+            .await
+            .and_then(|response| response.error_for_status())?
+            .text()
+            .await
+    };
+    match fetched.await {
+        Ok(html) => opengraph(&proxy, &url, &html),
+        Err(e) => {
+            tracing::warn!(error = %e, %url, "Failed to fetch link preview");
+            None
+        }
+    }
+}
+
+/// A preview from the page's OpenGraph tags, falling back on its `<title>` and description.
+/// `None` for pages with neither a title nor a description.
+fn opengraph(proxy: &str, url: &str, html: &str) -> Option<Embed> {
+    let page = Url::parse(url).ok()?;
+    let tags = meta_tags(html);
+    let tag = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| tags.get(*name))
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let title = tag(&["og:title", "twitter:title"]).or_else(|| title_tag(html));
+    let description = tag(&["og:description", "twitter:description", "description"]);
+    if title.is_none() && description.is_none() {
+        return None;
+    }
+    // Relative to the page, and loaded through the proxy like the page.
+    let thumbnail = tag(&["og:image", "og:image:url", "twitter:image"])
+        .and_then(|image| page.join(&image).ok())
+        .filter(|image| matches!(image.scheme(), "http" | "https"))
+        .and_then(|image| proxied(proxy, image.as_str()));
+
+    Some(Embed {
+        url: url.to_string(),
+        title,
+        description: description.map(|description| shorten(&description)),
+        site_name: tag(&["og:site_name"]).or_else(|| page.host_str().map(str::to_string)),
+        thumbnail,
+        color: tag(&["theme-color"]).and_then(|color| parse_color(&color)),
+    })
+}
+
+fn shorten(text: &str) -> String {
+    let mut short: String = text.chars().take(DESCRIPTION_LENGTH).collect();
+    if short.len() < text.len() {
+        short.push('…');
+    }
+    short
+}
+
+/// `#rrggbb`, the only form of `theme-color` used much.
+fn parse_color(color: &str) -> Option<u32> {
+    let hex = color.strip_prefix('#').filter(|hex| hex.len() == 6)?;
+    u32::from_str_radix(hex, 16).ok()
+}
+
+/// The `<meta>` tags in the page's head, by `property` or `name`, lowercased. The first of
+/// each wins, like for the sites that read them.
+fn meta_tags(html: &str) -> HashMap<String, String> {
+    // Lowercasing ASCII keeps every index the same.
+    let lower = html.to_ascii_lowercase();
+    let head = lower.find("</head>").unwrap_or(lower.len());
+    let mut tags = HashMap::new();
+    let mut from = 0;
+    while let Some(start) = lower[from..head].find("<meta") {
+        let start = from + start + "<meta".len();
+        // A tag left open runs to the end of the head at most.
+        let Some(end) = lower[start..head].find('>') else {
+            break;
+        };
+        let attributes = attributes(&html[start..start + end]);
+        let key = attributes.get("property").or(attributes.get("name"));
+        if let (Some(key), Some(content)) = (key, attributes.get("content")) {
+            tags.entry(key.to_ascii_lowercase())
+                .or_insert_with(|| unescape(content));
+        }
+        from = start + end;
+    }
+    tags
+}
+
+/// A tag's attributes by lowercased name, with their values in double, single or no quotes.
+fn attributes(tag: &str) -> HashMap<String, &str> {
+    let mut attributes = HashMap::new();
+    let mut rest = tag;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+        if rest.is_empty() {
+            break;
+        }
+        let name_end = rest
+            .find(|c: char| c == '=' || c == '/' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+
+        let mut value = "";
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (quoted, end) = match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let inner = &after[1..];
+                    let end = inner.find(quote).unwrap_or(inner.len());
+                    (&inner[..end], (end + 2).min(after.len()))
+                }
+                _ => {
+                    let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                    (&after[..end], end)
+                }
+            };
+            value = quoted;
+            rest = &after[end..];
+        }
+        if !name.is_empty() {
+            attributes.insert(name, value);
+        }
+    }
+    attributes
+}
+
+fn title_tag(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title>")?;
+    let title = unescape(html[start..end].trim());
+    (!title.is_empty()).then_some(title)
+}
+
+/// The entities pages use in titles and descriptions, `&amp;` last so `&amp;lt;` stays `&lt;`.
+fn unescape(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    const OPENGRAPH: &str = r##"<!doctype html>
+<html><head>
+<title>Ignored when og:title is there</title>
+<meta property="og:title" content="Release notes">
+<meta property="og:title" content="Second title, ignored">
+<meta property="og:description" content='Fixes &amp; improvements for &quot;everyone&quot;'>
+<meta property="og:site_name" content="Potato Blog">
+<meta property="og:image" content="/images/cover.png">
+<meta name="theme-color" content="#5865F2">
+</head><body><meta property="og:title" content="In the body, ignored"></body></html>"##;
+
+    const FALLBACK: &str = r#"<html><head>
+<TITLE> Plain &lt;page&gt; </TITLE>
+<meta name=description content=Unquoted>
+</head></html>"#;
+
+    const NOTHING: &str = "<html><head><meta charset=utf-8></head><body>Hi</body></html>";
+
+    /// A local stand-in for the link preview proxy, serving `pages` by the path of the URL
+    /// it's asked for and a 404 for anything else.
+    async fn proxy(pages: &'static [(&'static str, u16, &'static str)]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut request = vec![0; 8192];
+                    let read = socket.read(&mut request).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&request[..read]);
+                    let target = request.split_whitespace().nth(1).unwrap_or("/");
+                    let asked =
+                        Url::parse(&format!("http://proxy{target}"))
+                            .ok()
+                            .and_then(|proxied| {
+                                let (_, url) =
+                                    proxied.query_pairs().find(|(key, _)| key == "url")?;
+                                Url::parse(&url).ok()
+                            });
+                    let (status, body) = asked
+                        .and_then(|url| pages.iter().find(|(path, ..)| *path == url.path()))
+                        .map_or((404, "Not found"), |(_, status, body)| (*status, *body));
+                    let response = format!(
+                        "HTTP/1.1 {status} Fixture\r\nContent-Type: text/html; charset=utf-8\r\n\
+                         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        format!("http://{address}/preview")
+    }
+
+    #[tokio::test]
+    async fn reads_opengraph_tags() {
+        let proxy = proxy(&[("/post", 200, OPENGRAPH)]).await;
+        let embed = fetch_preview(proxy.clone(), "https://blog.example/post".into())
+            .await
+            .unwrap();
+
+        assert_eq!(embed.url, "https://blog.example/post");
+        assert_eq!(embed.title.as_deref(), Some("Release notes"));
+        assert_eq!(
+            embed.description.as_deref(),
+            Some("Fixes & improvements for \"everyone\"")
+        );
+        assert_eq!(embed.site_name.as_deref(), Some("Potato Blog"));
+        assert_eq!(embed.color, Some(0x5865f2));
+        // Resolved against the page, then fetched through the proxy like the page.
+        let thumbnail = Url::parse(embed.thumbnail.as_deref().unwrap()).unwrap();
+        assert!(thumbnail.as_str().starts_with(&proxy));
+        let (_, image) = thumbnail
+            .query_pairs()
+            .find(|(key, _)| key == "url")
+            .unwrap();
+        assert_eq!(image, "https://blog.example/images/cover.png");
+    }
+
+    #[tokio::test]
+    async fn falls_back_on_title_and_description() {
+        let proxy = proxy(&[("/plain", 200, FALLBACK)]).await;
+        let embed = fetch_preview(proxy, "https://plain.example/plain".into())
+            .await
+            .unwrap();
+
+        assert_eq!(embed.title.as_deref(), Some("Plain <page>"));
+        assert_eq!(embed.description.as_deref(), Some("Unquoted"));
+        assert_eq!(embed.site_name.as_deref(), Some("plain.example"));
+        assert_eq!(embed.thumbnail, None);
+        assert_eq!(embed.color, None);
+    }
+
+    #[tokio::test]
+    async fn nothing_to_preview() {
+        let proxy = proxy(&[("/empty", 200, NOTHING)]).await;
+        let embed = fetch_preview(proxy, "https://example.com/empty".into()).await;
+        assert_eq!(embed, None);
+    }
+
+    #[tokio::test]
+    async fn errors_are_not_previewed() {
+        let proxy = proxy(&[("/gone", 410, OPENGRAPH), ("/broken", 500, OPENGRAPH)]).await;
+        for url in [
+            "https://example.com/gone",
+            "https://example.com/broken",
+            "https://example.com/missing",
+        ] {
+            assert_eq!(
+                fetch_preview(proxy.clone(), url.into()).await,
+                None,
+                "{url}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn malformed_pages_dont_panic() {
+        let proxy = proxy(&[
+            ("/open-meta", 200, "<meta</head>"),
+            ("/unclosed", 200, "<title>Unclosed<meta property=\"og:title"),
+            (
+                "/late",
+                200,
+                "<head></head><meta name=description content=late>",
+            ),
+        ])
+        .await;
+        let embed = fetch_preview(proxy.clone(), "https://example.com/open-meta".into()).await;
+        assert_eq!(embed, None);
+        let embed = fetch_preview(proxy.clone(), "https://example.com/unclosed".into()).await;
+        assert_eq!(embed, None);
+        let embed = fetch_preview(proxy, "https://example.com/late".into()).await;
+        assert_eq!(embed, None);
+    }
+
+    #[test]
+    fn long_descriptions_are_shortened() {
+        let long = "a".repeat(DESCRIPTION_LENGTH + 50);
+        let html = format!(r#"<meta property="og:description" content="{long}">"#);
+        let embed = opengraph("http://proxy/", "https://example.com", &html).unwrap();
+        let description = embed.description.unwrap();
+        assert_eq!(description.chars().count(), DESCRIPTION_LENGTH + 1);
+        assert!(description.ends_with('…'));
+
+        let short = "é".repeat(DESCRIPTION_LENGTH);
+        assert_eq!(shorten(&short), short);
+    }
+
+    #[test]
+    fn meta_tags_stay_in_the_head() {
+        for html in [
+            "<meta</head>",
+            "<meta",
+            "<meta name=a</head>>",
+            "<META></HEAD><meta>",
+        ] {
+            assert!(meta_tags(html).is_empty(), "{html}");
+        }
+        let tags = meta_tags("<meta name=\"a\" content=\"1\"><meta name=b content='2' />");
+        assert_eq!(tags.get("a").map(String::as_str), Some("1"));
+        assert_eq!(tags.get("b").map(String::as_str), Some("2"));
+    }
+
+    #[test]
+    fn attributes_survive_broken_quotes() {
+        let parsed = attributes(r#" name="unterminated content=x"#);
+        assert_eq!(parsed.get("name"), Some(&"unterminated content=x"));
+        let parsed = attributes("content= spaced 'x");
+        assert_eq!(parsed.get("content"), Some(&"spaced"));
+    }
+
+    #[test]
+    fn unescapes_entities_once() {
+        assert_eq!(unescape("&amp;lt; &lt;b&gt; &#39;&apos;"), "&lt; <b> ''");
+        assert_eq!(parse_color("#GGGGGG"), None);
+        assert_eq!(parse_color("#fff"), None);
+        assert_eq!(parse_color("#00ff00"), Some(0x00ff00));
+    }
+}
@@ -0,0 +1,269 @@
+use iced::widget::image;
+use serde::{Deserialize, Serialize};
+
+/// At most this many emoji are remembered as recently used.
+pub const RECENT_LIMIT: usize = 24;
+
+/// The tabs of the emoji picker, in the order they're shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    People,
+    Nature,
+    Food,
+    Activities,
+    Objects,
+    Symbols,
+}
+
+impl Category {
+    pub const ALL: [Category; 6] = [
+        Category::People,
+        Category::Nature,
+        Category::Food,
+        Category::Activities,
+        Category::Objects,
+        Category::Symbols,
+    ];
+
+    /// An emoji standing for the category on its tab.
+    pub fn icon(self) -> &'static str {
+        match self {
+            Category::People => "😄",
+            Category::Nature => "🌳",
+            Category::Food => "🍕",
+            Category::Activities => "🎉",
+            Category::Objects => "💡",
+            Category::Symbols => "❤️",
+        }
+    }
+}
+
+/// Skin tone applied to the emoji that have one, like hands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkinTone {
+    /// The yellow emoji without a modifier.
+    #[default]
+    Default,
+    Light,
+    MediumLight,
+    Medium,
+    MediumDark,
+    Dark,
+}
+
+impl SkinTone {
+    pub const ALL: [SkinTone; 6] = [
+        SkinTone::Default,
+        SkinTone::Light,
+        SkinTone::MediumLight,
+        SkinTone::Medium,
+        SkinTone::MediumDark,
+        SkinTone::Dark,
+    ];
+
+    fn modifier(self) -> Option<char> {
+        match self {
+            SkinTone::Default => None,
+            SkinTone::Light => Some('\u{1F3FB}'),
+            SkinTone::MediumLight => Some('\u{1F3FC}'),
+            SkinTone::Medium => Some('\u{1F3FD}'),
+            SkinTone::MediumDark => Some('\u{1F3FE}'),
+            SkinTone::Dark => Some('\u{1F3FF}'),
+        }
+    }
+
+    /// A raised hand in this tone, for the tone selector.
+    pub fn sample(self) -> String {
+        toned(Category::People, "hand", "✋").glyph(self)
+    }
+}
+
+/// An emoji with the shortcode it's typed as, e.g. `:smile:`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Emoji {
+    pub category: Category,
+    pub name: &'static str,
+    glyph: &'static str,
+    /// Whether the emoji comes in skin tones.
+    toned: bool,
+}
+
+impl Emoji {
+    pub fn glyph(&self, tone: SkinTone) -> String {
+        match tone.modifier().filter(|_| self.toned) {
+            // The modifier replaces the emoji presentation selector, if there is one.
+            Some(modifier) => format!("{}{modifier}", self.glyph.trim_end_matches('\u{FE0F}')),
+            None => self.glyph.to_string(),
+        }
+    }
+}
+
+/// An emoji uploaded to a guild, used as `<:name:id>` in messages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomEmoji {
+    pub id: String,
+    pub name: String,
+    /// `None` until the image is downloaded, the name is shown until then.
+    pub image: Option<image::Handle>,
+}
+
+const fn emoji(category: Category, name: &'static str, glyph: &'static str) -> Emoji {
+    Emoji {
+        category,
+        name,
+        glyph,
+        toned: false,
+    }
+}
+
+const fn toned(category: Category, name: &'static str, glyph: &'static str) -> Emoji {
+    Emoji {
+        category,
+        name,
+        glyph,
+        toned: true,
+    }
+}
+
+pub const ALL: &[Emoji] = &[
+    emoji(Category::People, "smile", "😄"),
+    emoji(Category::People, "grin", "😁"),
+    emoji(Category::People, "joy", "😂"),
+    emoji(Category::People, "rofl", "🤣"),
+    emoji(Category::People, "slight_smile", "🙂"),
+    emoji(Category::People, "upside_down", "🙃"),
+    emoji(Category::People, "wink", "😉"),
+    emoji(Category::People, "blush", "😊"),
+    emoji(Category::People, "innocent", "😇"),
+    emoji(Category::People, "heart_eyes", "😍"),
+    emoji(Category::People, "kissing_heart", "😘"),
+    emoji(Category::People, "yum", "😋"),
+    emoji(Category::People, "stuck_out_tongue", "😛"),
+    emoji(Category::People, "thinking", "🤔"),
+    emoji(Category::People, "neutral_face", "😐"),
+    emoji(Category::People, "expressionless", "😑"),
+    emoji(Category::People, "unamused", "😒"),
+    emoji(Category::People, "rolling_eyes", "🙄"),
+    emoji(Category::People, "grimacing", "😬"),
+    emoji(Category::People, "relieved", "😌"),
+    emoji(Category::People, "pensive", "😔"),
+    emoji(Category::People, "sleepy", "😪"),
+    emoji(Category::People, "sleeping", "😴"),
+    emoji(Category::People, "sunglasses", "😎"),
+    emoji(Category::People, "nerd", "🤓"),
+    emoji(Category::People, "confused", "😕"),
+    emoji(Category::People, "worried", "😟"),
+    emoji(Category::People, "open_mouth", "😮"),
+    emoji(Category::People, "flushed", "😳"),
+    emoji(Category::People, "pleading_face", "🥺"),
+    emoji(Category::People, "cry", "😢"),
+    emoji(Category::People, "sob", "😭"),
+    emoji(Category::People, "scream", "😱"),
+    emoji(Category::People, "angry", "😠"),
+    emoji(Category::People, "rage", "😡"),
+    emoji(Category::People, "skull", "💀"),
+    emoji(Category::People, "clown", "🤡"),
+    emoji(Category::People, "ghost", "👻"),
+    emoji(Category::People, "robot", "🤖"),
+    toned(Category::People, "wave", "👋"),
+    toned(Category::People, "ok_hand", "👌"),
+    toned(Category::People, "thumbsup", "👍"),
+    toned(Category::People, "thumbsdown", "👎"),
+    toned(Category::People, "clap", "👏"),
+    toned(Category::People, "raised_hands", "🙌"),
+    toned(Category::People, "pray", "🙏"),
+    toned(Category::People, "muscle", "💪"),
+    toned(Category::People, "point_up", "☝️"),
+    emoji(Category::People, "eyes", "👀"),
+    emoji(Category::Nature, "tree", "🌳"),
+    emoji(Category::Nature, "cactus", "🌵"),
+    emoji(Category::Nature, "sunflower", "🌻"),
+    emoji(Category::Nature, "snowflake", "❄️"),
+    emoji(Category::Nature, "crescent_moon", "🌙"),
+    emoji(Category::Nature, "fox", "🦊"),
+    emoji(Category::Nature, "penguin", "🐧"),
+    emoji(Category::Nature, "frog", "🐸"),
+    emoji(Category::Nature, "rainbow", "🌈"),
+    emoji(Category::Nature, "sunny", "☀️"),
+    emoji(Category::Nature, "cat", "🐱"),
+    emoji(Category::Nature, "dog", "🐶"),
+    emoji(Category::Food, "apple", "🍎"),
+    emoji(Category::Food, "avocado", "🥑"),
+    emoji(Category::Food, "hamburger", "🍔"),
+    emoji(Category::Food, "fries", "🍟"),
+    emoji(Category::Food, "taco", "🌮"),
+    emoji(Category::Food, "sushi", "🍣"),
+    emoji(Category::Food, "cookie", "🍪"),
+    emoji(Category::Food, "tea", "🍵"),
+    emoji(Category::Food, "pizza", "🍕"),
+    emoji(Category::Food, "coffee", "☕"),
+    emoji(Category::Food, "beer", "🍺"),
+    emoji(Category::Food, "cake", "🍰"),
+    emoji(Category::Activities, "soccer", "⚽"),
+    emoji(Category::Activities, "basketball", "🏀"),
+    emoji(Category::Activities, "video_game", "🎮"),
+    emoji(Category::Activities, "game_die", "🎲"),
+    emoji(Category::Activities, "art", "🎨"),
+    emoji(Category::Activities, "guitar", "🎸"),
+    emoji(Category::Activities, "headphones", "🎧"),
+    emoji(Category::Activities, "medal", "🏅"),
+    emoji(Category::Activities, "tada", "🎉"),
+    emoji(Category::Activities, "confetti_ball", "🎊"),
+    emoji(Category::Activities, "gift", "🎁"),
+    emoji(Category::Activities, "trophy", "🏆"),
+    emoji(Category::Objects, "computer", "💻"),
+    emoji(Category::Objects, "keyboard", "⌨️"),
+    emoji(Category::Objects, "phone", "📱"),
+    emoji(Category::Objects, "bulb", "💡"),
+    emoji(Category::Objects, "books", "📚"),
+    emoji(Category::Objects, "hammer", "🔨"),
+    emoji(Category::Objects, "camera", "📷"),
+    emoji(Category::Objects, "hourglass", "⌛"),
+    emoji(Category::Objects, "rocket", "🚀"),
+    emoji(Category::Objects, "zap", "⚡"),
+    emoji(Category::Objects, "bug", "🐛"),
+    emoji(Category::Objects, "lock", "🔒"),
+    emoji(Category::Objects, "bell", "🔔"),
+    emoji(Category::Objects, "pushpin", "📌"),
+    emoji(Category::Objects, "link", "🔗"),
+    emoji(Category::Symbols, "heart", "❤️"),
+    emoji(Category::Symbols, "orange_heart", "🧡"),
+    emoji(Category::Symbols, "yellow_heart", "💛"),
+    emoji(Category::Symbols, "green_heart", "💚"),
+    emoji(Category::Symbols, "blue_heart", "💙"),
+    emoji(Category::Symbols, "purple_heart", "💜"),
+    emoji(Category::Symbols, "broken_heart", "💔"),
+    emoji(Category::Symbols, "sparkles", "✨"),
+    emoji(Category::Symbols, "star", "⭐"),
+    emoji(Category::Symbols, "fire", "🔥"),
+    emoji(Category::Symbols, "100", "💯"),
+    emoji(Category::Symbols, "check", "✅"),
+    emoji(Category::Symbols, "x", "❌"),
+    emoji(Category::Symbols, "warning", "⚠️"),
+    emoji(Category::Symbols, "question", "❓"),
+    emoji(Category::Symbols, "exclamation", "❗"),
+];
+
+/// Emoji whose shortcode contains `query`, those starting with it first.
+pub fn search(query: &str) -> Vec<Emoji> {
+    let query = query.to_lowercase();
+    let mut found: Vec<Emoji> = ALL
+        .iter()
+        .filter(|emoji| emoji.name.contains(&query))
+        .copied()
+        .collect();
+    found.sort_by_key(|emoji| !emoji.name.starts_with(&query));
+    found
+}
+
+pub fn in_category(category: Category) -> impl Iterator<Item = &'static Emoji> {
+    ALL.iter().filter(move |emoji| emoji.category == category)
+}
+
+/// `recent` with `glyph` moved to the front, as stored in the settings.
+pub fn remember(recent: &mut Vec<String>, glyph: &str) {
+    recent.retain(|used| used != glyph);
+    recent.insert(0, glyph.to_string());
+    recent.truncate(RECENT_LIMIT);
+}
@@ -0,0 +1,241 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::{Arc, LazyLock},
+};
+
+use iced::{Color, Task};
+use syntect::{
+    highlighting::ScopeSelectors,
+    parsing::{ParseState, ScopeStack, SyntaxReference, SyntaxSet},
+};
+
+use crate::{
+    colors::{AppColorForeground, AppColorMain, AppColorStatus},
+    markdown::{Block, Document},
+};
+
+/// The grammars bundled with syntect, loaded the first time a code block is highlighted.
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+/// TextMate scopes sorted into the few kinds of token the palette has colors for. The
+/// selector matching a token most specifically wins, like in a TextMate theme.
+static SCOPES: LazyLock<Vec<(ScopeSelectors, TokenKind)>> = LazyLock::new(|| {
+    [
+        ("comment", TokenKind::Comment),
+        ("keyword, storage", TokenKind::Keyword),
+        ("string, constant.character", TokenKind::String),
+        ("constant.numeric, constant.language", TokenKind::Number),
+        (
+            "entity.name.function, support.function, variable.function",
+            TokenKind::Function,
+        ),
+        (
+            "entity.name.type, entity.name.class, support.type, support.class",
+            TokenKind::Type,
+        ),
+        ("keyword.operator, punctuation", TokenKind::Punctuation),
+    ]
+    .into_iter()
+    .filter_map(|(selector, kind)| Some((ScopeSelectors::from_str(selector).ok()?, kind)))
+    .collect()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    Plain,
+    Comment,
+    Keyword,
+    String,
+    Number,
+    Function,
+    Type,
+    Punctuation,
+}
+
+impl TokenKind {
+    /// Looked up when drawing, so highlighted code follows theme changes without being
+    /// highlighted again.
+    pub fn color(self) -> Color {
+        match self {
+            TokenKind::Plain => AppColorForeground::Primary.into(),
+            TokenKind::Comment => AppColorForeground::SubtextSecondary.into(),
+            TokenKind::Keyword => AppColorMain::AccentPrimary.into(),
+            TokenKind::String => AppColorStatus::Success.into(),
+            TokenKind::Number => AppColorStatus::Warning.into(),
+            TokenKind::Function => AppColorMain::AccentSecondary.into(),
+            TokenKind::Type => AppColorMain::AccentTertiary.into(),
+            TokenKind::Punctuation => AppColorForeground::Secondary.into(),
+        }
+    }
+}
+
+/// The tokens of each line of a code block.
+#[derive(Debug)]
+pub struct Highlighted {
+    pub lines: Vec<Vec<(TokenKind, String)>>,
+}
+
+fn token_kind(stack: &ScopeStack) -> TokenKind {
+    SCOPES
+        .iter()
+        .filter_map(|(selector, kind)| Some((selector.does_match(stack.as_slice())?, *kind)))
+        .max_by_key(|(power, _)| *power)
+        .map_or(TokenKind::Plain, |(_, kind)| kind)
+}
+
+fn highlight_lines(
+    syntax: &SyntaxReference,
+    code: &str,
+) -> Result<Vec<Vec<(TokenKind, String)>>, syntect::Error> {
+    let mut state = ParseState::new(syntax);
+    let mut stack = ScopeStack::new();
+
+    code.lines()
+        .map(|line| -> Result<_, syntect::Error> {
+            // The bundled grammars expect every line to end in a newline.
+            let ops = state.parse_line(&format!("{line}\n"), &SYNTAXES)?;
+            let mut tokens: Vec<(TokenKind, String)> = Vec::new();
+            let mut start = 0;
+            let mut push = |kind: TokenKind, text: &str| match tokens.last_mut() {
+                Some((last, existing)) if *last == kind => existing.push_str(text),
+                _ if text.is_empty() => {}
+                _ => tokens.push((kind, text.to_string())),
+            };
+
+            for (offset, op) in ops {
+                let offset = offset.min(line.len());
+                push(token_kind(&stack), &line[start..offset]);
+                start = offset;
+                stack.apply(&op)?;
+            }
+            push(token_kind(&stack), &line[start..]);
+
+            Ok(tokens)
+        })
+        .collect()
+}
+
+/// `None` when there's no grammar for the language, or the grammar chokes on the code.
+fn highlight(language: &str, code: &str) -> Option<Highlighted> {
+    let syntax = SYNTAXES.find_syntax_by_token(language)?;
+    match highlight_lines(syntax, code) {
+        Ok(lines) => Some(Highlighted { lines }),
+        Err(e) => {
+            tracing::warn!(error = %e, language, "Failed to highlight code block");
+            None
+        }
+    }
+}
+
+/// A code block's key with its highlighting, see [`Cache::request`].
+pub type Finished = (u64, Option<Arc<Highlighted>>);
+
+/// Highlighted code blocks, by the key of the block so a message is highlighted once however
+/// often it's drawn. `None` while highlighting runs, and for languages without a grammar, both
+/// are drawn as plain code. Blocks no message has anymore are dropped with [`Cache::retain`].
+#[derive(Debug, Clone, Default)]
+pub struct Cache {
+    entries: HashMap<u64, Option<Arc<Highlighted>>>,
+}
+
+impl Cache {
+    pub fn get(&self, key: u64) -> Option<&Highlighted> {
+        self.entries.get(&key)?.as_deref()
+    }
+
+    /// Fills in a block requested before, unless it was dropped while being highlighted.
+    pub fn insert(&mut self, key: u64, highlighted: Option<Arc<Highlighted>>) {
+        if let Some(entry) = self.entries.get_mut(&key) {
+            *entry = highlighted;
+        }
+    }
+
+    /// Keeps only the code blocks of `documents`, after messages are deleted or edited.
+    pub fn retain<'a>(&mut self, documents: impl IntoIterator<Item = &'a Document>) {
+        fn collect(blocks: &[Block], keys: &mut HashSet<u64>) {
+            for block in blocks {
+                match block {
+                    Block::Code { key, .. } => {
+                        keys.insert(*key);
+                    }
+                    Block::Quote(quoted) => collect(quoted, keys),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut keys = HashSet::new();
+        for document in documents {
+            collect(&document.blocks, &mut keys);
+        }
+        self.entries.retain(|key, _| keys.contains(key));
+    }
+
+    /// Highlights the code blocks of `document` that aren't cached yet on tokio's blocking
+    /// threads, so long blocks don't stall the UI.
+    pub fn request(&mut self, document: &Document) -> Task<Finished> {
+        let mut tasks = Vec::new();
+        self.request_blocks(&document.blocks, &mut tasks);
+        Task::batch(tasks)
+    }
+
+    fn request_blocks(&mut self, blocks: &[Block], tasks: &mut Vec<Task<Finished>>) {
+        for block in blocks {
+            match block {
+                Block::Code {
+                    language: Some(language),
+                    code,
+                    key,
+                } => {
+                    let id = *key;
+                    if self.entries.contains_key(&id) {
+                        continue;
+                    }
+                    self.entries.insert(id, None);
+
+                    let (language, code) = (language.clone(), code.clone());
+                    let highlighted = async move {
+                        tokio::task::spawn_blocking(move || {
+                            highlight(&language, &code).map(Arc::new)
+                        })
+                        .await
+                        .ok()
+                        .flatten()
+                    };
+                    tasks.push(Task::perform(highlighted, move |highlighted| {
+                        (id, highlighted)
+                    }));
+                }
+                Block::Quote(quoted) => self.request_blocks(quoted, tasks),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::parse;
+
+    #[test]
+    fn retain_drops_blocks_no_message_has() {
+        let kept = parse("```rust\nfn main() {}\n```");
+        let dropped = parse("```rust\nlet x = 1;\n```");
+        let mut cache = Cache::default();
+        let _ = cache.request(&kept);
+        let _ = cache.request(&dropped);
+        assert_eq!(cache.entries.len(), 2);
+
+        cache.retain([&kept]);
+        assert_eq!(cache.entries.len(), 1);
+
+        // Highlighting that finishes after its block was dropped isn't kept.
+        let Block::Code { key, .. } = dropped.blocks[0] else {
+            panic!("expected a code block");
+        };
+        cache.insert(key, None);
+        assert_eq!(cache.entries.len(), 1);
+    }
+}
@@ -0,0 +1,254 @@
+use chrono::{DateTime, TimeZone};
+use fluent_bundle::{FluentArgs, FluentResource, concurrent::FluentBundle};
+use fluent_langneg::{NegotiationStrategy, convert_vec_str_to_langids_lossy, negotiate_languages};
+use iced::alignment::Horizontal;
+use std::{
+    fmt,
+    sync::{LazyLock, PoisonError, RwLock},
+};
+use unic_langid::LanguageIdentifier;
+use validator::{ValidationError, ValidationErrors};
+
+/// A language the app ships translations for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Language {
+    /// BCP 47 tag, also the value stored in the settings file.
+    pub id: &'static str,
+    /// Name of the language in that language, as shown in the language picker.
+    pub name: &'static str,
+    /// Used for month and weekday names when formatting dates.
+    pub date_locale: chrono::Locale,
+    /// Which way the whole UI is laid out while this language is active.
+    pub direction: Direction,
+    source: &'static str,
+}
+
+pub const LANGUAGES: [Language; 3] = [
+    Language {
+        id: "en-US",
+        name: "English",
+        date_locale: chrono::Locale::en_US,
+        direction: Direction::Ltr,
+        source: include_str!("../locales/en-US/main.ftl"),
+    },
+    Language {
+        id: "de",
+        name: "Deutsch",
+        date_locale: chrono::Locale::de_DE,
+        direction: Direction::Ltr,
+        source: include_str!("../locales/de/main.ftl"),
+    },
+    Language {
+        id: "he",
+        name: "עברית",
+        date_locale: chrono::Locale::he_IL,
+        direction: Direction::Rtl,
+        source: include_str!("../locales/he/main.ftl"),
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Ltr,
+    Rtl,
+}
+
+impl Direction {
+    /// The base direction of the first paragraph of `text`, taken from its first strong
+    /// character like the Unicode bidi algorithm does. `None` when it only has neutral
+    /// characters such as digits, punctuation or emoji.
+    pub fn of(text: &str) -> Option<Direction> {
+        match unicode_bidi::get_base_direction(text) {
+            unicode_bidi::Direction::Ltr => Some(Direction::Ltr),
+            unicode_bidi::Direction::Rtl => Some(Direction::Rtl),
+            unicode_bidi::Direction::Mixed => None,
+        }
+    }
+
+    /// The side text in this direction starts from.
+    pub fn start(self) -> Horizontal {
+        match self {
+            Direction::Ltr => Horizontal::Left,
+            Direction::Rtl => Horizontal::Right,
+        }
+    }
+
+    /// The side text in this direction ends on.
+    pub fn end(self) -> Horizontal {
+        match self {
+            Direction::Ltr => Horizontal::Right,
+            Direction::Rtl => Horizontal::Left,
+        }
+    }
+}
+
+/// Used when the system language isn't one we have translations for, and for any message
+/// missing from another language.
+pub const FALLBACK_LANGUAGE: Language = LANGUAGES[0];
+
+/// Translates a message id from the `.ftl` files, with optional `name = value` arguments,
+/// e.g. `tr!("reset-hint", email = state.email.as_str())`.
+macro_rules! tr {
+    ($id:expr) => {
+        $crate::i18n::translate($id, None)
+    };
+    ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::translate($id, Some(&args))
+    }};
+}
+pub(crate) use tr;
+
+struct Translations {
+    language: Language,
+    /// The chosen language first, then the fallback.
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Translations {
+    fn new(language: Language) -> Self {
+        let mut bundles = vec![bundle(language)];
+        if language != FALLBACK_LANGUAGE {
+            bundles.push(bundle(FALLBACK_LANGUAGE));
+        }
+        Self { language, bundles }
+    }
+
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+        self.bundles.iter().find_map(|bundle| {
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            let value = bundle.format_pattern(pattern, args, &mut errors);
+            if !errors.is_empty() {
+                tracing::warn!(id, ?errors, "Failed to format translation");
+            }
+            Some(value.into_owned())
+        })
+    }
+}
+
+fn bundle(language: Language) -> FluentBundle<FluentResource> {
+    let id: LanguageIdentifier = language.id.parse().expect("bundled language ids are valid");
+    let mut bundle = FluentBundle::new_concurrent(vec![id]);
+    // The isolation marks around arguments render as boxes with some fonts.
+    bundle.set_use_isolating(false);
+
+    let resource = FluentResource::try_new(language.source.to_string()).unwrap_or_else(
+        |(resource, errors)| {
+            tracing::error!(
+                language = language.id,
+                ?errors,
+                "Errors in translation file"
+            );
+            resource
+        },
+    );
+    if let Err(errors) = bundle.add_resource(resource) {
+        tracing::error!(language = language.id, ?errors, "Duplicate translations");
+    }
+    bundle
+}
+
+/// Like the active palette in `theme`, read by views through [`tr!`].
+static ACTIVE: LazyLock<RwLock<Translations>> =
+    LazyLock::new(|| RwLock::new(Translations::new(system_language())));
+
+/// The bundled language closest to the ones the system is set to.
+pub fn system_language() -> Language {
+    let requested = convert_vec_str_to_langids_lossy(sys_locale::get_locales());
+    let available: Vec<LanguageIdentifier> = LANGUAGES
+        .iter()
+        .map(|language| language.id.parse().expect("bundled language ids are valid"))
+        .collect();
+
+    negotiate_languages(&requested, &available, None, NegotiationStrategy::Lookup)
+        .first()
+        .and_then(|matched| available.iter().position(|id| id == *matched))
+        .map_or(FALLBACK_LANGUAGE, |index| LANGUAGES[index])
+}
+
+/// The language with the given id, or the system language for `None` and unknown ids.
+pub fn language(id: Option<&str>) -> Language {
+    id.and_then(|id| LANGUAGES.iter().find(|language| language.id == id))
+        .copied()
+        .unwrap_or_else(system_language)
+}
+
+pub fn active_language() -> Language {
+    ACTIVE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .language
+}
+
+/// Which way rows and text run in the UI, from the active language.
+pub fn layout_direction() -> Direction {
+    active_language().direction
+}
+
+pub fn set_language(language: Language) {
+    if active_language() == language {
+        return;
+    }
+    tracing::info!(language = language.id, "Switching language");
+    *ACTIVE.write().unwrap_or_else(PoisonError::into_inner) = Translations::new(language);
+}
+
+/// Falls back to the id itself so a missing translation is visible instead of blank.
+pub fn translate(id: &str, args: Option<&FluentArgs>) -> String {
+    ACTIVE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .format(id, args)
+        .unwrap_or_else(|| {
+            tracing::warn!(id, "Missing translation");
+            id.to_string()
+        })
+}
+
+/// Formats a date with the strftime pattern stored under `format_id`, using month and
+/// weekday names from the active language.
+pub fn format_date<Tz: TimeZone>(date: &DateTime<Tz>, format_id: &str) -> String
+where
+    Tz::Offset: fmt::Display,
+{
+    let format = translate(format_id, None);
+    date.format_localized(&format, active_language().date_locale)
+        .to_string()
+}
+
+/// The first error on `field`, translated from its validator code with the validator's
+/// parameters such as `min` and `max` as arguments.
+pub fn field_error(errors: &ValidationErrors, field: &str) -> Option<String> {
+    let field_errors = errors.field_errors();
+    let error = field_errors.get(field)?.first()?;
+    Some(validation_error(error))
+}
+
+fn validation_error(error: &ValidationError) -> String {
+    let mut args = FluentArgs::new();
+    for (name, value) in &error.params {
+        // `value` is what the user typed, which may be a password.
+        if name == "value" {
+            continue;
+        }
+        if let Some(number) = value.as_i64() {
+            args.set(name.clone(), number);
+        } else if let Some(text) = value.as_str() {
+            args.set(name.clone(), text.to_string());
+        }
+    }
+    translate(&format!("validation-{}", error.code), Some(&args))
+}
+
+/// Translates an error code sent by the server, keeping the server's own message for codes
+/// this build doesn't know about.
+pub fn server_error(code: &str, message: &str) -> String {
+    let id = format!("server-error-{}", code.to_lowercase().replace('_', "-"));
+    ACTIVE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .format(&id, None)
+        .unwrap_or_else(|| message.to_string())
+}
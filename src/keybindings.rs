@@ -0,0 +1,350 @@
+use iced::keyboard::{Key, Modifiers, key::Named};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use crate::i18n::tr;
+
+/// Shown for the command modifier, which is Cmd on macOS and Ctrl everywhere else.
+const COMMAND_LABEL: &str = if cfg!(target_os = "macos") {
+    "Cmd"
+} else {
+    "Ctrl"
+};
+
+/// Named keys that can be bound, with the name used for them in the settings file.
+const NAMED_KEYS: [(&str, Named); 26] = [
+    ("Escape", Named::Escape),
+    ("Enter", Named::Enter),
+    ("Tab", Named::Tab),
+    ("Space", Named::Space),
+    ("Backspace", Named::Backspace),
+    ("Delete", Named::Delete),
+    ("Up", Named::ArrowUp),
+    ("Down", Named::ArrowDown),
+    ("Left", Named::ArrowLeft),
+    ("Right", Named::ArrowRight),
+    ("PageUp", Named::PageUp),
+    ("PageDown", Named::PageDown),
+    ("Home", Named::Home),
+    ("End", Named::End),
+    ("F1", Named::F1),
+    ("F2", Named::F2),
+    ("F3", Named::F3),
+    ("F4", Named::F4),
+    ("F5", Named::F5),
+    ("F6", Named::F6),
+    ("F7", Named::F7),
+    ("F8", Named::F8),
+    ("F9", Named::F9),
+    ("F10", Named::F10),
+    ("F11", Named::F11),
+    ("F12", Named::F12),
+];
+
+/// Something the user can trigger from the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Action {
+    NextChannel,
+    PreviousChannel,
+    NextUnread,
+    JumpToMentions,
+    MarkRead,
+    OpenSettings,
+    ToggleMute,
+    EditLastMessage,
+    CloseModal,
+    ZoomIn,
+    ZoomOut,
+    ResetZoom,
+}
+
+impl Action {
+    pub const ALL: [Action; 12] = [
+        Action::NextChannel,
+        Action::PreviousChannel,
+        Action::NextUnread,
+        Action::JumpToMentions,
+        Action::MarkRead,
+        Action::OpenSettings,
+        Action::ToggleMute,
+        Action::EditLastMessage,
+        Action::CloseModal,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::ResetZoom,
+    ];
+
+    /// Key of the action in the `[keybindings]` table of the settings file.
+    pub fn id(self) -> &'static str {
+        match self {
+            Action::NextChannel => "next-channel",
+            Action::PreviousChannel => "previous-channel",
+            Action::NextUnread => "next-unread",
+            Action::JumpToMentions => "jump-to-mentions",
+            Action::MarkRead => "mark-read",
+            Action::OpenSettings => "open-settings",
+            Action::ToggleMute => "toggle-mute",
+            Action::EditLastMessage => "edit-last-message",
+            Action::CloseModal => "close-modal",
+            Action::ZoomIn => "zoom-in",
+            Action::ZoomOut => "zoom-out",
+            Action::ResetZoom => "reset-zoom",
+        }
+    }
+
+    pub fn label(self) -> String {
+        tr!(match self {
+            Action::NextChannel => "action-next-channel",
+            Action::PreviousChannel => "action-previous-channel",
+            Action::NextUnread => "action-next-unread",
+            Action::JumpToMentions => "action-jump-to-mentions",
+            Action::MarkRead => "action-mark-read",
+            Action::OpenSettings => "action-open-settings",
+            Action::ToggleMute => "action-toggle-mute",
+            Action::EditLastMessage => "action-edit-last-message",
+            Action::CloseModal => "action-close-modal",
+            Action::ZoomIn => "action-zoom-in",
+            Action::ZoomOut => "action-zoom-out",
+            Action::ResetZoom => "action-reset-zoom",
+        })
+    }
+
+    /// Whether the action does anything yet. Moving between channels and muting them wait
+    /// for the channel list, and aren't offered in the settings until then.
+    pub fn is_available(self) -> bool {
+        !matches!(
+            self,
+            Action::NextChannel | Action::PreviousChannel | Action::NextUnread | Action::ToggleMute
+        )
+    }
+
+    fn default_bindings(self) -> Vec<KeyBinding> {
+        let ids: &[&str] = match self {
+            Action::NextChannel => &["Alt+Down"],
+            Action::PreviousChannel => &["Alt+Up"],
+            Action::NextUnread => &["Alt+Shift+Down"],
+            Action::JumpToMentions => &["Ctrl+I"],
+            Action::MarkRead => &["Shift+Escape"],
+            Action::OpenSettings => &["Ctrl+,"],
+            Action::ToggleMute => &["Ctrl+Shift+M"],
+            Action::EditLastMessage => &["Up"],
+            Action::CloseModal => &["Escape"],
+            Action::ZoomIn => &["Ctrl+=", "Ctrl++"],
+            Action::ZoomOut => &["Ctrl+-"],
+            Action::ResetZoom => &["Ctrl+0"],
+        };
+        ids.iter()
+            .map(|id| id.parse().expect("default keybindings are valid"))
+            .collect()
+    }
+}
+
+/// A key together with the modifiers that have to be held for it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub key: Key,
+    /// Cmd on macOS, Ctrl everywhere else.
+    pub command: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyBinding {
+    /// `None` for keys that can't be bound, including pressing a modifier on its own.
+    pub fn from_event(key: &Key, modifiers: Modifiers) -> Option<Self> {
+        let key = match key {
+            Key::Named(named) => {
+                NAMED_KEYS.iter().find(|(_, known)| known == named)?;
+                key.clone()
+            }
+            Key::Character(character) => Key::Character(character.to_lowercase().into()),
+            Key::Unidentified => return None,
+        };
+        Some(Self {
+            key,
+            command: modifiers.command(),
+            shift: modifiers.shift(),
+            alt: modifiers.alt(),
+        })
+    }
+
+    /// Tab and Shift+Tab always move focus between fields so they can't be rebound.
+    pub fn is_reserved(&self) -> bool {
+        self.key == Key::Named(Named::Tab) && !self.command && !self.alt
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    /// Parses the form written by `Display`, e.g. `Ctrl+Shift+M` or `Alt+Up`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut binding = KeyBinding {
+            key: Key::Unidentified,
+            command: false,
+            shift: false,
+            alt: false,
+        };
+        let mut rest = s.trim();
+        // Splitting on '+' would break `Ctrl++`, so strip the modifiers off the front instead.
+        loop {
+            if let Some(after) = rest.strip_prefix("Ctrl+").or(rest.strip_prefix("Cmd+")) {
+                binding.command = true;
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("Shift+") {
+                binding.shift = true;
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("Alt+") {
+                binding.alt = true;
+                rest = after;
+            } else {
+                break;
+            }
+        }
+
+        binding.key = match NAMED_KEYS.iter().find(|(name, _)| *name == rest) {
+            Some((_, named)) => Key::Named(*named),
+            None if rest.chars().count() == 1 => Key::Character(rest.to_lowercase().into()),
+            None => return Err(format!("Unknown key in keybinding \"{}\"", s)),
+        };
+        Ok(binding)
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.command {
+            write!(f, "{}+", COMMAND_LABEL)?;
+        }
+        if self.shift {
+            f.write_str("Shift+")?;
+        }
+        if self.alt {
+            f.write_str("Alt+")?;
+        }
+        match &self.key {
+            Key::Named(named) => {
+                let name = NAMED_KEYS
+                    .iter()
+                    .find(|(_, known)| known == named)
+                    .map_or("?", |(name, _)| name);
+                f.write_str(name)
+            }
+            Key::Character(character) => f.write_str(&character.to_uppercase()),
+            Key::Unidentified => f.write_str("?"),
+        }
+    }
+}
+
+/// Which keys trigger which action. Stored in the settings file as a table of action ids to
+/// lists of keys, an empty list leaves the action unbound.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    from = "BTreeMap<String, Vec<String>>",
+    into = "BTreeMap<String, Vec<String>>"
+)]
+pub struct Keybindings(BTreeMap<Action, Vec<KeyBinding>>);
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self(
+            Action::ALL
+                .into_iter()
+                .map(|action| (action, action.default_bindings()))
+                .collect(),
+        )
+    }
+}
+
+impl Keybindings {
+    pub fn bindings(&self, action: Action) -> &[KeyBinding] {
+        self.0.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// The first action bound to `binding` that does anything yet, in the order of
+    /// [`Action::ALL`].
+    pub fn action_for(&self, binding: &KeyBinding) -> Option<Action> {
+        self.actions_for(binding)
+            .find(|action| action.is_available())
+    }
+
+    pub fn actions_for<'a>(&'a self, binding: &'a KeyBinding) -> impl Iterator<Item = Action> + 'a {
+        Action::ALL
+            .into_iter()
+            .filter(move |action| self.bindings(*action).contains(binding))
+    }
+
+    /// Actions sharing a key with `action`, which only the first of them will receive.
+    pub fn conflicts(&self, action: Action) -> Vec<Action> {
+        let mut conflicts: Vec<Action> = self
+            .bindings(action)
+            .iter()
+            .flat_map(|binding| self.actions_for(binding))
+            .filter(|other| *other != action && other.is_available())
+            .collect();
+        conflicts.sort();
+        conflicts.dedup();
+        conflicts
+    }
+
+    pub fn bind(&mut self, action: Action, binding: KeyBinding) {
+        let bindings = self.0.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    pub fn unbind(&mut self, action: Action, binding: &KeyBinding) {
+        if let Some(bindings) = self.0.get_mut(&action) {
+            bindings.retain(|bound| bound != binding);
+        }
+    }
+
+    /// Removes `binding` from every action so it can be given to another one.
+    pub fn release(&mut self, binding: &KeyBinding) {
+        for bindings in self.0.values_mut() {
+            bindings.retain(|bound| bound != binding);
+        }
+    }
+
+    pub fn reset(&mut self, action: Action) {
+        self.0.insert(action, action.default_bindings());
+    }
+}
+
+impl From<BTreeMap<String, Vec<String>>> for Keybindings {
+    /// Unknown actions and keys are skipped with a warning instead of rejecting the whole
+    /// settings file, actions missing from the file keep their defaults.
+    fn from(table: BTreeMap<String, Vec<String>>) -> Self {
+        let mut keybindings = Keybindings::default();
+        for (id, keys) in table {
+            let Some(action) = Action::ALL.into_iter().find(|action| action.id() == id) else {
+                tracing::warn!(action = %id, "Ignoring keybindings for an unknown action");
+                continue;
+            };
+            let bindings = keys
+                .iter()
+                .filter_map(|key| {
+                    key.parse()
+                        .inspect_err(|e| tracing::warn!(error = %e, "Ignoring keybinding"))
+                        .ok()
+                })
+                .collect();
+            keybindings.0.insert(action, bindings);
+        }
+        keybindings
+    }
+}
+
+impl From<Keybindings> for BTreeMap<String, Vec<String>> {
+    fn from(keybindings: Keybindings) -> Self {
+        keybindings
+            .0
+            .into_iter()
+            .map(|(action, bindings)| {
+                let keys = bindings.iter().map(KeyBinding::to_string).collect();
+                (action.id().to_string(), keys)
+            })
+            .collect()
+    }
+}
@@ -1,37 +1,74 @@
 use crate::{
-    RUBIK,
-    colors::{AppColorForeground, AppColorMain},
+    colors::{AppColorForeground, AppColorMain, AppColorStatus},
     components::styled_input,
+    constants::{API_URL, FORM_WIDTH},
+    i18n::tr,
+    screens::forgot_password_screen,
     styles::{button_style, container_style},
-    utils::set_token_from_secure_storage,
+    typography::{body, caption, heading},
+    utils::{ApiError, Lockout, Redacted, Session, device_name, platform, try_send_json},
 };
-use std::collections::HashMap;
-use turbo::{auth::AuthResponse, errors::ResponseError, types::PublicUser};
+use serde::Deserialize;
+use std::{collections::HashMap, time::Duration};
+use turbo::auth::AuthResponse;
 
 use iced::{
     Color, Element,
-    Length::{self, Fill},
-    Task,
-    widget::{button, column, container, row, text},
+    Length::Fill,
+    Subscription, Task, task,
+    time::{self, Instant},
+    widget::{button, column, container, row},
 };
 
 #[derive(Debug, Clone, Default)]
 pub struct State {
     pub identifier: String,
-    pub password: String,
+    pub password: Redacted<String>,
     pub is_loading: bool,
     pub identifier_error: Option<String>,
     pub password_error: Option<String>,
+    pub step: LoginStep,
+    pub mfa_code: Redacted<String>,
+    pub mfa_code_error: Option<String>,
+    pub use_backup_code: bool,
+    pub notice: Option<String>,
+    pub lockout: Option<Lockout>,
+    /// Handle to the login request in flight, aborted when the credentials change.
+    pub request: Option<task::Handle>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum LoginStep {
+    #[default]
+    Credentials,
+    /// The password was accepted but the account has two-factor authentication enabled.
+    /// The ticket proves the first step succeeded and is exchanged along with the code.
+    Mfa { ticket: Redacted<String> },
 }
 
 impl State {
     pub fn new() -> Self {
         Self {
             identifier: String::new(),
-            password: String::new(),
+            password: Redacted::default(),
             is_loading: false,
             identifier_error: None,
             password_error: None,
+            step: LoginStep::Credentials,
+            mfa_code: Redacted::default(),
+            mfa_code_error: None,
+            use_backup_code: false,
+            notice: None,
+            lockout: None,
+            request: None,
+        }
+    }
+
+    /// Opens the login screen with a confirmation shown above the form.
+    pub fn with_notice(notice: &str) -> Self {
+        Self {
+            notice: Some(notice.to_string()),
+            ..Self::new()
         }
     }
 }
@@ -40,153 +77,388 @@ impl State {
 pub enum Message {
     UsernameInputChanged(String),
     PasswordInputChanged(String),
+    MfaCodeInputChanged(String),
     LoginButtonPressed,
-    LoginSuccess(PublicUser),
-    LoginFailed(String),
+    MfaSubmitPressed,
+    ToggleBackupCode,
+    BackToCredentials,
+    MfaRequired(Redacted<String>),
+    LoginSuccess(Session),
+    LoginFailed(ApiError),
+    MfaFailed(ApiError),
+    Tick(Instant),
     RegisterInstead,
+    ForgotPasswordInstead,
     RequestScreenChange(super::CurrentScreen),
 }
 
+pub enum LoginOutcome {
+    Success(Session),
+    MfaRequired(Redacted<String>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LoginResponse {
+    MfaRequired { mfa_ticket: Redacted<String> },
+    Authenticated(AuthResponse),
+}
+
 pub fn view(state: &State) -> Element<'_, Message> {
+    let content = match &state.step {
+        LoginStep::Credentials => credentials_view(state),
+        LoginStep::Mfa { .. } => mfa_view(state),
+    };
+
+    container(content).style(container_style).into()
+}
+
+fn credentials_view(state: &State) -> Element<'_, Message> {
     let login_button = button(
-        text(if state.is_loading {
-            "Loging in…"
-        } else {
-            "Login"
+        body(match state.lockout {
+            Some(lockout) => lockout.label(),
+            None if state.is_loading => tr!("login-submit-busy"),
+            None => tr!("login-submit"),
         })
         .width(Fill)
         .center(),
     )
-    .on_press(Message::LoginButtonPressed)
+    .on_press_maybe(can_submit(state).then_some(Message::LoginButtonPressed))
     .height(44)
     .width(Fill)
     .style(button_style)
     .padding(10);
 
-    let register_link = button(
-        text("Register")
-            .color(Color::from(AppColorMain::Secondary))
-            .size(12),
-    )
-    .on_press(Message::RegisterInstead)
-    .style(button::text)
-    .height(14)
-    .padding(0);
+    let register_link =
+        button(caption(tr!("login-register")).color(Color::from(AppColorMain::Secondary)))
+            .on_press(Message::RegisterInstead)
+            .style(button::text)
+            .height(14)
+            .padding(0);
 
-    container(
+    let forgot_password_link =
+        button(caption(tr!("login-forgot-password")).color(Color::from(AppColorMain::Secondary)))
+            .on_press(Message::ForgotPasswordInstead)
+            .style(button::text)
+            .height(14)
+            .padding(0);
+
+    let notice = state.notice.as_deref().map(|notice| {
+        caption(notice)
+            .width(Fill)
+            .center()
+            .color(Color::from(AppColorStatus::Success))
+    });
+
+    column![
+        column![heading(tr!("login-title")).width(Fill).center()]
+            .push_maybe(notice)
+            .spacing(8),
+        styled_input(
+            &tr!("login-identifier"),
+            &state.identifier,
+            state.identifier_error.as_deref(),
+            Message::UsernameInputChanged,
+            //,
+            None,
+            Some(true),
+            Some(state.lockout.is_some()),
+        ),
         column![
-            text("Log in").font(RUBIK).size(24).width(Fill).center(),
-            styled_input(
-                "Username or Email",
-                &state.identifier,
-                state.identifier_error.as_deref(),
-                None,
-                Message::UsernameInputChanged,
-                //,
-                None,
-                Some(true),
-            ),
             styled_input(
-                "Password",
-                &state.password,
+                &tr!("login-password"),
+                state.password.expose(),
                 state.password_error.as_deref(),
-                None,
                 Message::PasswordInputChanged,
                 //,
                 Some(true),
                 Some(true),
+                Some(state.lockout.is_some()),
             ),
-            column![
-                login_button,
-                row![
-                    text("Don't have an account? ")
-                        .size(12)
-                        .color(Color::from(AppColorForeground::SubtextPrimary)),
-                    register_link
-                ],
-            ]
-            .spacing(8)
+            row![forgot_password_link],
+        ]
+        .spacing(8),
+        column![
+            login_button,
+            row![
+                caption(tr!("login-no-account"))
+                    .color(Color::from(AppColorForeground::SubtextPrimary)),
+                register_link
+            ],
         ]
-        .width(Length::Fixed(550.0))
-        .padding(24)
-        .spacing(24),
+        .spacing(8)
+    ]
+    .width(FORM_WIDTH)
+    .padding(24)
+    .spacing(24)
+    .into()
+}
+
+fn mfa_view(state: &State) -> Element<'_, Message> {
+    let verify_button = button(
+        body(match state.lockout {
+            Some(lockout) => lockout.label(),
+            None if state.is_loading => tr!("mfa-submit-busy"),
+            None => tr!("mfa-submit"),
+        })
+        .width(Fill)
+        .center(),
     )
-    .style(container_style)
+    .on_press_maybe(can_submit(state).then_some(Message::MfaSubmitPressed))
+    .height(44)
+    .width(Fill)
+    .style(button_style)
+    .padding(10);
+
+    let toggle_link = button(
+        caption(if state.use_backup_code {
+            tr!("mfa-use-app")
+        } else {
+            tr!("mfa-use-backup")
+        })
+        .color(Color::from(AppColorMain::Secondary)),
+    )
+    .on_press(Message::ToggleBackupCode)
+    .style(button::text)
+    .height(14)
+    .padding(0);
+
+    let back_link =
+        button(caption(tr!("back-to-login")).color(Color::from(AppColorMain::Secondary)))
+            .on_press(Message::BackToCredentials)
+            .style(button::text)
+            .height(14)
+            .padding(0);
+
+    column![
+        column![
+            heading(tr!("mfa-title")).width(Fill).center(),
+            caption(if state.use_backup_code {
+                tr!("mfa-hint-backup")
+            } else {
+                tr!("mfa-hint-app")
+            })
+            .width(Fill)
+            .center()
+            .color(Color::from(AppColorForeground::SubtextPrimary)),
+        ]
+        .spacing(8),
+        styled_input(
+            &if state.use_backup_code {
+                tr!("mfa-code-backup")
+            } else {
+                tr!("mfa-code-app")
+            },
+            state.mfa_code.expose(),
+            state.mfa_code_error.as_deref(),
+            Message::MfaCodeInputChanged,
+            //,
+            None,
+            Some(true),
+            Some(state.lockout.is_some()),
+        ),
+        column![verify_button, row![toggle_link], row![back_link]].spacing(8)
+    ]
+    .width(FORM_WIDTH)
+    .padding(24)
+    .spacing(24)
     .into()
 }
 
-pub async fn perform_login(identifier: String, password: String) -> Result<PublicUser, String> {
-    println!("Attempting login for user with identifier: {}.", identifier);
+fn can_submit(state: &State) -> bool {
+    !state.is_loading && state.lockout.is_none()
+}
+
+pub async fn perform_login(identifier: String, password: String) -> Result<LoginOutcome, ApiError> {
+    tracing::info!(%identifier, "Attempting login");
 
     let mut map = HashMap::new();
     map.insert("identifier", identifier);
     map.insert("password", password);
+    map.insert("device_name", device_name());
+    map.insert("platform", platform());
 
     let client = reqwest::Client::new();
-    let res = client
-        .post("http://localhost:8585/auth/login")
-        .json(&map)
-        .send()
-        .await
-        .map_err(|e| format!("Network error: {}", e))?;
-
-    if res.status().is_success() {
-        let success_body = res
-            .json::<AuthResponse>()
-            .await
-            .map_err(|e| format!("Failed to parse successful login: {}", e))?;
-
-        println!("res: {success_body:?}");
-        set_token_from_secure_storage(Some(success_body.refresh_token)).map_err(|e| "no")?;
-        Ok(success_body.user)
-    } else {
-        let error_body = res
-            .json::<ResponseError>()
-            .await
-            .map_err(|e| format!("Failed to parse error response: {}", e))?;
+    let response =
+        try_send_json::<LoginResponse>(client.post(format!("{API_URL}/auth/login")).json(&map))
+            .await?;
 
-        Err(error_body.error.message)
+    match response {
+        LoginResponse::MfaRequired { mfa_ticket } => Ok(LoginOutcome::MfaRequired(mfa_ticket)),
+        LoginResponse::Authenticated(auth) => Session::store(auth)
+            .map(LoginOutcome::Success)
+            .map_err(ApiError::Response),
+    }
+}
+
+/// Completes a login that was interrupted by [`LoginOutcome::MfaRequired`].
+/// The server accepts either a TOTP code or an unused backup code.
+pub async fn perform_mfa_login(
+    ticket: Redacted<String>,
+    code: String,
+) -> Result<Session, ApiError> {
+    tracing::info!("Attempting to complete login with a two-factor code");
+
+    let mut map = HashMap::new();
+    map.insert("ticket", ticket.into_inner());
+    map.insert("code", code.replace([' ', '-'], ""));
+    map.insert("device_name", device_name());
+    map.insert("platform", platform());
+
+    let client = reqwest::Client::new();
+    let auth =
+        try_send_json::<AuthResponse>(client.post(format!("{API_URL}/auth/mfa/login")).json(&map))
+            .await?;
+
+    Session::store(auth).map_err(ApiError::Response)
+}
+
+/// Drops the response of a request that no longer matches what the user typed.
+fn cancel_request(state: &mut State) {
+    if let Some(request) = state.request.take() {
+        request.abort();
+        state.is_loading = false;
+    }
+}
+
+/// Starts the countdown if the server asked us to back off, otherwise shows the error.
+fn apply_error(state: &mut State, error: &ApiError) -> Option<String> {
+    state.is_loading = false;
+    state.request = None;
+    if let Some(retry_after) = error.retry_after() {
+        state.lockout = Some(Lockout::new(retry_after.max(Duration::from_secs(1))));
+    }
+    match error {
+        ApiError::RateLimited { .. } => None,
+        _ => Some(error.to_string()),
+    }
+}
+
+pub fn subscription(state: &State) -> Subscription<Message> {
+    if state.lockout.is_some() {
+        time::every(Duration::from_secs(1)).map(Message::Tick)
+    } else {
+        Subscription::none()
     }
 }
 
 pub fn update(state: &mut State, message: Message) -> Task<Message> {
     match message {
         Message::UsernameInputChanged(value) => {
+            cancel_request(state);
             state.identifier = value;
             state.identifier_error = None;
             Task::none()
         }
         Message::PasswordInputChanged(value) => {
-            state.password = value;
+            cancel_request(state);
+            state.password = Redacted::new(value);
             state.password_error = None;
             Task::none()
         }
+        Message::MfaCodeInputChanged(value) => {
+            cancel_request(state);
+            state.mfa_code = Redacted::new(value);
+            state.mfa_code_error = None;
+            Task::none()
+        }
         Message::LoginButtonPressed => {
+            if !can_submit(state) {
+                return Task::none();
+            }
             state.is_loading = true;
-            Task::perform(
-                perform_login(state.identifier.clone(), state.password.clone()),
+            let (task, handle) = Task::perform(
+                perform_login(state.identifier.clone(), state.password.expose().clone()),
                 |result| match result {
-                    Ok(user) => Message::LoginSuccess(user),
+                    Ok(LoginOutcome::Success(session)) => Message::LoginSuccess(session),
+                    Ok(LoginOutcome::MfaRequired(ticket)) => Message::MfaRequired(ticket),
                     Err(e) => Message::LoginFailed(e),
                 },
             )
+            .abortable();
+            state.request = Some(handle);
+            task
+        }
+        Message::MfaRequired(ticket) => {
+            state.is_loading = false;
+            state.request = None;
+            state.step = LoginStep::Mfa { ticket };
+            state.mfa_code = Redacted::default();
+            state.mfa_code_error = None;
+            state.use_backup_code = false;
+            Task::none()
+        }
+        Message::MfaSubmitPressed => {
+            let LoginStep::Mfa { ticket } = &state.step else {
+                return Task::none();
+            };
+            if !can_submit(state) {
+                return Task::none();
+            }
+            if state.mfa_code.expose().trim().is_empty() {
+                state.mfa_code_error = Some(tr!("field-required"));
+                return Task::none();
+            }
+            let (task, handle) = Task::perform(
+                perform_mfa_login(ticket.clone(), state.mfa_code.expose().trim().to_string()),
+                |result| match result {
+                    Ok(session) => Message::LoginSuccess(session),
+                    Err(e) => Message::MfaFailed(e),
+                },
+            )
+            .abortable();
+            state.is_loading = true;
+            state.request = Some(handle);
+            task
+        }
+        Message::ToggleBackupCode => {
+            state.use_backup_code = !state.use_backup_code;
+            state.mfa_code = Redacted::default();
+            state.mfa_code_error = None;
+            Task::none()
+        }
+        Message::BackToCredentials => {
+            cancel_request(state);
+            state.is_loading = false;
+            state.step = LoginStep::Credentials;
+            state.password = Redacted::default();
+            state.mfa_code = Redacted::default();
+            state.mfa_code_error = None;
+            Task::none()
         }
         Message::LoginSuccess(_) => {
             state.is_loading = false;
+            state.request = None;
             Task::none()
         }
         Message::LoginFailed(error) => {
-            state.is_loading = false;
-            state.identifier_error = Some(error.clone());
-            state.password_error = Some(error);
+            let message = apply_error(state, &error);
+            state.identifier_error = message.clone();
+            state.password_error = message;
+            Task::none()
+        }
+        Message::MfaFailed(error) => {
+            state.mfa_code_error = apply_error(state, &error);
+            Task::none()
+        }
+        Message::Tick(now) => {
+            state.lockout.take_if(|lockout| !lockout.tick(now));
             Task::none()
         }
         Message::RegisterInstead => {
+            cancel_request(state);
             state.is_loading = false;
             Task::done(Message::RequestScreenChange(
                 super::CurrentScreen::Register(super::register_screen::State::new()),
             ))
         }
+        Message::ForgotPasswordInstead => {
+            cancel_request(state);
+            state.is_loading = false;
+            Task::done(Message::RequestScreenChange(
+                super::CurrentScreen::ForgotPassword(forgot_password_screen::State::new()),
+            ))
+        }
         _ => Task::none(),
     }
 }
@@ -1,58 +1,89 @@
+mod attachments;
 mod colors;
 mod components;
+mod config;
 mod constants;
+mod embeds;
+mod emoji;
+mod highlight;
+mod i18n;
+mod keybindings;
 mod login_screen;
+mod markdown;
+mod media;
+mod mentions;
+mod notifications;
+mod reactions;
 mod register_screen;
 mod screens;
 mod styles;
+mod theme;
+mod threads;
+mod typography;
 mod utils;
 
 use chrono::{DateTime, Utc};
 use iced::event::{self, Event};
 use iced::keyboard::key;
-use iced::widget::{self, button, container, text};
-use iced::{Element, Fill, Font, Subscription, Task, Theme, keyboard};
+use iced::widget::{self, button, container, stack, text};
+use iced::{Element, Fill, Subscription, Task, Theme, keyboard};
 use turbo::types::PublicUser;
 
 use crate::colors::AppColorMain;
-use crate::screens::app_screen;
+use crate::config::Settings;
+use crate::emoji::SkinTone;
+use crate::i18n::tr;
+use crate::keybindings::{Action, KeyBinding, Keybindings};
+use crate::notifications::{
+    Inbox, Notification, Toast, ToastAction, ToastKind, Toasts, inbox, toast,
+};
+use crate::screens::{app_screen, forgot_password_screen, settings_screen};
 use crate::styles::button_style;
-use crate::utils::get_user_with_token;
+use crate::theme::{ColorScheme, ThemeEntry};
+use crate::threads::AutoArchive;
+use crate::utils::{Redacted, Session, get_user_with_token, init_logging};
 
 pub fn main() -> iced::Result {
-    iced::application("Fictional Potato", App::update, App::view)
+    let _log_guard = init_logging();
+    tracing::info!(
+        version = env!("CARGO_PKG_VERSION"),
+        "Starting Fictional Potato"
+    );
+
+    let application = iced::application("Fictional Potato", App::update, App::view)
         .subscription(App::subscription)
         .theme(App::theme)
-        //       .font(include_bytes!("../fonts/static/Roboto-Black.ttf").as_slice())
-        //       .font(include_bytes!("../fonts/static/Roboto-Bold.ttf").as_slice())
-        //       .font(include_bytes!("../fonts/static/Roboto-ExtraBold.ttf").as_slice())
-        //       .font(include_bytes!("../fonts/static/Roboto-ExtraLight.ttf").as_slice())
-        //       .font(include_bytes!("../fonts/static/Roboto-Light.ttf").as_slice())
-        //       .font(include_bytes!("../fonts/static/Roboto-Medium.ttf").as_slice())
-        //       .font(include_bytes!("../fonts/static/Roboto-Regular.ttf").as_slice())
-        //       .font(include_bytes!("../fonts/static/Roboto-SemiBold.ttf").as_slice())
-        //       .font(include_bytes!("../fonts/static/Roboto-Thin.ttf").as_slice())
-        //       .font(include_bytes!("../fonts/static/Roboto-BlackItalic.ttf").as_slice())
-        .font(include_bytes!("../fonts/RubikMonoOne-Regular.ttf").as_slice())
-        .default_font(RUBIK)
+        .scale_factor(App::scale_factor);
+
+    typography::FONTS
+        .into_iter()
+        .fold(application, |application, font| application.font(font))
+        .default_font(typography::ROBOTO)
         .run_with(App::new)
 }
 
-//pub const ROBOTO_BLACK: Font = Font::with_name("Roboto-Black");
-//pub const ROBOTO_BLACK_ITALIC: Font = Font::with_name("Roboto-BlackItalic");
-//pub const ROBOTO_THIN: Font = Font::with_name("Roboto-Thin");
-pub const RUBIK: Font = Font::with_name("RubikMonoOne-Regular");
-
 #[derive(Debug, Default)]
 pub struct App {
     pub user: Option<PublicUser>,
     pub theme: Option<Theme>,
-    pub access_token: Option<String>,
-    pub refresh_token: Option<String>,
+    pub access_token: Option<Redacted<String>>,
+    pub refresh_token: Option<Redacted<String>>,
     pub current_modal: Option<ModalType>,
     pub current_dialog: Option<DialogType>,
     pub token_expires: DateTime<Utc>,
     pub current_screen: CurrentScreen,
+    pub config: Settings,
+    /// Bundled palettes followed by the ones found in the user's themes directory.
+    pub themes: Vec<ThemeEntry>,
+    /// What the desktop currently prefers, `None` when it can't tell us.
+    pub system_scheme: Option<ColorScheme>,
+    /// Settings are drawn over the current screen so closing them keeps its state.
+    pub settings_overlay: Option<settings_screen::State>,
+    /// Drawn over everything else, whatever screen is showing.
+    toasts: Toasts<Message>,
+    pub inbox: Inbox,
+    /// Counts changes typed into the settings, only the last one is saved.
+    typed_saves: u64,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -61,25 +92,63 @@ pub enum CurrentScreen {
     Loading,
     Register(register_screen::State),
     Login(login_screen::State),
-    App(app_screen::State),
+    ForgotPassword(forgot_password_screen::State),
+    App(Box<app_screen::State>),
+}
+
+impl CurrentScreen {
+    /// Short name used in logs, without the screen state.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CurrentScreen::Loading => "Loading",
+            CurrentScreen::Register(_) => "Register",
+            CurrentScreen::Login(_) => "Login",
+            CurrentScreen::ForgotPassword(_) => "ForgotPassword",
+            CurrentScreen::App(_) => "App",
+        }
+    }
 }
 
+// Each screen's messages are wrapped in a variant named after it.
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone)]
 enum Message {
+    /// Nothing opens a modal yet, the modals are placeholders for the DM and guild screens.
+    #[allow(dead_code)]
     ShowModal(ModalType),
     HideModal,
     ShowDialog(DialogType),
     HideDialog,
     Event(Event),
 
-    RefreshTokenChecked(Result<PublicUser, String>),
+    RefreshTokenChecked(Result<Session, String>),
+    SettingsLoaded(Result<Settings, String>),
+    SettingsFileChanged(Result<Settings, String>),
+    SaveSettings,
+    /// Saves what was typed if nothing was typed since, see [`App::typed_saves`].
+    SaveTypedSettings(u64),
+    SettingsSaved(Result<(), String>),
+    RestoreKeybindings(Keybindings),
+    UserThemesLoaded(Vec<ThemeEntry>),
+    SystemColorSchemeChanged(Option<ColorScheme>),
 
     ChangeCurrentScreen(CurrentScreen),
-    LogUserIn(Option<PublicUser>),
+    LogUserIn(Session),
+    OpenSettings,
+    CloseSettings,
+
+    Notify(Toast<Message>),
+    EmojiUsed(String),
+    SkinToneChanged(SkinTone),
+    AutoArchiveChanged(AutoArchive),
+    Toast(toast::Message),
+    Inbox(inbox::Message),
 
     LoginScreenMessage(login_screen::Message),
     RegisterScreenMessage(register_screen::Message),
+    ForgotPasswordScreenMessage(forgot_password_screen::Message),
     AppScreenMessage(app_screen::Message),
+    SettingsScreenMessage(settings_screen::Message),
 }
 
 #[derive(Debug, Clone)]
@@ -100,27 +169,194 @@ pub enum DialogType {
     LogoutAllDevices,
     CallIncoming,
     PinnedMessages,
+    Lightbox,
 }
 
 impl App {
+    fn access_token_string(&self) -> String {
+        self.access_token
+            .as_ref()
+            .map(|token| token.expose().clone())
+            .unwrap_or_default()
+    }
+
     fn theme(&self) -> Theme {
-        Theme::CatppuccinMocha
+        self.theme.clone().unwrap_or(Theme::CatppuccinMocha)
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.config.scale_factor()
+    }
+
+    /// Makes the palette for the current light/dark scheme the active one, falling back to
+    /// the default when it is missing (e.g. a custom theme file was deleted).
+    fn apply_theme(&mut self) {
+        let scheme = self.config.color_scheme(self.system_scheme);
+        let id = if self.config.high_contrast {
+            theme::HIGH_CONTRAST_THEME
+        } else {
+            self.config.theme_for(scheme)
+        };
+        let entry = self.themes.iter().find(|entry| entry.id == id).or_else(|| {
+            tracing::warn!(theme = %id, "Unknown theme, using the default");
+            self.themes
+                .iter()
+                .find(|entry| entry.id == scheme.default_theme())
+        });
+        if let Some(entry) = entry {
+            self.theme = Some(theme::activate(entry.palette.clone()));
+        }
+    }
+
+    /// Shows a toast and keeps it in the inbox for later.
+    fn notify(&mut self, toast: Toast<Message>) {
+        self.inbox.push(Notification::from(&toast));
+        self.toasts.push(toast);
+    }
+
+    /// Switches to settings read from disk, returns whether anything changed.
+    fn apply_settings(&mut self, settings: Settings) -> bool {
+        if settings == self.config {
+            return false;
+        }
+        tracing::info!("Applying settings from disk");
+        self.config = settings;
+        self.apply_theme();
+        typography::set_active(self.config.typography());
+        i18n::set_language(self.config.language());
+        true
+    }
+
+    fn key_pressed(&mut self, key: keyboard::Key, modifiers: keyboard::Modifiers) -> Task<Message> {
+        let binding = KeyBinding::from_event(&key, modifiers);
+
+        let is_recording = self
+            .settings_overlay
+            .as_ref()
+            .is_some_and(settings_screen::State::is_recording_key);
+        if let Some(binding) = binding.clone().filter(|_| is_recording) {
+            return Task::done(Message::SettingsScreenMessage(
+                settings_screen::Message::Keybindings(
+                    settings_screen::keybindings::Message::KeyCaptured(binding),
+                ),
+            ));
+        }
+
+        if key == keyboard::Key::Named(key::Named::Tab) && !modifiers.command() {
+            return if modifiers.shift() {
+                widget::focus_previous()
+            } else {
+                widget::focus_next()
+            };
+        }
+
+        match binding.and_then(|binding| self.config.keybindings.action_for(&binding)) {
+            Some(action) => self.perform_action(action),
+            None => Task::none(),
+        }
+    }
+
+    fn perform_action(&mut self, action: Action) -> Task<Message> {
+        tracing::debug!(?action, "Keybinding pressed");
+        match action {
+            Action::ZoomIn | Action::ZoomOut | Action::ResetZoom => {
+                let before = self.config.scale_factor();
+                match action {
+                    Action::ZoomIn => self.config.zoom(1),
+                    Action::ZoomOut => self.config.zoom(-1),
+                    _ => self.config.ui_scale = 1.0,
+                }
+                if self.config.scale_factor() == before {
+                    return Task::none();
+                }
+                Task::perform(config::save(self.config.clone()), Message::SettingsSaved)
+            }
+            Action::CloseModal => {
+                if self.inbox.is_open {
+                    Task::done(Message::Inbox(inbox::Message::Close))
+                } else if self.settings_overlay.is_some() {
+                    Task::done(Message::CloseSettings)
+                } else if self.current_dialog.is_some() {
+                    Task::done(Message::HideDialog)
+                } else if self.current_modal.is_some() {
+                    Task::done(Message::HideModal)
+                } else if let CurrentScreen::App(_) = self.current_screen {
+                    // Then whatever is open on the screen, like a message being edited.
+                    Task::done(Message::AppScreenMessage(app_screen::Message::Shortcut(
+                        action,
+                    )))
+                } else {
+                    Task::none()
+                }
+            }
+            // Settings and the rest of the shortcuts only make sense once logged in, and
+            // the channel shortcuts shouldn't reach the screen hidden behind the settings.
+            _ if self.settings_overlay.is_some() => Task::none(),
+            Action::OpenSettings => match self.current_screen {
+                CurrentScreen::App(_) => Task::done(Message::OpenSettings),
+                _ => Task::none(),
+            },
+            _ => match self.current_screen {
+                CurrentScreen::App(_) => Task::done(Message::AppScreenMessage(
+                    app_screen::Message::Shortcut(action),
+                )),
+                _ => Task::none(),
+            },
+        }
     }
 
     fn subscription(&self) -> Subscription<Message> {
-        event::listen().map(Message::Event)
+        let screen = match &self.current_screen {
+            CurrentScreen::Login(state) => {
+                login_screen::subscription(state).map(Message::LoginScreenMessage)
+            }
+            CurrentScreen::Register(state) => {
+                register_screen::subscription(state).map(Message::RegisterScreenMessage)
+            }
+            CurrentScreen::App(state) => {
+                app_screen::subscription(state, self.config.reduced_motion)
+                    .map(Message::AppScreenMessage)
+            }
+            _ => Subscription::none(),
+        };
+
+        Subscription::batch([
+            event::listen().map(Message::Event),
+            config::watch().map(Message::SettingsFileChanged),
+            theme::system::watch().map(Message::SystemColorSchemeChanged),
+            self.toasts
+                .subscription(self.config.reduced_motion)
+                .map(Message::Toast),
+            screen,
+        ])
     }
 
     fn new() -> (Self, Task<Message>) {
+        let mut app = App {
+            themes: theme::bundled_themes(),
+            ..App::default()
+        };
+        app.apply_theme();
         (
-            App::default(),
-            Task::perform(get_user_with_token(), Message::RefreshTokenChecked),
+            app,
+            Task::batch([
+                Task::perform(config::load(), Message::SettingsLoaded),
+                Task::perform(theme::load_user_themes(), Message::UserThemesLoaded),
+                Task::perform(get_user_with_token(), Message::RefreshTokenChecked),
+            ]),
         )
     }
 
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::ChangeCurrentScreen(screen) => {
+                let _span = tracing::info_span!(
+                    "screen_transition",
+                    from = self.current_screen.name(),
+                    to = screen.name(),
+                )
+                .entered();
+                tracing::info!("Changing screen");
                 self.current_screen = screen;
                 Task::none()
             }
@@ -128,7 +364,7 @@ impl App {
                 if let CurrentScreen::Login(login_state) = &mut self.current_screen {
                     let command = login_screen::update(login_state, msg);
                     command.map(|sub_msg| match sub_msg {
-                        login_screen::Message::LoginSuccess(user) => Message::LogUserIn(Some(user)),
+                        login_screen::Message::LoginSuccess(session) => Message::LogUserIn(session),
                         login_screen::Message::RequestScreenChange(screen) => {
                             Message::ChangeCurrentScreen(screen)
                         }
@@ -142,8 +378,8 @@ impl App {
                 if let CurrentScreen::Register(register_state) = &mut self.current_screen {
                     let command = register_screen::update(register_state, msg);
                     command.map(|sub_msg| match sub_msg {
-                        register_screen::Message::RegisterSuccess(user) => {
-                            Message::LogUserIn(Some(user))
+                        register_screen::Message::RegisterSuccess(session) => {
+                            Message::LogUserIn(session)
                         }
                         register_screen::Message::RequestScreenChange(screen) => {
                             Message::ChangeCurrentScreen(screen)
@@ -154,40 +390,247 @@ impl App {
                     Task::none()
                 }
             }
-            Message::LogUserIn(user_data) => {
-                self.user = user_data;
-                self.current_screen = CurrentScreen::App(app_screen::State::new());
-                Task::none()
+            Message::ForgotPasswordScreenMessage(msg) => {
+                if let CurrentScreen::ForgotPassword(forgot_state) = &mut self.current_screen {
+                    let command = forgot_password_screen::update(forgot_state, msg);
+                    command.map(|sub_msg| match sub_msg {
+                        forgot_password_screen::Message::RequestScreenChange(screen) => {
+                            Message::ChangeCurrentScreen(*screen)
+                        }
+                        _ => Message::ForgotPasswordScreenMessage(sub_msg),
+                    })
+                } else {
+                    Task::none()
+                }
             }
-            Message::Event(event) => match event {
-                Event::Keyboard(keyboard::Event::KeyPressed {
-                    key: keyboard::Key::Named(key::Named::Tab),
-                    modifiers,
-                    ..
-                }) => {
-                    if modifiers.shift() {
-                        widget::focus_previous()
+            Message::AppScreenMessage(msg) => {
+                let access_token = self.access_token_string();
+                let pins_open = matches!(self.current_dialog, Some(DialogType::PinnedMessages));
+                if let CurrentScreen::App(app_state) = &mut self.current_screen {
+                    let command = app_screen::update(app_state, msg, &access_token, &self.config);
+                    command.map(move |sub_msg| match sub_msg {
+                        app_screen::Message::OpenSettings => Message::OpenSettings,
+                        app_screen::Message::ToggleInbox => Message::Inbox(inbox::Message::Toggle),
+                        app_screen::Message::TogglePins if pins_open => Message::HideDialog,
+                        app_screen::Message::TogglePins => {
+                            Message::ShowDialog(DialogType::PinnedMessages)
+                        }
+                        app_screen::Message::ClosePins => Message::HideDialog,
+                        app_screen::Message::OpenLightbox(_) => {
+                            Message::ShowDialog(DialogType::Lightbox)
+                        }
+                        app_screen::Message::CloseLightbox => Message::HideDialog,
+                        app_screen::Message::EmojiUsed(glyph) => Message::EmojiUsed(glyph),
+                        app_screen::Message::SkinToneChanged(tone) => {
+                            Message::SkinToneChanged(tone)
+                        }
+                        app_screen::Message::AutoArchiveChanged(after) => {
+                            Message::AutoArchiveChanged(after)
+                        }
+                        app_screen::Message::Notify(toast) => {
+                            Message::Notify(toast.map(Message::AppScreenMessage))
+                        }
+                        _ => Message::AppScreenMessage(sub_msg),
+                    })
+                } else {
+                    Task::none()
+                }
+            }
+            Message::SettingsScreenMessage(msg) => {
+                let access_token = self.access_token_string();
+                if let Some(settings_state) = &mut self.settings_overlay {
+                    let typing = msg.is_typing();
+                    let resets_keybindings = matches!(
+                        msg,
+                        settings_screen::Message::Keybindings(
+                            settings_screen::keybindings::Message::ResetAllPressed
+                        )
+                    );
+                    let before = self.config.clone();
+                    let themes_before = self.themes.clone();
+                    let command = settings_screen::update(
+                        settings_state,
+                        msg,
+                        &access_token,
+                        &mut self.config,
+                        &mut self.themes,
+                    );
+                    let command = command.map(|sub_msg| match sub_msg {
+                        settings_screen::Message::Close => Message::CloseSettings,
+                        _ => Message::SettingsScreenMessage(sub_msg),
+                    });
+                    if self.config != before || self.themes != themes_before {
+                        self.apply_theme();
+                    }
+                    if resets_keybindings && self.config.keybindings != before.keybindings {
+                        let toast = Toast::new(ToastKind::Success, tr!("notify-keybindings-reset"));
+                        self.notify(toast.with_action(
+                            ToastAction::Undo,
+                            Message::RestoreKeybindings(before.keybindings.clone()),
+                        ));
+                    }
+                    if self.config != before {
+                        typography::set_active(self.config.typography());
+                        i18n::set_language(self.config.language());
+                        let save = if typing {
+                            self.typed_saves += 1;
+                            let typed = self.typed_saves;
+                            Task::perform(
+                                tokio::time::sleep(config::TYPING_SAVE_DELAY),
+                                move |()| Message::SaveTypedSettings(typed),
+                            )
+                        } else {
+                            Task::perform(config::save(self.config.clone()), Message::SettingsSaved)
+                        };
+                        Task::batch([command, save])
                     } else {
-                        widget::focus_next()
+                        command
                     }
+                } else {
+                    Task::none()
                 }
-                _ => Task::none(),
+            }
+            Message::OpenSettings => {
+                self.inbox.is_open = false;
+                let (settings_state, command) = settings_screen::open();
+                self.settings_overlay = Some(settings_state);
+                command.map(Message::SettingsScreenMessage)
+            }
+            Message::CloseSettings => {
+                self.settings_overlay = None;
+                Task::none()
+            }
+            Message::HideModal => {
+                self.current_modal = None;
+                Task::none()
+            }
+            Message::ShowDialog(dialog) => {
+                self.current_dialog = Some(dialog);
+                Task::none()
+            }
+            Message::HideDialog => {
+                self.current_dialog = None;
+                Task::none()
+            }
+            Message::SettingsLoaded(result) => {
+                match result {
+                    Ok(settings) => {
+                        self.apply_settings(settings);
+                    }
+                    Err(e) => tracing::warn!(error = %e, "Keeping current settings"),
+                }
+                Task::none()
+            }
+            Message::SettingsFileChanged(result) => {
+                match result {
+                    Ok(settings) => {
+                        if self.apply_settings(settings) {
+                            self.notify(Toast::new(
+                                ToastKind::Info,
+                                tr!("notify-settings-reloaded"),
+                            ));
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Keeping current settings");
+                        self.notify(Toast::new(
+                            ToastKind::Warning,
+                            tr!("notify-settings-invalid", details = e),
+                        ));
+                    }
+                }
+                Task::none()
+            }
+            Message::UserThemesLoaded(user_themes) => {
+                tracing::info!(count = user_themes.len(), "Loaded user themes");
+                self.themes.retain(|entry| entry.bundled);
+                self.themes.extend(user_themes);
+                self.apply_theme();
+                Task::none()
+            }
+            Message::SystemColorSchemeChanged(scheme) => {
+                tracing::info!(?scheme, "System color scheme");
+                self.system_scheme = scheme;
+                self.apply_theme();
+                Task::none()
+            }
+            Message::SaveSettings => {
+                Task::perform(config::save(self.config.clone()), Message::SettingsSaved)
+            }
+            Message::SaveTypedSettings(typed) if typed == self.typed_saves => {
+                Task::done(Message::SaveSettings)
+            }
+            Message::SaveTypedSettings(_) => Task::none(),
+            Message::SettingsSaved(result) => {
+                if let Err(e) = result {
+                    tracing::error!(error = %e, "Failed to save settings");
+                    let toast = Toast::new(
+                        ToastKind::Error,
+                        tr!("notify-settings-save-failed", details = e),
+                    );
+                    self.notify(toast.with_action(ToastAction::Retry, Message::SaveSettings));
+                }
+                Task::none()
+            }
+            Message::RestoreKeybindings(keybindings) => {
+                self.config.keybindings = keybindings;
+                Task::done(Message::SaveSettings)
+            }
+            Message::Notify(toast) => {
+                self.notify(toast);
+                Task::none()
+            }
+            Message::Toast(msg) => match self.toasts.update(msg) {
+                Some(action) => Task::done(action),
+                None => Task::none(),
             },
+            Message::EmojiUsed(glyph) => {
+                emoji::remember(&mut self.config.recent_emoji, &glyph);
+                Task::done(Message::SaveSettings)
+            }
+            Message::SkinToneChanged(tone) => {
+                self.config.skin_tone = tone;
+                Task::done(Message::SaveSettings)
+            }
+            Message::AutoArchiveChanged(after) => {
+                self.config.thread_auto_archive = after;
+                Task::done(Message::SaveSettings)
+            }
+            Message::Inbox(msg) => {
+                self.inbox.update(msg);
+                Task::none()
+            }
+            Message::LogUserIn(session) => {
+                let _span = tracing::info_span!(
+                    "screen_transition",
+                    from = self.current_screen.name(),
+                    to = "App",
+                )
+                .entered();
+                tracing::info!("User logged in");
+                // Any ID type the server uses, mentions carry it as text.
+                let user_id = session.user.id.to_string();
+                self.user = Some(session.user);
+                self.access_token = Some(session.access_token);
+                self.current_screen =
+                    CurrentScreen::App(Box::new(app_screen::State::new(Some(user_id))));
+                Task::done(Message::AppScreenMessage(app_screen::Message::Load))
+            }
+            Message::Event(Event::Keyboard(keyboard::Event::KeyPressed {
+                key, modifiers, ..
+            })) => self.key_pressed(key, modifiers),
             Message::RefreshTokenChecked(res) => {
                 if res.is_err() {
-                    println!(
-                        "Error getting user from token: {}",
-                        res.err().unwrap_or_default()
+                    tracing::warn!(
+                        error = %res.err().unwrap_or_default(),
+                        "Error getting user from token"
                     );
                     Task::done(Message::ChangeCurrentScreen(CurrentScreen::Login(
                         login_screen::State::new(),
                     )))
                 } else {
-                    let user = res.ok().unwrap();
-                    self.user = Some(user);
-                    Task::done(Message::ChangeCurrentScreen(CurrentScreen::App(
-                        app_screen::State::new(),
-                    )))
+                    let session = res.ok().unwrap();
+                    Task::done(Message::LogUserIn(session))
                 }
             }
             _ => Task::none(),
@@ -195,12 +638,55 @@ impl App {
     }
 
     fn view(&self) -> Element<'_, Message> {
+        stack![self.screen_view()]
+            .push_maybe(self.dialog_view())
+            .push_maybe(
+                self.inbox
+                    .is_open
+                    .then(|| self.inbox.view().map(Message::Inbox)),
+            )
+            .push(
+                self.toasts
+                    .view(self.config.reduced_motion)
+                    .map(Message::Toast),
+            )
+            .into()
+    }
+
+    /// The open dialog, over the screen it was opened from.
+    fn dialog_view(&self) -> Option<Element<'_, Message>> {
+        match (&self.current_dialog, &self.current_screen) {
+            _ if self.settings_overlay.is_some() => None,
+            (Some(DialogType::PinnedMessages), CurrentScreen::App(state)) => {
+                Some(app_screen::pinned_messages(state).map(Message::AppScreenMessage))
+            }
+            (Some(DialogType::Lightbox), CurrentScreen::App(state)) => {
+                Some(app_screen::lightbox(state).map(Message::AppScreenMessage))
+            }
+            _ => None,
+        }
+    }
+
+    fn screen_view(&self) -> Element<'_, Message> {
+        if let Some(settings_state) = &self.settings_overlay {
+            return settings_screen::view(
+                settings_state,
+                &self.config,
+                &self.themes,
+                self.system_scheme,
+            )
+            .map(Message::SettingsScreenMessage);
+        }
+
         let content: Element<Message> = match &self.current_screen {
             CurrentScreen::Login(state) => {
-                login_screen::view(&state).map(Message::LoginScreenMessage)
+                login_screen::view(state).map(Message::LoginScreenMessage)
             }
             CurrentScreen::Register(state) => {
-                register_screen::view(&state).map(Message::RegisterScreenMessage)
+                register_screen::view(state).map(Message::RegisterScreenMessage)
+            }
+            CurrentScreen::ForgotPassword(state) => {
+                forgot_password_screen::view(state).map(Message::ForgotPasswordScreenMessage)
             }
             CurrentScreen::Loading => button(text("Go to register screen").center())
                 .height(40)
@@ -210,7 +696,8 @@ impl App {
                     register_screen::State::new(),
                 )))
                 .into(),
-            CurrentScreen::App(state) => app_screen::view(&state).map(Message::AppScreenMessage),
+            CurrentScreen::App(state) => app_screen::view(state, &self.config, self.inbox.unread())
+                .map(Message::AppScreenMessage),
         };
 
         container(content)
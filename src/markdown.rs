@@ -0,0 +1,897 @@
+use reqwest::Url;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// How deeply inline styles can nest before the rest is kept as plain text, so a message
+/// made of thousands of `**` can't blow the stack.
+const MAX_NESTING: usize = 16;
+
+/// A chat message parsed from the markdown subset Discord supports. Parsing never fails,
+/// anything that isn't valid markdown is kept as the text that was typed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Document {
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    /// A single line of text, empty lines included so the message keeps its spacing.
+    Paragraph(Vec<Span>),
+    /// `#`, `##` or `###` at the start of a line.
+    Heading { level: u8, content: Vec<Span> },
+    /// `- item`, `* item` or `1. item`, nested by two spaces of indentation per level.
+    ListItem {
+        marker: ListMarker,
+        depth: usize,
+        content: Vec<Span>,
+    },
+    /// Lines starting with `> `, or everything after `>>> `.
+    Quote(Vec<Block>),
+    /// Fenced by ``` lines, optionally naming the language after the opening fence.
+    Code {
+        language: Option<String>,
+        code: String,
+        /// Hash of the language and code, which the block's highlighting and whether it's
+        /// expanded are kept under.
+        key: u64,
+    },
+}
+
+impl Block {
+    /// A code block, keyed by its language and code so identical blocks share their
+    /// highlighting.
+    pub fn code(language: Option<String>, code: String) -> Self {
+        let mut hasher = DefaultHasher::new();
+        (&language, &code).hash(&mut hasher);
+        Block::Code {
+            language,
+            code,
+            key: hasher.finish(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListMarker {
+    Bullet,
+    Number(u32),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Style {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+    pub code: bool,
+}
+
+/// A ping or a link to a channel. Users, roles and channels are written as tokens with their
+/// ID, like `<@123>`, so renaming them doesn't break older messages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mention {
+    User(String),
+    Role(String),
+    Channel(String),
+    Everyone,
+    Here,
+}
+
+/// A run of text with the same style.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+    /// Target of a masked link or a URL typed as it is, always an http or https URL.
+    pub link: Option<String>,
+    /// Which spoiler of the message this is part of, counting from 0.
+    pub spoiler: Option<usize>,
+    /// Set when the text is a mention token, which is then drawn as a pill with a name.
+    pub mention: Option<Mention>,
+}
+
+impl Document {
+    /// Every mention in the message, quoted ones included.
+    pub fn mentions(&self) -> Vec<&Mention> {
+        fn collect<'a>(blocks: &'a [Block], mentions: &mut Vec<&'a Mention>) {
+            for block in blocks {
+                match block {
+                    Block::Paragraph(content)
+                    | Block::Heading { content, .. }
+                    | Block::ListItem { content, .. } => {
+                        mentions.extend(content.iter().filter_map(|span| span.mention.as_ref()));
+                    }
+                    Block::Quote(quoted) => collect(quoted, mentions),
+                    Block::Code { .. } => {}
+                }
+            }
+        }
+
+        let mut mentions = Vec::new();
+        collect(&self.blocks, &mut mentions);
+        mentions
+    }
+
+    /// Where the message's links point, in order and each once, quoted ones included. Links
+    /// in spoilers are left out, a preview would give them away.
+    pub fn links(&self) -> Vec<&str> {
+        fn collect<'a>(blocks: &'a [Block], links: &mut Vec<&'a str>) {
+            for block in blocks {
+                match block {
+                    Block::Paragraph(content)
+                    | Block::Heading { content, .. }
+                    | Block::ListItem { content, .. } => {
+                        let targets = content
+                            .iter()
+                            .filter(|span| span.spoiler.is_none())
+                            .filter_map(|span| span.link.as_deref());
+                        for target in targets {
+                            if !links.contains(&target) {
+                                links.push(target);
+                            }
+                        }
+                    }
+                    Block::Quote(quoted) => collect(quoted, links),
+                    Block::Code { .. } => {}
+                }
+            }
+        }
+
+        let mut links = Vec::new();
+        collect(&self.blocks, &mut links);
+        links
+    }
+}
+
+pub fn parse(source: &str) -> Document {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut spoilers = 0;
+    Document {
+        blocks: parse_blocks(&lines, true, &mut spoilers),
+    }
+}
+
+/// Quotes don't nest, a `> ` inside a quote is shown as typed.
+fn parse_blocks(lines: &[&str], quotes: bool, spoilers: &mut usize) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+
+        if let Some((block, used)) = code_block(&lines[index..]) {
+            blocks.push(block);
+            index += used;
+            continue;
+        }
+
+        let block_quote = line.strip_prefix(">>> ").or((line == ">>>").then_some(""));
+        if let Some(rest) = block_quote.filter(|_| quotes) {
+            let quoted: Vec<&str> = std::iter::once(rest)
+                .chain(lines[index + 1..].iter().copied())
+                .collect();
+            blocks.push(Block::Quote(parse_blocks(&quoted, false, spoilers)));
+            break;
+        }
+
+        if quotes && quoted_line(line).is_some() {
+            let quoted: Vec<&str> = lines[index..]
+                .iter()
+                .map_while(|line| quoted_line(line))
+                .collect();
+            index += quoted.len();
+            blocks.push(Block::Quote(parse_blocks(&quoted, false, spoilers)));
+            continue;
+        }
+
+        blocks.push(line_block(line, spoilers));
+        index += 1;
+    }
+
+    blocks
+}
+
+fn quoted_line(line: &str) -> Option<&str> {
+    line.strip_prefix("> ").or((line == ">").then_some(""))
+}
+
+/// A fenced code block starting at the first line, with the number of lines it takes.
+/// `None` when the fence is never closed, the backticks are then shown as typed.
+fn code_block(lines: &[&str]) -> Option<(Block, usize)> {
+    let rest = lines.first()?.strip_prefix("```")?;
+
+    if let Some(code) = rest.strip_suffix("```").filter(|code| !code.is_empty()) {
+        return Some((Block::code(None, code.to_string()), 1));
+    }
+
+    // Like Discord, a single word right after the fence names the language, anything else
+    // is the first line of code.
+    let is_language = !rest.is_empty()
+        && rest
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '#' | '_' | '.'));
+    let (language, first) = if is_language {
+        (Some(rest.to_string()), None)
+    } else {
+        (None, Some(rest).filter(|rest| !rest.is_empty()))
+    };
+
+    let (end, last) = lines
+        .iter()
+        .enumerate()
+        .skip(1)
+        .find_map(|(index, line)| Some((index, line.trim_end().strip_suffix("```")?)))?;
+
+    let code: Vec<&str> = first
+        .into_iter()
+        .chain(lines[1..end].iter().copied())
+        .chain(Some(last).filter(|last| !last.is_empty()))
+        .collect();
+    Some((Block::code(language, code.join("\n")), end + 1))
+}
+
+fn line_block(line: &str, spoilers: &mut usize) -> Block {
+    for (level, prefix) in [(1, "# "), (2, "## "), (3, "### ")] {
+        if let Some(rest) = line.strip_prefix(prefix) {
+            return Block::Heading {
+                level,
+                content: inline(rest, spoilers),
+            };
+        }
+    }
+
+    let content = line.trim_start_matches(' ');
+    let depth = (line.len() - content.len()) / 2;
+    let marker = if let Some(rest) = content.strip_prefix("- ").or(content.strip_prefix("* ")) {
+        Some((ListMarker::Bullet, rest))
+    } else {
+        content.split_once(". ").and_then(|(number, rest)| {
+            let is_number = !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit());
+            let number = number.parse().ok().filter(|_| is_number)?;
+            Some((ListMarker::Number(number), rest))
+        })
+    };
+
+    match marker {
+        Some((marker, rest)) => Block::ListItem {
+            marker,
+            depth,
+            content: inline(rest, spoilers),
+        },
+        None => Block::Paragraph(inline(line, spoilers)),
+    }
+}
+
+fn inline(text: &str, spoilers: &mut usize) -> Vec<Span> {
+    let mut parser = Inline {
+        spans: Vec::new(),
+        spoilers,
+    };
+    parser.parse(text, Style::default(), None, None, 0);
+    parser.spans
+}
+
+struct Inline<'a> {
+    spans: Vec<Span>,
+    spoilers: &'a mut usize,
+}
+
+/// Delimiters that wrap styled text, longest first so `**` isn't read as two `*`.
+const DELIMITERS: [&str; 7] = ["***", "**", "__", "~~", "||", "*", "_"];
+
+impl Inline<'_> {
+    fn push(&mut self, text: &str, style: Style, link: Option<&str>, spoiler: Option<usize>) {
+        if text.is_empty() {
+            return;
+        }
+        let link = link.map(str::to_string);
+        match self.spans.last_mut() {
+            Some(last)
+                if last.style == style
+                    && last.link == link
+                    && last.spoiler == spoiler
+                    && last.mention.is_none() =>
+            {
+                last.text.push_str(text);
+            }
+            _ => self.spans.push(Span {
+                text: text.to_string(),
+                style,
+                link,
+                spoiler,
+                mention: None,
+            }),
+        }
+    }
+
+    /// Mentions get a span of their own, two in a row are still two pills.
+    fn push_mention(
+        &mut self,
+        token: &str,
+        style: Style,
+        spoiler: Option<usize>,
+        mention: Mention,
+    ) {
+        self.spans.push(Span {
+            text: token.to_string(),
+            style,
+            link: None,
+            spoiler,
+            mention: Some(mention),
+        });
+    }
+
+    fn parse(
+        &mut self,
+        text: &str,
+        style: Style,
+        link: Option<&str>,
+        spoiler: Option<usize>,
+        depth: usize,
+    ) {
+        if depth > MAX_NESTING {
+            self.push(text, style, link, spoiler);
+            return;
+        }
+
+        // Everything between `plain` and `index` is text without markup. Delimiters are
+        // ASCII, so any index they're found at is a char boundary.
+        let mut plain = 0;
+        let mut index = 0;
+
+        while index < text.len() {
+            let rest = &text[index..];
+
+            if let Some(escaped) = rest
+                .strip_prefix('\\')
+                .and_then(|after| after.chars().next())
+                .filter(char::is_ascii_punctuation)
+            {
+                self.push(&text[plain..index], style, link, spoiler);
+                self.push(&escaped.to_string(), style, link, spoiler);
+                index += 2;
+                plain = index;
+                continue;
+            }
+
+            if rest.starts_with('`') {
+                let ticks = rest.len() - rest.trim_start_matches('`').len();
+                let fence = &rest[..ticks];
+                if let Some(end) = rest[ticks..].find(fence).filter(|&end| end > 0) {
+                    self.push(&text[plain..index], style, link, spoiler);
+                    let code = Style {
+                        code: true,
+                        ..style
+                    };
+                    self.push(&rest[ticks..ticks + end], code, link, spoiler);
+                    index += ticks * 2 + end;
+                    plain = index;
+                } else {
+                    index += ticks;
+                }
+                continue;
+            }
+
+            // Links can't contain links, so a label like `[a](b)` is kept as it is.
+            if let Some((label, url, used)) = masked_link(rest).filter(|_| link.is_none()) {
+                self.push(&text[plain..index], style, link, spoiler);
+                self.parse(label, style, Some(&url), spoiler, depth + 1);
+                index += used;
+                plain = index;
+                continue;
+            }
+
+            // A mention can't be a link target, inside a link label it's plain text.
+            let before = text[..index].chars().next_back();
+            if let Some((mention, used)) = mention(rest, before).filter(|_| link.is_none()) {
+                self.push(&text[plain..index], style, link, spoiler);
+                self.push_mention(&rest[..used], style, spoiler, mention);
+                index += used;
+                plain = index;
+                continue;
+            }
+
+            // Inside a link label a URL is just text, and one in the middle of a word isn't
+            // a URL.
+            let starts_word = !before.is_some_and(char::is_alphanumeric);
+            if let Some(url) = bare_url(rest).filter(|_| link.is_none() && starts_word) {
+                self.push(&text[plain..index], style, link, spoiler);
+                self.push(url, style, Some(url), spoiler);
+                index += url.len();
+                plain = index;
+                continue;
+            }
+
+            // Custom emoji can only be drawn as images outside of text, in text they're
+            // shown by name.
+            if let Some((name, used)) = custom_emoji(rest) {
+                self.push(&text[plain..index], style, link, spoiler);
+                self.push(&format!(":{name}:"), style, link, spoiler);
+                index += used;
+                plain = index;
+                continue;
+            }
+
+            let delimited = DELIMITERS.iter().find_map(|delimiter| {
+                let inner = rest.strip_prefix(delimiter)?;
+                let end = closing(text, index, delimiter)?;
+                Some((*delimiter, &inner[..end]))
+            });
+
+            if let Some((delimiter, inner)) = delimited {
+                self.push(&text[plain..index], style, link, spoiler);
+                let mut inner_style = style;
+                let mut inner_spoiler = spoiler;
+                match delimiter {
+                    "***" => {
+                        inner_style.bold = true;
+                        inner_style.italic = true;
+                    }
+                    "**" => inner_style.bold = true,
+                    "__" => inner_style.underline = true,
+                    "~~" => inner_style.strikethrough = true,
+                    "||" => {
+                        inner_spoiler = Some(*self.spoilers);
+                        *self.spoilers += 1;
+                    }
+                    _ => inner_style.italic = true,
+                }
+                self.parse(inner, inner_style, link, inner_spoiler, depth + 1);
+                index += delimiter.len() * 2 + inner.len();
+                plain = index;
+                continue;
+            }
+
+            index += rest.chars().next().map_or(1, char::len_utf8);
+        }
+
+        self.push(&text[plain..], style, link, spoiler);
+    }
+}
+
+/// Where the text wrapped by `delimiter` at `start` ends, relative to the start of that
+/// text. Wrapped text can't be empty, and `_` only counts at word boundaries so snake_case
+/// names stay as they are.
+fn closing(text: &str, start: usize, delimiter: &str) -> Option<usize> {
+    let inner_start = start + delimiter.len();
+    let inner = &text[inner_start..];
+
+    if delimiter == "_" {
+        let before = text[..start].chars().next_back();
+        if before.is_some_and(char::is_alphanumeric) {
+            return None;
+        }
+    }
+
+    let mut from = 0;
+    loop {
+        let end = from + inner[from..].find(delimiter)?;
+        let after = &inner[end + delimiter.len()..];
+        // A doubled `*` or `_` is bold or underline nested inside, skip over both.
+        if delimiter.len() == 1 && after.starts_with(delimiter) {
+            from = end + 2;
+            continue;
+        }
+        if end > 0 && (delimiter != "_" || !after.starts_with(char::is_alphanumeric)) {
+            return Some(end);
+        }
+        from = end + delimiter.len();
+    }
+}
+
+/// `[label](https://example.com)`, returns the label, the URL and the length of the link
+/// markup. Only http and https URLs are linked, anything else stays plain text.
+fn masked_link(text: &str) -> Option<(&str, String, usize)> {
+    let rest = text.strip_prefix('[')?;
+    let (label, rest) = rest.split_once("](")?;
+    let (url, _) = rest.split_once(')')?;
+    if label.is_empty() || label.contains(['[', ']', '\n']) {
+        return None;
+    }
+    let url = url.trim_start_matches('<').trim_end_matches('>');
+    let parsed = Url::parse(url).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return None;
+    }
+    let used = 1 + label.len() + 2 + rest.find(')')? + 1;
+    Some((label, parsed.to_string(), used))
+}
+
+/// A URL typed as it is, like `https://example.com/a`, up to the next space. Punctuation at
+/// the end is left out, it more likely ends the sentence than the URL.
+fn bare_url(text: &str) -> Option<&str> {
+    if !text.starts_with("https://") && !text.starts_with("http://") {
+        return None;
+    }
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    let url = text[..end].trim_end_matches(['.', ',', ':', ';', '!', '?', ')', '"', '\'']);
+    Url::parse(url).ok()?.host()?;
+    Some(url)
+}
+
+/// A mention token at the start of `text`, with its length. `@everyone` and `@here` only
+/// count as whole words, `before` is the character in front of `text`.
+fn mention(text: &str, before: Option<char>) -> Option<(Mention, usize)> {
+    for (keyword, mention) in [("@everyone", Mention::Everyone), ("@here", Mention::Here)] {
+        let Some(after) = text.strip_prefix(keyword) else {
+            continue;
+        };
+        let alphanumeric = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+        if alphanumeric(before) || alphanumeric(after.chars().next()) {
+            return None;
+        }
+        return Some((mention, keyword.len()));
+    }
+
+    let (token, _) = text.strip_prefix('<')?.split_once('>')?;
+    let (mention, id): (fn(String) -> Mention, &str) = match token.strip_prefix('@') {
+        Some(id) => match id.strip_prefix('&') {
+            Some(role) => (Mention::Role, role),
+            // `<@!id>` is an older form of a user mention.
+            None => (Mention::User, id.strip_prefix('!').unwrap_or(id)),
+        },
+        None => (Mention::Channel, token.strip_prefix('#')?),
+    };
+    if id.is_empty() || !id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-') {
+        return None;
+    }
+    Some((mention(id.to_string()), token.len() + 2))
+}
+
+/// A custom emoji token like `<:name:id>` or `<a:name:id>` at the start of `text`, with its
+/// name and length.
+fn custom_emoji(text: &str) -> Option<(&str, usize)> {
+    let (token, _) = text.strip_prefix('<')?.split_once('>')?;
+    let unanimated = token.strip_prefix('a').unwrap_or(token);
+    let (name, id) = unanimated.strip_prefix(':')?.split_once(':')?;
+    let valid = |part: &str| {
+        !part.is_empty() && part.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+    };
+    (valid(name) && valid(id)).then_some((name, token.len() + 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// The spans of a message that's a single paragraph.
+    fn spans(source: &str) -> Vec<Span> {
+        match parse(source).blocks.as_slice() {
+            [Block::Paragraph(spans)] => spans.clone(),
+            blocks => panic!("expected one paragraph, got {blocks:?}"),
+        }
+    }
+
+    fn styled(source: &str) -> Vec<(String, Style)> {
+        spans(source)
+            .into_iter()
+            .map(|span| (span.text, span.style))
+            .collect()
+    }
+
+    fn text(text: &str, style: Style) -> (String, Style) {
+        (text.to_string(), style)
+    }
+
+    const PLAIN: Style = Style {
+        bold: false,
+        italic: false,
+        underline: false,
+        strikethrough: false,
+        code: false,
+    };
+    const BOLD: Style = Style {
+        bold: true,
+        ..PLAIN
+    };
+    const ITALIC: Style = Style {
+        italic: true,
+        ..PLAIN
+    };
+    const CODE: Style = Style {
+        code: true,
+        ..PLAIN
+    };
+
+    #[test]
+    fn bold_and_italic() {
+        assert_eq!(
+            styled("a **b** c"),
+            [text("a ", PLAIN), text("b", BOLD), text(" c", PLAIN)]
+        );
+        assert_eq!(
+            styled("*a* _b_"),
+            [text("a", ITALIC), text(" ", PLAIN), text("b", ITALIC)]
+        );
+        assert_eq!(
+            styled("***a***"),
+            [text(
+                "a",
+                Style {
+                    bold: true,
+                    italic: true,
+                    ..PLAIN
+                }
+            )]
+        );
+        assert_eq!(
+            styled("*a **b** c*"),
+            [
+                text("a ", ITALIC),
+                text(
+                    "b",
+                    Style {
+                        bold: true,
+                        ..ITALIC
+                    }
+                ),
+                text(" c", ITALIC)
+            ]
+        );
+    }
+
+    #[test]
+    fn underline_and_strikethrough() {
+        let underline = Style {
+            underline: true,
+            ..PLAIN
+        };
+        let strikethrough = Style {
+            strikethrough: true,
+            ..PLAIN
+        };
+        assert_eq!(styled("__a__"), [text("a", underline)]);
+        assert_eq!(styled("~~a~~"), [text("a", strikethrough)]);
+    }
+
+    #[test]
+    fn unmatched_and_empty_delimiters_stay_as_typed() {
+        for source in ["**a", "a**", "~~", "2 * 3 = 6", "snake_case_name", "||"] {
+            assert_eq!(styled(source), [text(source, PLAIN)], "{source}");
+        }
+    }
+
+    #[test]
+    fn code_spans() {
+        assert_eq!(
+            styled("use `*x*` here"),
+            [text("use ", PLAIN), text("*x*", CODE), text(" here", PLAIN)]
+        );
+        assert_eq!(styled("``a ` b``"), [text("a ` b", CODE)]);
+        assert_eq!(styled("`open"), [text("`open", PLAIN)]);
+    }
+
+    #[test]
+    fn fenced_code_blocks() {
+        let document = parse("```rust\nfn main() {}\n```");
+        assert_eq!(
+            document.blocks,
+            [Block::code(Some("rust".into()), "fn main() {}".into())]
+        );
+        let document = parse("```one line```");
+        assert_eq!(document.blocks, [Block::code(None, "one line".into())]);
+        let document = parse("```not a language\nx```");
+        assert_eq!(
+            document.blocks,
+            [Block::code(None, "not a language\nx".into())]
+        );
+    }
+
+    #[test]
+    fn unclosed_fences_are_text() {
+        let document = parse("```rust\nfn main() {}");
+        assert_eq!(document.blocks.len(), 2);
+        assert!(
+            document
+                .blocks
+                .iter()
+                .all(|block| matches!(block, Block::Paragraph(_)))
+        );
+    }
+
+    #[test]
+    fn quotes() {
+        let document = parse("> a\n> b\nc");
+        assert_eq!(document.blocks.len(), 2);
+        let Block::Quote(quoted) = &document.blocks[0] else {
+            panic!("expected a quote, got {:?}", document.blocks[0]);
+        };
+        assert_eq!(quoted.len(), 2);
+        // Quotes don't nest.
+        let Block::Quote(quoted) = &parse("> > a").blocks[0] else {
+            panic!("expected a quote");
+        };
+        assert_eq!(quoted, &[Block::Paragraph(vec![plain_span("> a")])]);
+    }
+
+    #[test]
+    fn block_quotes_take_the_rest_of_the_message() {
+        let document = parse("a\n>>> b\nc\n> d");
+        assert_eq!(document.blocks.len(), 2);
+        let Block::Quote(quoted) = &document.blocks[1] else {
+            panic!("expected a quote, got {:?}", document.blocks[1]);
+        };
+        assert_eq!(quoted.len(), 3);
+    }
+
+    #[test]
+    fn lists() {
+        let document = parse("- a\n  * b\n3. c\n3.c");
+        assert_eq!(
+            document.blocks[..3],
+            [
+                Block::ListItem {
+                    marker: ListMarker::Bullet,
+                    depth: 0,
+                    content: vec![plain_span("a")],
+                },
+                Block::ListItem {
+                    marker: ListMarker::Bullet,
+                    depth: 1,
+                    content: vec![plain_span("b")],
+                },
+                Block::ListItem {
+                    marker: ListMarker::Number(3),
+                    depth: 0,
+                    content: vec![plain_span("c")],
+                },
+            ]
+        );
+        assert!(matches!(document.blocks[3], Block::Paragraph(_)));
+    }
+
+    #[test]
+    fn headings() {
+        for (source, level) in [("# a", 1), ("## a", 2), ("### a", 3)] {
+            assert_eq!(
+                parse(source).blocks,
+                [Block::Heading {
+                    level,
+                    content: vec![plain_span("a")],
+                }]
+            );
+        }
+        assert!(matches!(parse("#### a").blocks[0], Block::Paragraph(_)));
+        assert!(matches!(parse("#a").blocks[0], Block::Paragraph(_)));
+    }
+
+    #[test]
+    fn spoilers_are_counted_across_the_message() {
+        let document = parse("||a|| b ||c||\n||d||");
+        let spoilers: Vec<usize> = document
+            .blocks
+            .iter()
+            .flat_map(|block| match block {
+                Block::Paragraph(spans) => spans.clone(),
+                _ => Vec::new(),
+            })
+            .filter_map(|span| span.spoiler)
+            .collect();
+        assert_eq!(spoilers, [0, 1, 2]);
+    }
+
+    #[test]
+    fn masked_links() {
+        let linked = spans("see [the **docs**](https://example.com/a) now");
+        assert_eq!(linked[1].text, "the ");
+        assert_eq!(linked[1].link.as_deref(), Some("https://example.com/a"));
+        assert_eq!(linked[2].text, "docs");
+        assert!(linked[2].style.bold);
+        assert_eq!(
+            parse("[a](javascript:alert(1))").links(),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            parse("[a](https://x.dev) [b](<https://x.dev>)").links(),
+            ["https://x.dev/"]
+        );
+    }
+
+    #[test]
+    fn bare_urls() {
+        let linked = spans("go to https://example.com/a, then stop");
+        assert_eq!(linked[1].text, "https://example.com/a");
+        assert_eq!(linked[1].link.as_deref(), Some("https://example.com/a"));
+        assert!(
+            spans("nohttps://example.com")
+                .iter()
+                .all(|s| s.link.is_none())
+        );
+        assert!(parse("||https://example.com||").links().is_empty());
+    }
+
+    #[test]
+    fn mentions_and_custom_emoji() {
+        let spans = spans("<@1><@!2><@&3><#4> @everyone me@here <:blob:5>");
+        let mentions: Vec<_> = spans.iter().filter_map(|s| s.mention.clone()).collect();
+        assert_eq!(
+            mentions,
+            [
+                Mention::User("1".into()),
+                Mention::User("2".into()),
+                Mention::Role("3".into()),
+                Mention::Channel("4".into()),
+                Mention::Everyone,
+            ]
+        );
+        assert!(spans.last().unwrap().text.ends_with(":blob:"));
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(styled(r"\*a\* \_b\_ \`c\`"), [text("*a* _b_ `c`", PLAIN)]);
+        // Only punctuation can be escaped.
+        assert_eq!(styled(r"\a"), [text(r"\a", PLAIN)]);
+    }
+
+    #[test]
+    fn nesting_is_capped() {
+        let depth = MAX_NESTING + 8;
+        let source = format!("{}a{}", "*_".repeat(depth), "_*".repeat(depth));
+        let spans = spans(&source);
+        let text: String = spans.iter().map(|span| span.text.as_str()).collect();
+        assert!(text.contains('a'));
+        // Past the cap, the rest is kept as it was typed.
+        assert!(text.contains("*_"));
+    }
+
+    #[test]
+    fn many_delimiters_dont_overflow() {
+        let source = "**".repeat(10_000);
+        let _ = parse(&source);
+        let source = "||*".repeat(5_000);
+        let _ = parse(&source);
+    }
+
+    fn plain_span(text: &str) -> Span {
+        Span {
+            text: text.to_string(),
+            style: Style::default(),
+            link: None,
+            spoiler: None,
+            mention: None,
+        }
+    }
+
+    /// Every line of the message, as the text of its spans.
+    fn lines(document: &Document) -> Vec<String> {
+        document
+            .blocks
+            .iter()
+            .map(|block| match block {
+                Block::Paragraph(spans) => spans.iter().map(|span| span.text.as_str()).collect(),
+                block => panic!("expected only paragraphs, got {block:?}"),
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn parsing_never_panics(source in any::<String>()) {
+            let document = parse(&source);
+            let _ = document.links();
+            let _ = document.mentions();
+        }
+
+        #[test]
+        fn parsing_markup_never_panics(source in r"([*_~|`>#\-\\\[\]()<@&!:a1 \n]|https://x\.y/)*") {
+            let document = parse(&source);
+            let _ = document.links();
+        }
+
+        #[test]
+        fn unstyled_text_round_trips(source in r"[a-zA-Z0-9 ,!?'éא😀\n]*") {
+            let document = parse(&source);
+            let expected: Vec<&str> = source.lines().collect();
+            prop_assert_eq!(lines(&document), expected);
+            let spans = document.blocks.iter().flat_map(|block| match block {
+                Block::Paragraph(spans) => spans.iter(),
+                _ => [].iter(),
+            });
+            for span in spans {
+                prop_assert_eq!(span.style, Style::default());
+                prop_assert!(span.link.is_none() && span.spoiler.is_none() && span.mention.is_none());
+            }
+        }
+    }
+}
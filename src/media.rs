@@ -0,0 +1,340 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+    time::{Duration, SystemTime},
+};
+
+use iced::{Task, advanced::image::Bytes, widget::image};
+use sha2::{Digest, Sha256};
+use tokio::sync::Semaphore;
+
+/// Decoded images are kept in memory up to about this many bytes of pixels, those requested
+/// longest ago make room for new ones.
+const MEMORY_BUDGET: usize = 128 * 1024 * 1024;
+/// Downloads are kept on disk up to this many bytes, those used longest ago make room for new
+/// ones.
+const DISK_BUDGET: u64 = 512 * 1024 * 1024;
+/// How many images are downloaded at once, the others wait their turn.
+const MAX_DOWNLOADS: usize = 4;
+/// Larger images are given up on, whether the server says how big they are or not.
+const MAX_DOWNLOAD_SIZE: u64 = 25 * 1024 * 1024;
+/// A download taking longer than this is given up on, so it doesn't hold its turn forever.
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+static DOWNLOADS: LazyLock<Semaphore> = LazyLock::new(|| Semaphore::new(MAX_DOWNLOADS));
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+});
+
+/// An image by where it's downloaded from and how big it's decoded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub url: String,
+    pub size: Size,
+}
+
+impl Key {
+    pub fn original(url: String) -> Self {
+        Self {
+            url,
+            size: Size::Original,
+        }
+    }
+
+    /// Scaled down to fit `max`, twice what it's drawn at so it stays sharp on high DPI
+    /// screens.
+    pub fn thumbnail(url: String, max: u32) -> Self {
+        Self {
+            url,
+            size: Size::Thumbnail(max),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Size {
+    Original,
+    /// Scaled down to fit a square this many pixels wide, smaller images are left as they
+    /// are.
+    Thumbnail(u32),
+}
+
+/// An image decoded off the UI thread, so drawing it doesn't decode it again.
+#[derive(Debug, Clone)]
+pub struct Decoded {
+    handle: image::Handle,
+    /// Of its pixels, counted against [`MEMORY_BUDGET`].
+    bytes: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Entry {
+    Loading,
+    /// With when it was last requested, see [`Cache::request`].
+    Loaded {
+        handle: image::Handle,
+        bytes: usize,
+        used: u64,
+    },
+    /// Tried again the next time it's requested.
+    Failed,
+}
+
+/// What's drawn for an image.
+pub enum Lookup<'a> {
+    Loaded(&'a image::Handle),
+    /// Drawn as a placeholder.
+    Loading,
+    Failed,
+}
+
+/// Downloaded and decoded images, like avatars, icons and attachments, so they're fetched and
+/// decoded once however often they're drawn. Screens request the images of what they're about
+/// to show, those requested longest ago are dropped from memory past [`MEMORY_BUDGET`] and
+/// loaded again from the disk cache when they're requested next.
+#[derive(Debug, Clone, Default)]
+pub struct Cache {
+    entries: HashMap<Key, Entry>,
+    /// Bytes of pixels of the images in memory.
+    bytes: usize,
+    /// Counts requests, to tell when each image was last requested.
+    clock: u64,
+}
+
+impl Cache {
+    /// Images that weren't requested, or were dropped from memory, are `Loading` until they
+    /// are.
+    pub fn get(&self, key: &Key) -> Lookup<'_> {
+        match self.entries.get(key) {
+            Some(Entry::Loaded { handle, .. }) => Lookup::Loaded(handle),
+            Some(Entry::Loading) | None => Lookup::Loading,
+            Some(Entry::Failed) => Lookup::Failed,
+        }
+    }
+
+    /// Loads an image that isn't in memory, or failed to load before, from the disk cache or
+    /// by downloading it. One that is in memory is marked as just used.
+    pub fn request(&mut self, key: Key) -> Task<(Key, Result<Decoded, String>)> {
+        self.clock += 1;
+        match self.entries.get_mut(&key) {
+            Some(Entry::Loaded { used, .. }) => {
+                *used = self.clock;
+                return Task::none();
+            }
+            Some(Entry::Loading) => return Task::none(),
+            Some(Entry::Failed) | None => {}
+        }
+        self.entries.insert(key.clone(), Entry::Loading);
+        Task::perform(load(key.clone()), move |result| (key.clone(), result))
+    }
+
+    pub fn insert(&mut self, key: Key, result: Result<Decoded, String>) {
+        match result {
+            Ok(Decoded { handle, bytes }) => {
+                self.bytes += bytes;
+                let used = self.clock;
+                let loaded = Entry::Loaded {
+                    handle,
+                    bytes,
+                    used,
+                };
+                let replaced = self.entries.insert(key.clone(), loaded);
+                if let Some(Entry::Loaded { bytes, .. }) = replaced {
+                    self.bytes -= bytes;
+                }
+                self.evict(&key);
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, url = %key.url, "Failed to load image");
+                self.entries.insert(key, Entry::Failed);
+            }
+        }
+    }
+
+    /// Drops the images requested longest ago until the rest fit in [`MEMORY_BUDGET`]. The image
+    /// just loaded stays even if it's bigger than that, or it would be loaded over and over.
+    fn evict(&mut self, loaded: &Key) {
+        while self.bytes > MEMORY_BUDGET {
+            let oldest = self
+                .entries
+                .iter()
+                .filter(|(key, _)| *key != loaded)
+                .filter_map(|(key, entry)| match entry {
+                    Entry::Loaded { used, .. } => Some((*used, key)),
+                    _ => None,
+                })
+                .min_by_key(|(used, _)| *used)
+                .map(|(_, key)| key.clone());
+            let Some(oldest) = oldest else {
+                break;
+            };
+            if let Some(Entry::Loaded { bytes, .. }) = self.entries.remove(&oldest) {
+                self.bytes -= bytes;
+            }
+        }
+    }
+}
+
+async fn load(key: Key) -> Result<Decoded, String> {
+    let url = key.url.clone();
+    let cached = tokio::task::spawn_blocking(move || read_cached(&url))
+        .await
+        .ok()
+        .flatten();
+    let bytes = match cached {
+        Some(bytes) => bytes,
+        None => download(&key.url).await?,
+    };
+    tokio::task::spawn_blocking(move || decode(&bytes, key.size))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Downloads an image and keeps it in the disk cache, waiting while [`MAX_DOWNLOADS`] others
+/// are running. The body is read a piece at a time, stopping past [`MAX_DOWNLOAD_SIZE`].
+async fn download(url: &str) -> Result<Bytes, String> {
+    let _permit = DOWNLOADS.acquire().await.map_err(|e| e.to_string())?;
+    let mut response = CLIENT
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?;
+    let too_large = || format!("Image is larger than {MAX_DOWNLOAD_SIZE} bytes");
+    if response
+        .content_length()
+        .is_some_and(|length| length > MAX_DOWNLOAD_SIZE)
+    {
+        return Err(too_large());
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        if (body.len() + chunk.len()) as u64 > MAX_DOWNLOAD_SIZE {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    let bytes = Bytes::from(body);
+
+    let (url, stored) = (url.to_string(), bytes.clone());
+    // It's still shown if it can't be kept, it's downloaded again next time.
+    if let Ok(Err(e)) = tokio::task::spawn_blocking(move || store(&url, &stored)).await {
+        tracing::warn!(error = %e, "Failed to keep image in the disk cache");
+    }
+    Ok(bytes)
+}
+
+fn decode(bytes: &[u8], size: Size) -> Result<Decoded, String> {
+    let mut decoded = ::image::load_from_memory(bytes).map_err(|e| e.to_string())?;
+    match size {
+        Size::Thumbnail(max) if decoded.width() > max || decoded.height() > max => {
+            decoded = decoded.thumbnail(max, max);
+        }
+        _ => {}
+    }
+    let pixels = decoded.into_rgba8();
+    let (width, height) = pixels.dimensions();
+    let pixels = pixels.into_raw();
+    Ok(Decoded {
+        bytes: pixels.len(),
+        handle: image::Handle::from_rgba(width, height, pixels),
+    })
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("fictional-potato").join("media"))
+}
+
+/// A download's file in the disk cache, named by the SHA-256 of its URL so the name stays
+/// the same across builds.
+fn file_name(url: &str) -> String {
+    Sha256::digest(url.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// What's kept on disk for a download: its URL on the first line, then the image.
+fn cache_entry(url: &str, bytes: &[u8]) -> Vec<u8> {
+    [url.as_bytes(), b"\n", bytes].concat()
+}
+
+/// The image in a file from the disk cache, `None` unless the file was kept for `url`.
+fn cached_image<'a>(url: &str, entry: &'a [u8]) -> Option<&'a [u8]> {
+    entry
+        .strip_prefix(url.as_bytes())
+        .and_then(|rest| rest.strip_prefix(b"\n"))
+}
+
+/// A download from the disk cache, marked as just used.
+fn read_cached(url: &str) -> Option<Bytes> {
+    let path = cache_dir()?.join(file_name(url));
+    let entry = std::fs::read(&path).ok()?;
+    let bytes = cached_image(url, &entry)?.to_vec();
+    let _ = File::options()
+        .write(true)
+        .open(&path)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+    Some(bytes.into())
+}
+
+fn store(url: &str, bytes: &[u8]) -> std::io::Result<()> {
+    let Some(dir) = cache_dir() else {
+        return Ok(());
+    };
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join(file_name(url)), cache_entry(url, bytes))?;
+    evict_files(&dir)
+}
+
+/// Removes the downloads used longest ago until the rest fit in [`DISK_BUDGET`].
+fn evict_files(dir: &Path) -> std::io::Result<()> {
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = std::fs::read_dir(dir)?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let metadata = entry.metadata().ok()?;
+            Some((metadata.modified().ok()?, metadata.len(), entry.path()))
+        })
+        .collect();
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    files.sort_by_key(|(modified, _, _)| *modified);
+    for (_, len, path) in files {
+        if total <= DISK_BUDGET {
+            break;
+        }
+        std::fs::remove_file(path)?;
+        total -= len;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_names_are_the_sha256_of_the_url() {
+        assert_eq!(
+            file_name("https://example.com/a.png"),
+            file_name("https://example.com/a.png")
+        );
+        assert_eq!(
+            file_name(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn cached_images_are_only_read_back_for_their_url() {
+        let url = "https://example.com/a.png";
+        let entry = cache_entry(url, b"\x89PNG\n");
+        assert_eq!(cached_image(url, &entry), Some(&b"\x89PNG\n"[..]));
+        assert_eq!(cached_image("https://example.com/b.png", &entry), None);
+        assert_eq!(cached_image("https://example.com/a.pn", &entry), None);
+    }
+}
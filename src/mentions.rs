@@ -0,0 +1,193 @@
+use crate::{
+    emoji::{self, CustomEmoji, SkinTone},
+    markdown::{Document, Mention},
+};
+
+/// More suggestions than this are cut off, keep typing to narrow them down.
+const MAX_SUGGESTIONS: usize = 8;
+/// `:` is common in prose, emoji are only suggested after this many characters of the name.
+const EMOJI_MIN_QUERY: usize = 2;
+
+/// Someone who can be mentioned where the user is typing, a guild member or DM participant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub id: String,
+    pub name: String,
+    /// IDs of the member's roles, a mention of any of them pings the member.
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Role {
+    pub id: String,
+    pub name: String,
+    /// Whether anyone can mention the role, otherwise only those who can mention everyone.
+    pub mentionable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Channel {
+    pub id: String,
+    pub name: String,
+}
+
+/// Who and what can be mentioned in the current conversation, used to suggest mentions and
+/// to put names on the mentions in messages.
+#[derive(Debug, Clone, Default)]
+pub struct Directory {
+    pub members: Vec<Member>,
+    pub roles: Vec<Role>,
+    pub channels: Vec<Channel>,
+    /// The guild's own emoji, offered in the emoji picker next to the Unicode ones.
+    pub emoji: Vec<CustomEmoji>,
+    /// Whether the user may use `@everyone`, `@here` and roles that aren't mentionable.
+    pub can_mention_everyone: bool,
+    /// Whether the user may pin and unpin messages, as anyone can in a DM.
+    pub can_pin_messages: bool,
+}
+
+impl Directory {
+    pub fn member(&self, id: &str) -> Option<&Member> {
+        self.members.iter().find(|member| member.id == id)
+    }
+
+    pub fn role(&self, id: &str) -> Option<&Role> {
+        self.roles.iter().find(|role| role.id == id)
+    }
+
+    pub fn channel(&self, id: &str) -> Option<&Channel> {
+        self.channels.iter().find(|channel| channel.id == id)
+    }
+}
+
+/// Whether `document` pings the user, directly, through one of their roles, or with
+/// `@everyone` or `@here`.
+pub fn mentions_user(document: &Document, user_id: &str, directory: &Directory) -> bool {
+    let roles = directory
+        .member(user_id)
+        .map(|member| member.roles.as_slice())
+        .unwrap_or_default();
+    document
+        .mentions()
+        .into_iter()
+        .any(|mention| match mention {
+            Mention::User(id) => id == user_id,
+            Mention::Role(id) => roles.contains(id),
+            Mention::Everyone | Mention::Here => true,
+            Mention::Channel(_) => false,
+        })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    /// What the list shows, e.g. `@name` or `#channel`.
+    pub label: String,
+    /// What replaces the word being typed, a mention token or an emoji.
+    pub insert: String,
+}
+
+/// Suggestions for the word at the end of the draft when it starts with `@` (members, roles,
+/// `@everyone` and `@here`), `#` (channels) or `:` (emoji).
+///
+/// Only the end of the draft is looked at, the composer doesn't tell where the cursor is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Autocomplete {
+    /// Byte offset of the word being completed.
+    start: usize,
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Autocomplete {
+    /// `None` when the draft doesn't end in a word to complete, or nothing matches it. Emoji
+    /// are suggested in the user's skin `tone`.
+    pub fn new(draft: &str, directory: &Directory, tone: SkinTone) -> Option<Self> {
+        let start = draft
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(index, c)| index + c.len_utf8());
+        let word = &draft[start..];
+
+        let mut suggestions: Vec<Suggestion> = if let Some(query) = word.strip_prefix('@') {
+            mention_suggestions(query, directory)
+        } else if let Some(query) = word.strip_prefix('#') {
+            matching(&directory.channels, query, |channel| channel.name.as_str())
+                .map(|channel| Suggestion {
+                    label: format!("#{}", channel.name),
+                    insert: format!("<#{}> ", channel.id),
+                })
+                .collect()
+        } else if let Some(query) = word.strip_prefix(':') {
+            let query = Some(query).filter(|query| query.len() >= EMOJI_MIN_QUERY)?;
+            emoji::search(query)
+                .into_iter()
+                .map(|emoji| Suggestion {
+                    label: format!("{} :{}:", emoji.glyph(tone), emoji.name),
+                    insert: format!("{} ", emoji.glyph(tone)),
+                })
+                .collect()
+        } else {
+            return None;
+        };
+
+        suggestions.truncate(MAX_SUGGESTIONS);
+        (!suggestions.is_empty()).then_some(Self { start, suggestions })
+    }
+
+    /// The draft with the word being completed replaced by the suggestion at `index`.
+    pub fn complete(&self, draft: &str, index: usize) -> Option<String> {
+        let suggestion = self.suggestions.get(index)?;
+        let kept = draft.get(..self.start)?;
+        Some(format!("{kept}{}", suggestion.insert))
+    }
+}
+
+fn mention_suggestions(query: &str, directory: &Directory) -> Vec<Suggestion> {
+    let members =
+        matching(&directory.members, query, |member| member.name.as_str()).map(|member| {
+            Suggestion {
+                label: format!("@{}", member.name),
+                insert: format!("<@{}> ", member.id),
+            }
+        });
+
+    let lowercase = query.to_lowercase();
+    let everyone = ["everyone", "here"]
+        .into_iter()
+        .filter(|_| directory.can_mention_everyone)
+        .filter(|keyword| keyword.starts_with(&lowercase))
+        .map(|keyword| Suggestion {
+            label: format!("@{keyword}"),
+            insert: format!("@{keyword} "),
+        });
+
+    let roles = directory
+        .roles
+        .iter()
+        .filter(|role| role.mentionable || directory.can_mention_everyone);
+    let roles = matching(roles, query, |role| role.name.as_str()).map(|role| Suggestion {
+        label: format!("@{}", role.name),
+        insert: format!("<@&{}> ", role.id),
+    });
+
+    members.chain(everyone).chain(roles).collect()
+}
+
+/// Items whose name contains `query` ignoring case, those starting with it first.
+fn matching<'a, T: 'a>(
+    items: impl IntoIterator<Item = &'a T>,
+    query: &str,
+    name: impl Fn(&T) -> &str,
+) -> impl Iterator<Item = &'a T> {
+    let query = query.to_lowercase();
+    let mut found: Vec<(bool, &T)> = items
+        .into_iter()
+        .filter_map(|item| {
+            let name = name(item).to_lowercase();
+            name.contains(&query)
+                .then_some((!name.starts_with(&query), item))
+        })
+        .collect();
+    found.sort_by_key(|(later, _)| *later);
+    found.into_iter().map(|(_, item)| item)
+}
@@ -0,0 +1,206 @@
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+
+use iced::{
+    Border, Color, Element,
+    Length::Fill,
+    Shadow, Vector,
+    alignment::Vertical,
+    widget::{button, column, container, horizontal_space, row, scrollable, text},
+};
+
+use super::toast::{Toast, ToastKind};
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorMain},
+    constants::BORDER_RADIUS,
+    i18n::{self, layout_direction, tr},
+    styles::button_secondary_style,
+    typography::{TypeScale, body, caption, title},
+};
+
+/// Older notifications are dropped once the inbox holds this many.
+pub const HISTORY_LIMIT: usize = 100;
+const PANEL_WIDTH: f32 = 360.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotificationKind {
+    Mention,
+    FriendRequest,
+    /// Anything the app itself reported, e.g. a failed save. Keeps the toast's kind.
+    System(ToastKind),
+}
+
+impl NotificationKind {
+    fn icon(self) -> &'static str {
+        match self {
+            NotificationKind::Mention => "@",
+            NotificationKind::FriendRequest => "+",
+            NotificationKind::System(_) => "●",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            NotificationKind::Mention | NotificationKind::FriendRequest => {
+                AppColorMain::AccentSecondary.into()
+            }
+            NotificationKind::System(kind) => kind.color(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub text: String,
+    pub received: DateTime<Local>,
+    pub read: bool,
+}
+
+impl Notification {
+    pub fn new(kind: NotificationKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            received: Local::now(),
+            read: false,
+        }
+    }
+}
+
+impl<M> From<&Toast<M>> for Notification {
+    fn from(toast: &Toast<M>) -> Self {
+        Notification::new(NotificationKind::System(toast.kind), toast.text.clone())
+    }
+}
+
+/// History of mentions, friend requests and system events, newest first. Toasts go away on
+/// their own, this is where to find them again.
+#[derive(Debug, Clone, Default)]
+pub struct Inbox {
+    pub is_open: bool,
+    notifications: VecDeque<Notification>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Toggle,
+    Close,
+    MarkAllReadPressed,
+    ClearPressed,
+}
+
+impl Inbox {
+    pub fn push(&mut self, notification: Notification) {
+        self.notifications.push_front(notification);
+        self.notifications.truncate(HISTORY_LIMIT);
+    }
+
+    pub fn unread(&self) -> usize {
+        self.notifications
+            .iter()
+            .filter(|notification| !notification.read)
+            .count()
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Toggle => self.is_open = !self.is_open,
+            Message::Close => self.is_open = false,
+            Message::MarkAllReadPressed => {
+                for notification in &mut self.notifications {
+                    notification.read = true;
+                }
+            }
+            Message::ClearPressed => self.notifications.clear(),
+        }
+    }
+
+    /// The inbox panel, meant to be layered over the window while [`Inbox::is_open`].
+    pub fn view(&self) -> Element<'_, Message> {
+        let header = row![
+            title(tr!("inbox-title")),
+            horizontal_space(),
+            button(text(tr!("inbox-mark-read")).size(TypeScale::Caption.size()))
+                .on_press_maybe((self.unread() > 0).then_some(Message::MarkAllReadPressed))
+                .padding([4, 10])
+                .style(button_secondary_style),
+            button(text(tr!("inbox-clear")).size(TypeScale::Caption.size()))
+                .on_press_maybe((!self.notifications.is_empty()).then_some(Message::ClearPressed))
+                .padding([4, 10])
+                .style(button_secondary_style),
+            button(text("×").size(TypeScale::Body.size()))
+                .on_press(Message::Close)
+                .padding([0, 6])
+                .style(|_t, _s| button::Style {
+                    text_color: AppColorForeground::Secondary.into(),
+                    ..Default::default()
+                }),
+        ]
+        .spacing(8)
+        .align_y(Vertical::Center);
+
+        let list: Element<Message> = if self.notifications.is_empty() {
+            caption(tr!("inbox-empty"))
+                .color(Color::from(AppColorForeground::SubtextPrimary))
+                .into()
+        } else {
+            scrollable(column(self.notifications.iter().map(notification)).spacing(4)).into()
+        };
+
+        let panel = container(column![header, list].spacing(16))
+            .width(PANEL_WIDTH)
+            .max_height(520.0)
+            .padding(16)
+            .style(|_t| container::Style {
+                background: Some(AppColorBackground::Secondary.to_bg()),
+                border: Border {
+                    radius: BORDER_RADIUS.into(),
+                    ..Default::default()
+                },
+                shadow: Shadow {
+                    color: Color::BLACK.scale_alpha(0.3),
+                    offset: Vector::new(0.0, 4.0),
+                    blur_radius: 16.0,
+                },
+                ..Default::default()
+            });
+
+        container(panel)
+            .width(Fill)
+            .height(Fill)
+            .padding(16)
+            .align_x(layout_direction().end())
+            .into()
+    }
+}
+
+fn notification(notification: &Notification) -> Element<'_, Message> {
+    let received = i18n::format_date(&notification.received, "date-time-format");
+
+    container(
+        row![
+            text(notification.kind.icon())
+                .size(TypeScale::Body.size())
+                .color(notification.kind.color()),
+            column![
+                body(&notification.text),
+                caption(received).color(Color::from(AppColorForeground::SubtextPrimary)),
+            ]
+            .spacing(2)
+            .width(Fill),
+        ]
+        .spacing(10),
+    )
+    .width(Fill)
+    .padding([8, 10])
+    .style(move |_t| container::Style {
+        background: (!notification.read).then(|| AppColorBackground::SurfacePrimary.to_bg()),
+        border: Border {
+            radius: BORDER_RADIUS.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .into()
+}
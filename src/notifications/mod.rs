@@ -0,0 +1,5 @@
+pub mod inbox;
+pub mod toast;
+
+pub use inbox::{Inbox, Notification};
+pub use toast::{Toast, ToastAction, ToastKind, Toasts};
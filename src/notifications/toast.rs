@@ -0,0 +1,283 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use iced::{
+    Border, Color, Element,
+    Length::Fill,
+    Shadow, Subscription, Vector,
+    alignment::Vertical,
+    time,
+    widget::{button, column, container, progress_bar, row, text},
+};
+
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorMain, AppColorStatus},
+    constants::BORDER_RADIUS,
+    i18n::{layout_direction, tr},
+    styles::button_secondary_style,
+    typography::{TypeScale, body},
+};
+
+/// How many toasts are on screen at once, later ones wait until one goes away.
+pub const MAX_VISIBLE: usize = 3;
+const TOAST_WIDTH: f32 = 340.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    /// How long a toast stays up. Problems stay longer so there's time to read them and
+    /// press Retry.
+    fn duration(self) -> Duration {
+        match self {
+            ToastKind::Info | ToastKind::Success => Duration::from_secs(4),
+            ToastKind::Warning => Duration::from_secs(6),
+            ToastKind::Error => Duration::from_secs(10),
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            // There's no status color for plain information, the accent keeps it neutral.
+            ToastKind::Info => AppColorMain::AccentPrimary.into(),
+            ToastKind::Success => AppColorStatus::Success.into(),
+            ToastKind::Warning => AppColorStatus::Warning.into(),
+            ToastKind::Error => AppColorStatus::Failure.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastAction {
+    Undo,
+    Retry,
+}
+
+impl ToastAction {
+    fn label(self) -> String {
+        match self {
+            ToastAction::Undo => tr!("toast-undo"),
+            ToastAction::Retry => tr!("toast-retry"),
+        }
+    }
+}
+
+/// Short-lived feedback shown in the corner of the window, e.g. "Settings saved". `M` is the
+/// message sent when its action button is pressed.
+#[derive(Debug, Clone)]
+pub struct Toast<M> {
+    pub kind: ToastKind,
+    pub text: String,
+    pub action: Option<(ToastAction, Box<M>)>,
+}
+
+impl<M> Toast<M> {
+    pub fn new(kind: ToastKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            action: None,
+        }
+    }
+
+    pub fn with_action(mut self, action: ToastAction, message: M) -> Self {
+        self.action = Some((action, Box::new(message)));
+        self
+    }
+
+    /// Wraps the action message, used when a screen's toast reaches the [`crate::App`].
+    pub fn map<N>(self, f: impl FnOnce(M) -> N) -> Toast<N> {
+        Toast {
+            kind: self.kind,
+            text: self.text,
+            action: self
+                .action
+                .map(|(action, message)| (action, Box::new(f(*message)))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Shown<M> {
+    id: u64,
+    toast: Toast<M>,
+    since: Instant,
+}
+
+impl<M> Shown<M> {
+    /// Share of the toast's time that is left, from 1 down to 0.
+    fn remaining(&self, now: Instant) -> f32 {
+        let duration = self.toast.kind.duration();
+        let elapsed = now.saturating_duration_since(self.since);
+        1.0 - (elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.0)
+    }
+}
+
+/// The toasts on screen and the ones waiting for a free spot.
+#[derive(Debug, Clone)]
+pub struct Toasts<M> {
+    next_id: u64,
+    shown: Vec<Shown<M>>,
+    queued: VecDeque<Toast<M>>,
+}
+
+impl<M> Default for Toasts<M> {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            shown: Vec::new(),
+            queued: VecDeque::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Tick(Instant),
+    DismissPressed(u64),
+    ActionPressed(u64),
+}
+
+impl<M> Toasts<M> {
+    pub fn push(&mut self, toast: Toast<M>) {
+        self.queued.push_back(toast);
+        self.fill(Instant::now());
+    }
+
+    /// Moves queued toasts on screen while there's room. Their timers start from here.
+    fn fill(&mut self, now: Instant) {
+        while self.shown.len() < MAX_VISIBLE {
+            let Some(toast) = self.queued.pop_front() else {
+                break;
+            };
+            self.shown.push(Shown {
+                id: self.next_id,
+                toast,
+                since: now,
+            });
+            self.next_id += 1;
+        }
+    }
+
+    fn remove(&mut self, id: u64) -> Option<Toast<M>> {
+        let index = self.shown.iter().position(|shown| shown.id == id)?;
+        let shown = self.shown.remove(index);
+        self.fill(Instant::now());
+        Some(shown.toast)
+    }
+
+    /// Ticks while anything is on screen. With reduced motion there's no countdown bar to
+    /// animate, so a slow tick is enough to dismiss toasts on time.
+    pub fn subscription(&self, reduced_motion: bool) -> Subscription<Message> {
+        if self.shown.is_empty() {
+            return Subscription::none();
+        }
+        let interval = if reduced_motion {
+            Duration::from_millis(500)
+        } else {
+            Duration::from_millis(50)
+        };
+        time::every(interval).map(Message::Tick)
+    }
+
+    /// Returns the message of the action that was pressed, if any.
+    pub fn update(&mut self, message: Message) -> Option<M> {
+        match message {
+            Message::Tick(now) => {
+                self.shown.retain(|shown| shown.remaining(now) > 0.0);
+                self.fill(now);
+                None
+            }
+            Message::DismissPressed(id) => {
+                self.remove(id);
+                None
+            }
+            Message::ActionPressed(id) => {
+                let (_, message) = self.remove(id)?.action?;
+                Some(*message)
+            }
+        }
+    }
+
+    /// The toast stack, meant to be layered over the window. Newest toasts are at the bottom,
+    /// in the corner where the UI's text ends.
+    pub fn view(&self, reduced_motion: bool) -> Element<'_, Message> {
+        let now = Instant::now();
+        let toasts = column(
+            self.shown
+                .iter()
+                .map(|shown| toast(shown, now, reduced_motion)),
+        )
+        .spacing(8)
+        .width(TOAST_WIDTH);
+
+        container(toasts)
+            .width(Fill)
+            .height(Fill)
+            .padding(16)
+            .align_x(layout_direction().end())
+            .align_y(Vertical::Bottom)
+            .into()
+    }
+}
+
+fn toast<M>(shown: &Shown<M>, now: Instant, reduced_motion: bool) -> Element<'_, Message> {
+    let color = shown.toast.kind.color();
+
+    let content = row![
+        text("●").size(TypeScale::Caption.size()).color(color),
+        body(&shown.toast.text).width(Fill),
+    ]
+    .push_maybe(shown.toast.action.as_ref().map(|(action, _)| {
+        button(text(action.label()).size(TypeScale::Caption.size()))
+            .on_press(Message::ActionPressed(shown.id))
+            .padding([4, 10])
+            .style(button_secondary_style)
+    }))
+    .push(
+        button(text("×").size(TypeScale::Body.size()))
+            .on_press(Message::DismissPressed(shown.id))
+            .padding([0, 6])
+            .style(|_t, _s| button::Style {
+                text_color: AppColorForeground::Secondary.into(),
+                ..Default::default()
+            }),
+    )
+    .spacing(10)
+    .align_y(Vertical::Center);
+
+    let countdown = (!reduced_motion).then(|| {
+        progress_bar(0.0..=1.0, shown.remaining(now))
+            .height(2)
+            .style(move |_t| progress_bar::Style {
+                background: Color::TRANSPARENT.into(),
+                bar: color.scale_alpha(0.6).into(),
+                border: Border::default(),
+            })
+    });
+
+    container(column![content].push_maybe(countdown).spacing(8))
+        .width(Fill)
+        .padding([10, 12])
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+            border: Border {
+                radius: BORDER_RADIUS.into(),
+                ..Default::default()
+            },
+            shadow: Shadow {
+                color: Color::BLACK.scale_alpha(0.3),
+                offset: Vector::new(0.0, 4.0),
+                blur_radius: 12.0,
+            },
+            ..Default::default()
+        })
+        .into()
+}
@@ -0,0 +1,128 @@
+use reqwest::Url;
+
+use crate::{constants::API_URL, utils::send_empty};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReactionEmoji {
+    /// The glyph, skin tone included.
+    Unicode(String),
+    Custom {
+        id: String,
+        name: String,
+    },
+}
+
+impl ReactionEmoji {
+    /// How the API names the emoji in a URL, the glyph itself or `name:id`.
+    fn path_segment(&self) -> String {
+        match self {
+            ReactionEmoji::Unicode(glyph) => glyph.clone(),
+            ReactionEmoji::Custom { id, name } => format!("{name}:{id}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reaction {
+    pub emoji: ReactionEmoji,
+    /// IDs of who reacted, first to react first.
+    pub users: Vec<String>,
+}
+
+impl Reaction {
+    pub fn count(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn includes(&self, user_id: &str) -> bool {
+        self.users.iter().any(|user| user == user_id)
+    }
+}
+
+/// The reactions on a message, in the order they were first added.
+#[derive(Debug, Clone, Default)]
+pub struct Reactions {
+    reactions: Vec<Reaction>,
+}
+
+impl Reactions {
+    pub fn iter(&self) -> impl Iterator<Item = &Reaction> {
+        self.reactions.iter()
+    }
+
+    /// Returns `false` when the user had already reacted with the emoji.
+    pub fn add(&mut self, emoji: ReactionEmoji, user_id: &str) -> bool {
+        match self
+            .reactions
+            .iter_mut()
+            .find(|reaction| reaction.emoji == emoji)
+        {
+            Some(reaction) if reaction.includes(user_id) => false,
+            Some(reaction) => {
+                reaction.users.push(user_id.to_string());
+                true
+            }
+            None => {
+                self.reactions.push(Reaction {
+                    emoji,
+                    users: vec![user_id.to_string()],
+                });
+                true
+            }
+        }
+    }
+
+    /// Returns `false` when the user hadn't reacted with the emoji. A reaction nobody is left
+    /// on goes away.
+    pub fn remove(&mut self, emoji: &ReactionEmoji, user_id: &str) -> bool {
+        let Some(reaction) = self.reactions.iter_mut().find(|r| &r.emoji == emoji) else {
+            return false;
+        };
+        let before = reaction.users.len();
+        reaction.users.retain(|user| user != user_id);
+        let removed = reaction.users.len() < before;
+        self.reactions.retain(|reaction| !reaction.users.is_empty());
+        removed
+    }
+
+    /// Adds the user's reaction if they haven't reacted with the emoji yet, removes it
+    /// otherwise. Returns whether it was added.
+    pub fn toggle(&mut self, emoji: ReactionEmoji, user_id: &str) -> bool {
+        !self.remove(&emoji, user_id) && self.add(emoji, user_id)
+    }
+}
+
+fn reaction_url(channel_id: &str, message_id: &str, emoji: &ReactionEmoji) -> Result<Url, String> {
+    let mut url = Url::parse(API_URL).map_err(|e| e.to_string())?;
+    url.path_segments_mut()
+        .map_err(|()| "API URL can't have a path".to_string())?
+        .pop_if_empty()
+        .extend([
+            "channels",
+            channel_id,
+            "messages",
+            message_id,
+            "reactions",
+            &emoji.path_segment(),
+            "@me",
+        ]);
+    Ok(url)
+}
+
+/// Adds or removes the user's reaction on the server.
+pub async fn set_reaction(
+    access_token: String,
+    channel_id: String,
+    message_id: String,
+    emoji: ReactionEmoji,
+    added: bool,
+) -> Result<(), String> {
+    let url = reaction_url(&channel_id, &message_id, &emoji)?;
+    let client = reqwest::Client::new();
+    let request = if added {
+        client.put(url)
+    } else {
+        client.delete(url)
+    };
+    send_empty(request.bearer_auth(access_token)).await
+}
@@ -1,44 +1,57 @@
 use crate::{
-    RUBIK,
     colors::{AppColorForeground, AppColorMain},
-    components::styled_input,
+    components::{password_score, password_strength, styled_input},
+    constants::{API_URL, FORM_WIDTH},
+    i18n::{self, tr},
     styles::{button_style, container_style},
-    utils::set_token_from_secure_storage,
+    typography::{body, caption, heading},
+    utils::{ApiError, Lockout, Redacted, Session, device_name, platform, try_send_json},
 };
-use std::collections::HashMap;
-use turbo::{auth::AuthResponse, errors::ResponseError, types::PublicUser};
+use std::{collections::HashMap, time::Duration};
+use turbo::auth::AuthResponse;
 
 use iced::{
     Color, Element,
-    Length::{self, Fill},
-    Task,
-    widget::{button, column, container, row, text},
+    Length::Fill,
+    Subscription, Task, task,
+    time::{self, Instant},
+    widget::{button, column, container, row},
 };
-use validator::Validate;
-use zxcvbn::zxcvbn;
+use validator::{Validate, ValidateEmail};
 
 #[derive(Debug, Clone, Default, Validate)]
 pub struct State {
-    #[validate(length(min = 2, max = 32, message = "Must be between 2 to 32 characters"))]
+    #[validate(length(min = 2, max = 32, code = "username-length"))]
     pub username: String,
-    #[validate(length(min = 1, max = 72, message = "Must be between 2 to 72 characters"))]
-    pub password: String,
-    #[validate(length(min = 1, max = 72, message = "Must be between 2 to 72 characters"))]
-    pub password_confirm: String,
+    pub email: String,
+    #[validate(length(min = 1, max = 72, code = "password-length"))]
+    pub password: Redacted<String>,
+    #[validate(length(min = 1, max = 72, code = "password-length"))]
+    pub password_confirm: Redacted<String>,
     pub is_loading: bool,
+    pub password_score: Option<u8>,
     pub username_error: Option<String>,
+    pub email_error: Option<String>,
     pub password_error: Option<String>,
+    pub lockout: Option<Lockout>,
+    /// Handle to the registration request in flight, aborted when the form changes.
+    pub request: Option<task::Handle>,
 }
 
 impl State {
     pub fn new() -> Self {
         Self {
             username: String::new(),
-            password: String::new(),
-            password_confirm: String::new(),
+            email: String::new(),
+            password: Redacted::default(),
+            password_confirm: Redacted::default(),
             is_loading: false,
+            password_score: None,
             username_error: None,
+            email_error: None,
             password_error: None,
+            lockout: None,
+            request: None,
         }
     }
 }
@@ -46,210 +59,244 @@ impl State {
 #[derive(Debug, Clone)]
 pub enum Message {
     UsernameInputChanged(String),
+    EmailInputChanged(String),
     PasswordInputChanged(String),
     PasswordConfirmInputChanged(String),
     RegisterButtonPressed,
-    RegisterSuccess(PublicUser),
-    RegisterFailed(String),
+    RegisterSuccess(Session),
+    RegisterFailed(ApiError),
+    Tick(Instant),
     LoginInstead,
     RequestScreenChange(super::CurrentScreen),
 }
 
 pub fn view(state: &State) -> Element<'_, Message> {
     let register_button = button(
-        text(if state.is_loading {
-            "Registering…"
-        } else {
-            "Register"
+        body(match state.lockout {
+            Some(lockout) => lockout.label(),
+            None if state.is_loading => tr!("register-submit-busy"),
+            None => tr!("register-submit"),
         })
         .width(Fill)
         .center(),
     )
-    .on_press(Message::RegisterButtonPressed)
+    .on_press_maybe(can_submit(state).then_some(Message::RegisterButtonPressed))
     .height(44)
     .width(Fill)
     .style(button_style)
     .padding(10);
 
-    let login_link = button(
-        text("Login")
-            .color(Color::from(AppColorMain::Secondary))
-            .size(12),
-    )
-    .on_press(Message::LoginInstead)
-    .style(button::text)
-    .height(16)
-    .padding(0);
+    let login_link =
+        button(caption(tr!("register-login")).color(Color::from(AppColorMain::Secondary)))
+            .on_press(Message::LoginInstead)
+            .style(button::text)
+            .height(16)
+            .padding(0);
 
     container(
         column![
-            text("Create your account")
-                .font(RUBIK)
-                .size(24)
-                .width(Fill)
-                .center(),
+            heading(tr!("register-title")).width(Fill).center(),
             column![
                 styled_input(
-                    "Username",
+                    &tr!("register-username"),
                     &state.username,
                     state.username_error.as_deref(),
-                    None,
                     Message::UsernameInputChanged,
                     //Message::RegisterButtonPressed,
                     None,
                     Some(true),
+                    Some(state.lockout.is_some()),
                 ),
                 styled_input(
-                    "Password",
-                    &state.password,
-                    state.password_error.as_deref(),
-                    None,
-                    Message::PasswordInputChanged,
+                    &tr!("register-email"),
+                    &state.email,
+                    state.email_error.as_deref(),
+                    Message::EmailInputChanged,
                     //Message::RegisterButtonPressed,
                     None,
-                    Some(true),
+                    None,
+                    Some(state.lockout.is_some()),
                 ),
+                column![
+                    styled_input(
+                        &tr!("register-password"),
+                        state.password.expose(),
+                        state.password_error.as_deref(),
+                        Message::PasswordInputChanged,
+                        //Message::RegisterButtonPressed,
+                        None,
+                        Some(true),
+                        Some(state.lockout.is_some()),
+                    ),
+                    password_strength(state.password_score),
+                ]
+                .spacing(8),
                 styled_input(
-                    "Confirm Password",
-                    &state.password_confirm,
+                    &tr!("register-password-confirm"),
+                    state.password_confirm.expose(),
                     state.password_error.as_deref(),
-                    None,
                     Message::PasswordConfirmInputChanged,
                     //Message::RegisterButtonPressed,
                     Some(true),
                     Some(true),
+                    Some(state.lockout.is_some()),
                 ),
             ]
             .spacing(24),
             column![
                 register_button,
                 row![
-                    text("Have an account already? ")
-                        .size(12)
+                    caption(tr!("register-have-account"))
                         .color(Color::from(AppColorForeground::SubtextPrimary)),
                     login_link
                 ],
             ]
             .spacing(8)
         ]
-        .width(Length::Fixed(550.0))
+        .width(FORM_WIDTH)
         .padding(28)
         .spacing(36),
     )
-    .style(|theme| container_style(theme))
+    .style(container_style)
     .into()
 }
 
+fn can_submit(state: &State) -> bool {
+    !state.is_loading && state.lockout.is_none()
+}
+
 pub async fn perform_registration(
     username: String,
+    email: Option<String>,
     password: String,
-) -> Result<PublicUser, String> {
-    println!(
-        "Attempting registration for new user with username: {}.",
-        username
-    );
+) -> Result<Session, ApiError> {
+    tracing::info!(%username, "Attempting registration");
 
     let name = username.trim_ascii().to_string();
 
     let mut map = HashMap::new();
     map.insert("username", name);
     map.insert("password", password);
+    map.insert("device_name", device_name());
+    map.insert("platform", platform());
+    if let Some(email) = email {
+        map.insert("email", email);
+    }
 
     let client = reqwest::Client::new();
-    let res = client
-        .post("http://localhost:8585/auth/register")
-        .json(&map)
-        .send()
-        .await
-        .map_err(|e| format!("Network error: {}", e))?;
+    let auth =
+        try_send_json::<AuthResponse>(client.post(format!("{API_URL}/auth/register")).json(&map))
+            .await?;
 
-    if res.status().is_success() {
-        let success_body = res
-            .json::<AuthResponse>()
-            .await
-            .map_err(|e| format!("Failed to parse successful login: {}", e))?;
+    Session::store(auth).map_err(ApiError::Response)
+}
 
-        println!("res: {success_body:?}");
-        set_token_from_secure_storage(Some(success_body.refresh_token)).map_err(|e| "Err")?;
-        Ok(success_body.user)
-    } else {
-        let error_body = res
-            .json::<ResponseError>()
-            .await
-            .map_err(|e| format!("Failed to parse error response: {}", e))?;
+/// Drops the response of a request that no longer matches what the user typed.
+fn cancel_request(state: &mut State) {
+    if let Some(request) = state.request.take() {
+        request.abort();
+        state.is_loading = false;
+    }
+}
 
-        Err(error_body.error.message)
+pub fn subscription(state: &State) -> Subscription<Message> {
+    if state.lockout.is_some() {
+        time::every(Duration::from_secs(1)).map(Message::Tick)
+    } else {
+        Subscription::none()
     }
 }
 
 pub fn update(state: &mut State, message: Message) -> Task<Message> {
     match message {
         Message::UsernameInputChanged(value) => {
+            cancel_request(state);
             state.username = value;
             state.username_error = None;
             Task::none()
         }
+        Message::EmailInputChanged(value) => {
+            cancel_request(state);
+            state.email = value;
+            state.email_error = None;
+            Task::none()
+        }
         Message::PasswordInputChanged(value) => {
-            let estimate = zxcvbn(&value, &[]);
-            println!("Estimate: {}", estimate.score());
-            println!("Guesses: {}", estimate.guesses());
-            println!("Crack time: {:?}", estimate.crack_times());
-            println!("Feedback: {:?}\n", estimate.feedback());
-            state.password = value;
+            cancel_request(state);
+            state.password_score = password_score(&value);
+            state.password = Redacted::new(value);
             state.password_error = None;
             Task::none()
         }
         Message::PasswordConfirmInputChanged(value) => {
-            state.password_confirm = value;
+            cancel_request(state);
+            state.password_confirm = Redacted::new(value);
             state.password_error = None;
             Task::none()
         }
         Message::RegisterButtonPressed => {
+            if !can_submit(state) {
+                return Task::none();
+            }
             state.is_loading = true;
             match state.validate() {
                 Ok(_) => {}
                 Err(e) => {
                     state.is_loading = false;
-                    if let Some(username_errors) = e.field_errors().get("username") {
-                        if let Some(first_error) = username_errors.first() {
-                            if let Some(message) = &first_error.message {
-                                state.username_error = Some(message.to_string());
-                            }
-                        }
-                    }
-                    if let Some(password_errors) = e.field_errors().get("password") {
-                        if let Some(first_error) = password_errors.first() {
-                            if let Some(message) = &first_error.message {
-                                state.password_error = Some(message.to_string());
-                            }
-                        }
-                    }
+                    state.username_error = i18n::field_error(&e, "username");
+                    state.password_error = i18n::field_error(&e, "password");
                     return Task::none();
                 }
             }
+            let email = state.email.trim();
+            if !email.is_empty() && !email.validate_email() {
+                state.is_loading = false;
+                state.email_error = Some(tr!("error-invalid-email"));
+                return Task::none();
+            }
             if state.password != state.password_confirm {
-                state.password_error = Some("Passwords need to match.".to_string());
+                state.is_loading = false;
+                state.password_error = Some(tr!("error-passwords-differ"));
                 return Task::none();
             }
-            Task::perform(
-                perform_registration(state.username.clone(), state.password.clone()),
+            let (task, handle) = Task::perform(
+                perform_registration(
+                    state.username.clone(),
+                    (!email.is_empty()).then(|| email.to_string()),
+                    state.password.expose().clone(),
+                ),
                 |result| match result {
-                    Ok(user) => Message::RegisterSuccess(user),
+                    Ok(session) => Message::RegisterSuccess(session),
                     Err(e) => Message::RegisterFailed(e),
                 },
             )
+            .abortable();
+            state.request = Some(handle);
+            task
         }
         Message::RegisterSuccess(_) => {
             state.is_loading = false;
+            state.request = None;
             Task::none()
         }
         Message::RegisterFailed(error) => {
             state.is_loading = false;
-            state.username_error = Some(error.clone());
-            state.password_error = Some(error);
+            state.request = None;
+            if let Some(retry_after) = error.retry_after() {
+                state.lockout = Some(Lockout::new(retry_after.max(Duration::from_secs(1))));
+            }
+            if !matches!(error, ApiError::RateLimited { .. }) {
+                state.username_error = Some(error.to_string());
+                state.password_error = Some(error.to_string());
+            }
+            Task::none()
+        }
+        Message::Tick(now) => {
+            state.lockout.take_if(|lockout| !lockout.tick(now));
             Task::none()
         }
         Message::LoginInstead => {
+            cancel_request(state);
             state.is_loading = false;
             Task::done(Message::RequestScreenChange(super::CurrentScreen::Login(
                 super::login_screen::State::new(),
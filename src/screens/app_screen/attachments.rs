@@ -0,0 +1,352 @@
+use crate::{
+    attachments::{Attachment, File, Pending, Progress, Upload, format_size},
+    colors::{AppColorBackground, AppColorMain},
+    components::{MessageAction, directional_row},
+    embeds::Embed,
+    i18n::tr,
+    media::{self, Lookup},
+    notifications::{Toast, ToastKind},
+    styles::button_secondary_style,
+    typography::{TypeScale, caption, label, title},
+};
+
+use iced::{
+    Border, Color, Element,
+    Length::Fill,
+    Task,
+    widget::{button, column, container, horizontal_space, image, text, tooltip},
+};
+
+use super::{
+    CONVERSATION, Message, SidePanel, State,
+    composer::{composer_mut, composers_mut, drop_target},
+    messages::{ChatMessage, find_message},
+    panel_button,
+    threads::thread_conversation,
+    tooltip_style,
+};
+use std::{ops::Range, path::PathBuf};
+
+/// Messages just out of view of a list whose images are loaded along with those in view.
+const VISIBLE_MARGIN: usize = 3;
+
+/// Over the whole screen while files are dragged over the window.
+pub fn drop_overlay<'a>() -> Element<'a, Message> {
+    let hint = container(title(tr!("attachment-drop-hint")))
+        .padding([24, 32])
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::Secondary.to_bg()),
+            border: Border {
+                color: AppColorMain::AccentPrimary.into(),
+                width: 2.0,
+                radius: 12.0.into(),
+            },
+            ..Default::default()
+        });
+    container(hint)
+        .center(Fill)
+        .style(|_t| container::Style {
+            background: Some(Color::BLACK.scale_alpha(0.5).into()),
+            ..Default::default()
+        })
+        .into()
+}
+
+/// The attached image open in the lightbox, zoomed with the wheel and moved by dragging,
+/// drawn by the [`crate::App`] over the screen while its lightbox dialog is open.
+pub fn lightbox(state: &State) -> Element<'_, Message> {
+    let open = state
+        .lightbox
+        .as_deref()
+        .and_then(|id| find_attachment(state, id));
+    let image = open.and_then(
+        |(_, attachment)| match (&attachment.preview, &attachment.url) {
+            (Some(preview), _) => Some(Lookup::Loaded(preview)),
+            (None, Some(url)) => Some(state.media.get(&media::Key::original(url.clone()))),
+            (None, None) => None,
+        },
+    );
+
+    let mut header: Vec<Element<'_, Message>> = Vec::new();
+    if let Some((message, attachment)) = open {
+        header.push(label(attachment.name.as_str()).into());
+        header.push(caption(format_size(attachment.size)).into());
+        header.push(horizontal_space().into());
+        header.extend(attachment.url.clone().map(|url| {
+            let action = MessageAction::OpenUrl(url);
+            button(text(tr!("attachment-open-original")).size(TypeScale::Caption.size()))
+                .on_press(Message::MessageAction(message.id.clone(), action))
+                .padding([2, 8])
+                .style(button_secondary_style)
+                .into()
+        }));
+    } else {
+        header.push(horizontal_space().into());
+    }
+    header.push(
+        tooltip(
+            panel_button("×", Message::CloseLightbox),
+            container(caption(tr!("attachment-close")))
+                .padding([4, 8])
+                .style(tooltip_style),
+            tooltip::Position::Bottom,
+        )
+        .into(),
+    );
+
+    let status = |status: String| -> Element<'_, Message> {
+        caption(status).width(Fill).height(Fill).center().into()
+    };
+    let viewer: Option<Element<'_, Message>> = image.map(|image| match image {
+        Lookup::Loaded(handle) => image::viewer(handle.clone())
+            .width(Fill)
+            .height(Fill)
+            .into(),
+        Lookup::Loading => status(tr!("attachment-loading")),
+        Lookup::Failed => status(tr!("attachment-unavailable")),
+    });
+    container(
+        column![
+            directional_row(header)
+                .spacing(8)
+                .align_y(iced::Alignment::Center)
+        ]
+        .push_maybe(viewer)
+        .spacing(16),
+    )
+    .width(Fill)
+    .height(Fill)
+    .padding(24)
+    .style(|_t| container::Style {
+        background: Some(Color::BLACK.scale_alpha(0.85).into()),
+        text_color: Some(Color::WHITE),
+        ..Default::default()
+    })
+    .into()
+}
+
+/// Adds a file read to the channel's composer (`None`) or a thread's, uploading it right away
+/// to the channel shown or the thread, which is a channel of its own on the server. Without
+/// one it stays here, like the messages written here.
+pub fn attach(
+    state: &mut State,
+    thread_id: Option<String>,
+    file: File,
+    access_token: &str,
+) -> Task<Message> {
+    let upload_to = match thread_id.as_deref() {
+        Some(thread_id) => state
+            .threads
+            .iter()
+            .find(|thread| thread.id == thread_id && thread.channel_id.is_some())
+            .map(|thread| thread.id.clone()),
+        None => state.channel.as_ref().map(|channel| channel.id.clone()),
+    };
+    state.local_messages += 1;
+    let id = format!("local-{}", state.local_messages);
+    let mut pending = Pending::new(id.clone(), file);
+    let Some(composer) = composer_mut(state, thread_id.as_deref()) else {
+        return Task::none();
+    };
+    let Some(upload_to) = upload_to else {
+        composer.attachments.push(pending);
+        return Task::none();
+    };
+    let upload = crate::attachments::upload(
+        access_token.to_string(),
+        upload_to,
+        pending.name.clone(),
+        pending.bytes.clone(),
+    );
+    let (task, handle) = Task::run(upload, move |progress| {
+        Message::UploadProgress(id.clone(), progress)
+    })
+    .abortable();
+    pending.upload = Upload::Sending { sent: 0, handle };
+    composer.attachments.push(pending);
+    task
+}
+
+/// Downloads and scales down the images attached to a message and those of its embeds that
+/// aren't loaded yet.
+pub fn load_thumbnails(media: &mut media::Cache, message: &ChatMessage) -> Task<Message> {
+    let thumbnails =
+        thumbnails(message).map(|thumbnail| media.request(thumbnail).map(media_loaded));
+    Task::batch(thumbnails)
+}
+
+fn thumbnails(message: &ChatMessage) -> impl Iterator<Item = media::Key> + '_ {
+    let attachments = message.attachments.iter().filter_map(Attachment::thumbnail);
+    let embeds = message.embeds.iter().filter_map(Embed::thumbnail_key);
+    attachments.chain(embeds)
+}
+
+/// Loads the thumbnails of the messages in view of a list, and of a few around them as the
+/// messages aren't all as tall.
+fn load_visible(state: &mut State, list: &str, visible: Range<f32>) -> Task<Message> {
+    let shown: Vec<&ChatMessage> = if list == CONVERSATION {
+        state
+            .messages
+            .iter()
+            .filter(|message| message.thread_id.is_none())
+            .collect()
+    } else {
+        let open = match &state.side_panel {
+            Some(SidePanel::Thread(id)) => state.threads.iter().find(|thread| thread.id == *id),
+            _ => None,
+        };
+        open.map(|thread| thread_conversation(state, thread).collect())
+            .unwrap_or_default()
+    };
+    let count = shown.len() as f32;
+    let first = ((visible.start * count) as usize).saturating_sub(VISIBLE_MARGIN);
+    let last = ((visible.end * count).ceil() as usize + VISIBLE_MARGIN).min(shown.len());
+    let keys: Vec<media::Key> = shown
+        .get(first..last)
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|message| thumbnails(message))
+        .collect();
+    Task::batch(
+        keys.into_iter()
+            .map(|key| state.media.request(key).map(media_loaded)),
+    )
+}
+
+pub fn media_loaded((key, result): (media::Key, Result<media::Decoded, String>)) -> Message {
+    Message::MediaLoaded(key, result)
+}
+
+/// Reads a file picked, dropped or pasted, for the channel's composer (`None`) or a thread's.
+fn read(thread_id: Option<String>, path: PathBuf) -> Task<Message> {
+    Task::perform(crate::attachments::read(path), move |file| {
+        Message::FileRead(thread_id.clone(), file)
+    })
+}
+
+/// A file attached in any composer, by ID.
+fn pending_mut<'a>(state: &'a mut State, id: &str) -> Option<&'a mut Pending> {
+    composers_mut(state)
+        .flat_map(|composer| composer.attachments.iter_mut())
+        .find(|file| file.id == id)
+}
+
+/// An attachment by ID, with the message it's attached to.
+pub fn find_attachment<'a>(
+    state: &'a State,
+    id: &str,
+) -> Option<(&'a ChatMessage, &'a Attachment)> {
+    state.messages.iter().find_map(|message| {
+        let attachment = message
+            .attachments
+            .iter()
+            .find(|attachment| attachment.id == id)?;
+        Some((message, attachment))
+    })
+}
+
+pub fn update(state: &mut State, message: Message, access_token: &str) -> Task<Message> {
+    match message {
+        Message::AttachPressed(thread_id) => {
+            state.composing_in = thread_id.clone();
+            Task::perform(crate::attachments::pick_files(), move |paths| {
+                Message::FilesPicked(thread_id.clone(), paths)
+            })
+        }
+        Message::FilesPicked(thread_id, paths) => {
+            Task::batch(paths.into_iter().map(|path| read(thread_id.clone(), path)))
+        }
+        Message::FilesHovered(hovering) => {
+            state.dropping_files = hovering;
+            Task::none()
+        }
+        Message::FileDropped(path) => {
+            state.dropping_files = false;
+            read(drop_target(state), path)
+        }
+        Message::PasteRequested => {
+            let thread_id = drop_target(state);
+            Task::future(crate::attachments::paste_image())
+                .and_then(move |file| Task::done(Message::FileRead(thread_id.clone(), file)))
+        }
+        Message::FileRead(thread_id, Ok(file)) => attach(state, thread_id, file, access_token),
+        Message::FileRead(_, Err(e)) => {
+            let toast = Toast::new(ToastKind::Error, e);
+            Task::done(Message::Notify(toast))
+        }
+        Message::UploadProgress(id, progress) => {
+            let Some(file) = pending_mut(state, &id) else {
+                return Task::none();
+            };
+            match progress {
+                Progress::Sent(bytes) => {
+                    if let Upload::Sending { sent, .. } = &mut file.upload {
+                        *sent = bytes;
+                    }
+                    Task::none()
+                }
+                Progress::Finished(Ok(uploaded)) => {
+                    file.upload = Upload::Uploaded(uploaded);
+                    Task::none()
+                }
+                Progress::Finished(Err(e)) => {
+                    tracing::warn!(error = %e, name = %file.name, "Failed to upload attachment");
+                    let details = e.clone();
+                    file.upload = Upload::Failed(e);
+                    let name = file.name.clone();
+                    let toast = Toast::new(
+                        ToastKind::Error,
+                        tr!("notify-upload-failed", name = name, details = details),
+                    );
+                    Task::done(Message::Notify(toast))
+                }
+            }
+        }
+        Message::SpoilerToggled(id) => {
+            if let Some(file) = pending_mut(state, &id) {
+                file.spoiler = !file.spoiler;
+            }
+            Task::none()
+        }
+        Message::AttachmentRemoved(id) => {
+            for composer in composers_mut(state) {
+                let index = composer.attachments.iter().position(|file| file.id == id);
+                let removed = index.map(|index| composer.attachments.remove(index));
+                if let Some(Upload::Sending { handle, .. }) = removed.map(|file| file.upload) {
+                    handle.abort();
+                }
+            }
+            Task::none()
+        }
+        Message::AttachmentRevealed(message_id, attachment_id) => {
+            if let Some(message) = find_message(&mut state.messages, &message_id) {
+                message.state.revealed_attachments.insert(attachment_id);
+            }
+            Task::none()
+        }
+        Message::MediaLoaded(key, result) => {
+            state.media.insert(key, result);
+            Task::none()
+        }
+        Message::Scrolled(list, visible) => load_visible(state, list, visible),
+        Message::OpenLightbox(id) => {
+            state.lightbox = Some(id.clone());
+            // Attached here, it's shown from the file itself.
+            let url = find_attachment(state, &id)
+                .filter(|(_, attachment)| attachment.preview.is_none())
+                .and_then(|(_, attachment)| attachment.url.clone());
+            let original = url.map(|url| state.media.request(media::Key::original(url)));
+            let original = original.map(|task| task.map(media_loaded));
+            Task::batch(
+                original
+                    .into_iter()
+                    .chain([Task::done(Message::OpenLightbox(id))]),
+            )
+        }
+        Message::CloseLightbox => {
+            state.lightbox = None;
+            Task::done(Message::CloseLightbox)
+        }
+        _ => Task::none(),
+    }
+}
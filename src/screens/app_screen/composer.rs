@@ -0,0 +1,280 @@
+use crate::{
+    attachments::Pending,
+    colors::{AppColorBackground, AppColorForeground},
+    components::{autocomplete, directional_row, upload_tray},
+    config::Settings,
+    i18n::{Direction, layout_direction, tr},
+    mentions::Autocomplete,
+    notifications::{Toast, ToastKind},
+    styles::button_secondary_style,
+    threads::Thread,
+    typography::{TypeScale, caption, message_font},
+};
+use std::iter;
+
+use iced::{
+    Border, Color, Element,
+    Length::Fill,
+    Task,
+    widget::{button, column, container, horizontal_space, text, text_input, tooltip},
+};
+
+use super::{
+    COMPOSER, Message, PickerTarget, State,
+    embeds::request_previews,
+    message_input_style,
+    messages::{ChatMessage, ReplyTo, emoji_picker_in, highlight_code},
+    threads::{THREAD_COMPOSER, is_open, post_in_thread},
+    tooltip_style,
+};
+
+/// What's being written in the channel's composer or a thread's.
+#[derive(Debug, Clone, Default)]
+pub struct Composer {
+    pub draft: String,
+    /// Shown while the draft ends in a mention, channel or emoji being typed.
+    pub autocomplete: Option<Autocomplete>,
+    /// Files attached, sent with the next message.
+    pub attachments: Vec<Pending>,
+    /// What the draft answers, quoted above the input.
+    pub replying_to: Option<ReplyTo>,
+}
+
+/// The composer of the channel (`None`) or of a thread, `None` for a thread that's gone.
+pub fn composer_mut<'a>(state: &'a mut State, thread_id: Option<&str>) -> Option<&'a mut Composer> {
+    match thread_id {
+        None => Some(&mut state.composer),
+        Some(id) => state
+            .threads
+            .iter_mut()
+            .find(|thread| thread.id == id)
+            .map(|thread| &mut thread.composer),
+    }
+}
+
+/// The channel's composer and those of every thread.
+pub fn composers_mut(state: &mut State) -> impl Iterator<Item = &mut Composer> {
+    iter::once(&mut state.composer)
+        .chain(state.threads.iter_mut().map(|thread| &mut thread.composer))
+}
+
+/// Where files dropped on the window or pasted go: the thread typed in last while it's open,
+/// the channel otherwise.
+pub fn drop_target(state: &State) -> Option<String> {
+    state
+        .composing_in
+        .clone()
+        .filter(|thread_id| is_open(state, thread_id))
+}
+
+/// The channel's composer, or a thread's in its pane, with what goes above the input: the
+/// suggestions, the emoji picker, the reply and the files attached. It follows the direction
+/// of what's typed, so starting a message in Hebrew right-aligns it even in an English UI.
+pub fn composer<'a>(
+    state: &'a State,
+    settings: &'a Settings,
+    thread: Option<&'a Thread>,
+) -> Element<'a, Message> {
+    let (composer, id, placeholder) = match thread {
+        Some(thread) => (
+            &thread.composer,
+            THREAD_COMPOSER,
+            tr!("thread-composer-placeholder", name = thread.name.clone()),
+        ),
+        None => (&state.composer, COMPOSER, tr!("composer-placeholder")),
+    };
+    let thread_id = thread.map(|thread| thread.id.clone());
+    let direction = Direction::of(&composer.draft).unwrap_or(layout_direction());
+
+    let on_pick = {
+        let thread_id = thread_id.clone();
+        move |index| Message::SuggestionPicked(thread_id.clone(), index)
+    };
+    let on_input = {
+        let thread_id = thread_id.clone();
+        move |draft| Message::DraftChanged(thread_id.clone(), draft)
+    };
+    let input = text_input(&placeholder, &composer.draft)
+        .on_input(on_input)
+        .on_submit(Message::DraftSubmitted(thread_id.clone()))
+        .id(id)
+        .font(message_font())
+        .size(TypeScale::Body.size())
+        .align_x(direction.start())
+        .padding([10, 16])
+        .style(message_input_style);
+    let tools = directional_row([
+        input.into(),
+        composer_button(
+            "📎",
+            tr!("attachment-attach"),
+            Message::AttachPressed(thread_id.clone()),
+        ),
+        composer_button(
+            "😀",
+            tr!("composer-emoji"),
+            Message::EmojiPickerToggled(PickerTarget::Composer(thread_id.clone())),
+        ),
+    ])
+    .spacing(8)
+    .align_y(iced::Alignment::Center);
+
+    column![]
+        .push_maybe(
+            composer
+                .autocomplete
+                .as_ref()
+                .map(|suggested| autocomplete(&suggested.suggestions, on_pick)),
+        )
+        .push_maybe(emoji_picker_in(state, settings, thread_id.as_deref()))
+        .push_maybe(
+            composer
+                .replying_to
+                .as_ref()
+                .map(|reply| reply_bar(reply, thread_id.clone())),
+        )
+        .push_maybe((!composer.attachments.is_empty()).then(|| {
+            upload_tray(
+                &composer.attachments,
+                Message::SpoilerToggled,
+                Message::AttachmentRemoved,
+            )
+        }))
+        .push(tools)
+        .spacing(settings.density.message_spacing())
+        .into()
+}
+
+/// A button next to the composer's input, like the emoji picker's, named by its tooltip.
+fn composer_button<'a>(icon: &'a str, name: String, on_press: Message) -> Element<'a, Message> {
+    let icon = button(text(icon).size(TypeScale::Title.size()))
+        .padding([6, 8])
+        .on_press(on_press)
+        .style(|_t, _s| button::Style {
+            background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+            border: Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    tooltip(
+        icon,
+        container(caption(name))
+            .padding([4, 8])
+            .style(tooltip_style),
+        tooltip::Position::Top,
+    )
+    .into()
+}
+
+/// The message the draft answers, above the input.
+fn reply_bar(reply: &ReplyTo, thread_id: Option<String>) -> Element<'_, Message> {
+    let cancel = button(text("✕").size(TypeScale::Label.size()))
+        .padding([2, 6])
+        .on_press(Message::ReplyCancelled(thread_id))
+        .style(button_secondary_style);
+    let content = directional_row([
+        caption(tr!("reply-to", author = reply.author.clone())).into(),
+        caption(reply.excerpt.as_str())
+            .color(Color::from(AppColorForeground::SubtextPrimary))
+            .into(),
+        horizontal_space().into(),
+        cancel.into(),
+    ])
+    .spacing(8)
+    .align_y(iced::Alignment::Center);
+
+    container(content)
+        .width(Fill)
+        .padding([4, 12])
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+            border: Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Sends what's in a composer. Sending isn't there yet, the message only joins the
+/// conversation shown here, or the thread it was written in.
+fn submit(state: &mut State, thread_id: Option<String>, settings: &Settings) -> Task<Message> {
+    let Some(composer) = composer_mut(state, thread_id.as_deref()) else {
+        return Task::none();
+    };
+    if composer.autocomplete.is_some() {
+        return Task::done(Message::SuggestionPicked(thread_id, 0));
+    }
+    if composer.draft.trim().is_empty() && composer.attachments.iter().all(Pending::has_failed) {
+        return Task::none();
+    }
+    if composer.attachments.iter().any(Pending::is_uploading) {
+        let toast = Toast::new(ToastKind::Info, tr!("notify-attachments-uploading"));
+        return Task::done(Message::Notify(toast));
+    }
+
+    let content = std::mem::take(&mut composer.draft);
+    let reply = composer.replying_to.take();
+    // Files that failed to upload stay behind to be removed or tried again.
+    let (failed, sent): (Vec<Pending>, Vec<Pending>) = std::mem::take(&mut composer.attachments)
+        .into_iter()
+        .partition(Pending::has_failed);
+    composer.attachments = failed;
+
+    state.local_messages += 1;
+    let mut message = ChatMessage::local(
+        format!("local-{}", state.local_messages),
+        state.user_id.clone(),
+        content,
+        reply,
+    );
+    message.attachments = sent.into_iter().map(Pending::into_attachment).collect();
+    if thread_id.is_some() {
+        message.thread_id = thread_id;
+        return post_in_thread(state, message, settings);
+    }
+    let task = Task::batch([
+        highlight_code(&mut state.highlights, &message.document),
+        request_previews(&mut state.previews, settings, &message),
+    ]);
+    state.messages.push(message);
+    task
+}
+
+pub fn update(state: &mut State, message: Message, settings: &Settings) -> Task<Message> {
+    match message {
+        Message::DraftChanged(thread_id, draft) => {
+            let autocomplete = Autocomplete::new(&draft, &state.directory, settings.skin_tone);
+            if let Some(composer) = composer_mut(state, thread_id.as_deref()) {
+                composer.draft = draft;
+                composer.autocomplete = autocomplete;
+            }
+            state.composing_in = thread_id;
+            Task::none()
+        }
+        Message::DraftSubmitted(thread_id) => submit(state, thread_id, settings),
+        Message::SuggestionPicked(thread_id, index) => {
+            let Some(composer) = composer_mut(state, thread_id.as_deref()) else {
+                return Task::none();
+            };
+            if let Some(draft) = composer
+                .autocomplete
+                .take()
+                .and_then(|autocomplete| autocomplete.complete(&composer.draft, index))
+            {
+                composer.draft = draft;
+            }
+            Task::none()
+        }
+        Message::ReplyCancelled(thread_id) => {
+            if let Some(composer) = composer_mut(state, thread_id.as_deref()) {
+                composer.replying_to = None;
+            }
+            Task::none()
+        }
+        _ => Task::none(),
+    }
+}
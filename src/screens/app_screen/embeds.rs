@@ -0,0 +1,127 @@
+use crate::{
+    components::{MessageAction, embed_card},
+    config::Settings,
+    constants::API_URL,
+    embeds::{Embed, Previews},
+    i18n::tr,
+    notifications::{Toast, ToastKind},
+    utils::send_empty,
+};
+use std::collections::HashMap;
+
+use iced::{Element, Task, widget::column};
+
+use super::{
+    Message, State,
+    attachments::media_loaded,
+    messages::{ChatMessage, find_message},
+};
+
+/// Messages with more links only preview the first few.
+const MAX_EMBEDS: usize = 5;
+
+/// The previews of the links in a message, the server's first and then those made here for
+/// the other links. `None` when there are none or the user turned them off.
+pub fn embed_list<'a>(
+    state: &'a State,
+    settings: &Settings,
+    message: &'a ChatMessage,
+) -> Option<Element<'a, Message>> {
+    if message.suppress_embeds || !settings.link_previews {
+        return None;
+    }
+    let made_here = message
+        .document
+        .links()
+        .into_iter()
+        .filter(|url| !message.embeds.iter().any(|embed| embed.url == *url))
+        .filter_map(|url| state.previews.get(url));
+    let cards: Vec<Element<'a, Message>> = message
+        .embeds
+        .iter()
+        .chain(made_here)
+        .take(MAX_EMBEDS)
+        .map(|embed| {
+            embed_card(embed, &state.media, |url| {
+                Message::MessageAction(message.id.clone(), MessageAction::OpenUrl(url))
+            })
+        })
+        .collect();
+    (!cards.is_empty()).then(|| column(cards).spacing(4).into())
+}
+
+pub async fn suppress_embeds(
+    access_token: String,
+    channel_id: String,
+    message_id: String,
+) -> Result<(), String> {
+    let mut map = HashMap::new();
+    map.insert("suppress_embeds", true);
+
+    let client = reqwest::Client::new();
+    send_empty(
+        client
+            .patch(format!(
+                "{API_URL}/channels/{channel_id}/messages/{message_id}"
+            ))
+            .bearer_auth(access_token)
+            .json(&map),
+    )
+    .await
+}
+
+/// Hides a message's previews right away, and for everyone on the server for messages there.
+pub fn remove_embeds(state: &mut State, id: String, access_token: &str) -> Task<Message> {
+    let Some(message) = find_message(&mut state.messages, &id) else {
+        return Task::none();
+    };
+    message.suppress_embeds = true;
+    let Some(channel_id) = message.channel_id.clone() else {
+        return Task::none();
+    };
+    Task::perform(
+        suppress_embeds(access_token.to_string(), channel_id, id.clone()),
+        move |result| Message::EmbedsSaved(id.clone(), result),
+    )
+}
+
+/// Makes previews for the links in a message the server didn't send an embed for, unless the
+/// user turned them off or the author removed them.
+pub fn request_previews(
+    previews: &mut Previews,
+    settings: &Settings,
+    message: &ChatMessage,
+) -> Task<Message> {
+    if !settings.link_previews || message.suppress_embeds {
+        return Task::none();
+    }
+    previews
+        .request(
+            &settings.link_preview_proxy,
+            &message.document,
+            &message.embeds,
+        )
+        .map(|(url, embed)| Message::PreviewLoaded(url, embed))
+}
+
+pub fn update(state: &mut State, message: Message) -> Task<Message> {
+    match message {
+        Message::EmbedsSaved(id, Err(e)) => {
+            tracing::warn!(error = %e, message_id = %id, "Failed to remove embeds");
+            if let Some(message) = find_message(&mut state.messages, &id) {
+                message.suppress_embeds = false;
+            }
+            let toast = Toast::new(ToastKind::Error, tr!("notify-embeds-failed"));
+            Task::done(Message::Notify(toast))
+        }
+        Message::PreviewLoaded(url, embed) => {
+            let thumbnail = embed.as_ref().and_then(Embed::thumbnail_key);
+            state.previews.insert(url, embed);
+            match thumbnail {
+                Some(thumbnail) => state.media.request(thumbnail).map(media_loaded),
+                None => Task::none(),
+            }
+        }
+        _ => Task::none(),
+    }
+}
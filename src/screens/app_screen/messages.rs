@@ -0,0 +1,1008 @@
+use crate::{
+    attachments::Attachment,
+    colors::{AppColorForeground, AppColorMain, AppColorStatus},
+    components::{
+        EmojiPicker, MessageAction, MessageCommand, MessageState, attachment_list, context_menu,
+        directional_row, emoji_picker::Event as PickerEvent, message_body, message_toolbar,
+        reaction_bar,
+    },
+    config::Settings,
+    constants::{API_URL, WEB_URL},
+    embeds::Embed,
+    highlight,
+    i18n::{self, Direction, layout_direction, tr},
+    keybindings::Action,
+    markdown::{self, Document},
+    mentions,
+    notifications::{Toast, ToastKind},
+    reactions::{self, ReactionEmoji, Reactions},
+    styles::{button_danger_style, button_secondary_style},
+    typography::{TypeScale, caption, label, message_font},
+    utils::send_empty,
+};
+use chrono::{DateTime, Local};
+use std::{
+    collections::HashMap,
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use iced::{
+    Border, Color, Element, Event,
+    Length::Fill,
+    Padding, Task, event, keyboard,
+    widget::{
+        Column, button, column, container, horizontal_space, mouse_area, scrollable,
+        scrollable::RelativeOffset, text, text_input, tooltip,
+    },
+    window,
+};
+
+use super::{
+    COMPOSER, CONVERSATION, Message, PickerTarget, State,
+    attachments::load_thumbnails,
+    composer::{composer_mut, composers_mut},
+    copy,
+    embeds::{embed_list, remove_embeds, request_previews},
+    message_input_style,
+    pins::{pin_notice, set_pinned},
+    threads::{
+        THREAD_COMPOSER, THREAD_CONVERSATION, is_open, open_thread, start_thread, thread_link,
+        thread_of,
+    },
+    tooltip_style,
+};
+
+const EDIT_INPUT: &str = "message-edit";
+/// How long the mark on a message jumped to takes to fade out.
+const JUMP_FADE: Duration = Duration::from_secs(2);
+/// How many characters of a message a reply quotes.
+const EXCERPT_LENGTH: usize = 80;
+
+/// A message in the conversation.
+#[derive(Debug, Clone)]
+pub struct ChatMessage {
+    /// The server's ID, or one made up here for a message that isn't on the server.
+    pub id: String,
+    /// `None` for a message that isn't on the server, like the preview.
+    pub channel_id: Option<String>,
+    pub author_id: Option<String>,
+    /// The author's name as the server sent it, `None` for messages written here.
+    pub author: Option<String>,
+    /// The markdown as typed, for editing and copying.
+    pub content: String,
+    pub document: Document,
+    pub sent: DateTime<Local>,
+    pub edited: Option<DateTime<Local>>,
+    /// When the message was pinned to the channel.
+    pub pinned: Option<DateTime<Local>>,
+    pub kind: MessageKind,
+    /// The thread the message was posted in, `None` for the channel itself.
+    pub thread_id: Option<String>,
+    pub reply_to: Option<ReplyTo>,
+    pub attachments: Vec<Attachment>,
+    /// Previews of the links in the message, as the server sent them.
+    pub embeds: Vec<Embed>,
+    /// The author removed the previews, none are shown or made here.
+    pub suppress_embeds: bool,
+    /// Spoilers and code blocks clicked open.
+    pub state: MessageState,
+    pub reactions: Reactions,
+}
+
+impl ChatMessage {
+    /// A message written here that isn't on the server.
+    pub fn local(
+        id: String,
+        author_id: Option<String>,
+        content: String,
+        reply_to: Option<ReplyTo>,
+    ) -> Self {
+        Self {
+            id,
+            channel_id: None,
+            author_id,
+            author: None,
+            document: markdown::parse(&content),
+            content,
+            sent: Local::now(),
+            edited: None,
+            pinned: None,
+            kind: MessageKind::Normal,
+            thread_id: None,
+            reply_to,
+            attachments: Vec::new(),
+            embeds: Vec::new(),
+            suppress_embeds: false,
+            state: MessageState::default(),
+            reactions: Reactions::default(),
+        }
+    }
+
+    pub fn set_content(&mut self, content: String) {
+        self.document = markdown::parse(&content);
+        self.content = content;
+    }
+
+    /// The start of the message's first line, with an ellipsis if there's more.
+    pub fn excerpt(&self, length: usize) -> String {
+        let line = self.content.lines().next().unwrap_or_default();
+        let mut excerpt: String = line.chars().take(length).collect();
+        if excerpt.len() < self.content.trim_end().len() {
+            excerpt.push('…');
+        }
+        excerpt
+    }
+
+    /// A quote of the message's first line, for a reply to it.
+    pub fn reply(&self, author: String) -> ReplyTo {
+        ReplyTo {
+            message_id: self.id.clone(),
+            author,
+            excerpt: self.excerpt(EXCERPT_LENGTH),
+        }
+    }
+}
+
+/// Messages written by users, or posted by the app about something that happened in the
+/// channel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageKind {
+    Normal,
+    /// The author pinned the message with this ID.
+    Pinned(String),
+}
+
+/// The message a reply answers, quoted above the reply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplyTo {
+    pub message_id: String,
+    pub author: String,
+    pub excerpt: String,
+}
+
+/// Messages oldest first, with a divider where the unread ones start. The channel and the
+/// open thread each have their own, scrolled by `id`.
+pub fn message_list<'a>(
+    state: &'a State,
+    settings: &'a Settings,
+    messages: impl Iterator<Item = &'a ChatMessage>,
+    id: &'static str,
+) -> Element<'a, Message> {
+    let messages = messages.flat_map(|message| {
+        let divider = (state.unread_from.as_ref() == Some(&message.id)).then(unread_divider);
+        divider
+            .into_iter()
+            .chain([message_view(state, settings, message)])
+    });
+    scrollable(column(messages).spacing(8))
+        .id(scrollable::Id::new(id))
+        .on_scroll(move |viewport| Message::Scrolled(id, visible_part(viewport)))
+        .anchor_bottom()
+        .height(Fill)
+        .into()
+}
+
+/// The part of a list in view, as fractions of its height from the top.
+fn visible_part(viewport: scrollable::Viewport) -> Range<f32> {
+    let height = viewport.content_bounds().height.max(1.0);
+    let top = viewport.absolute_offset().y;
+    top / height..(top + viewport.bounds().height) / height
+}
+
+fn unread_divider<'a>() -> Element<'a, Message> {
+    let line = container(horizontal_space())
+        .width(Fill)
+        .height(1)
+        .style(|_t| container::Style {
+            background: Some(AppColorStatus::Failure.to_bg()),
+            ..Default::default()
+        });
+    let label = caption(tr!("message-unread-divider")).color(Color::from(AppColorStatus::Failure));
+    directional_row([line.into(), label.into()])
+        .spacing(8)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+pub fn is_own(state: &State, message: &ChatMessage) -> bool {
+    state.user_id.is_some() && state.user_id == message.author_id
+}
+
+/// What the toolbar of a hovered message offers, the rest is in its context menu. Only the
+/// author may edit or delete a message, and threads are only started from the channel.
+fn toolbar_commands(state: &State, message: &ChatMessage) -> Vec<MessageCommand> {
+    let mut commands = vec![MessageCommand::Reply, MessageCommand::React];
+    if message.thread_id.is_none() {
+        commands.push(MessageCommand::StartThread);
+    }
+    if is_own(state, message) {
+        commands.extend([MessageCommand::Edit, MessageCommand::Delete]);
+    }
+    commands.push(MessageCommand::OpenMenu);
+    commands
+}
+
+/// Everything the user may do with a message. Links and IDs only exist for messages on the
+/// server, and only the author may remove the previews of its links.
+fn menu_commands(state: &State, message: &ChatMessage) -> Vec<MessageCommand> {
+    let own = is_own(state, message);
+    let has_embeds = !message.embeds.is_empty() || !message.document.links().is_empty();
+    let on_server = message.channel_id.is_some();
+    let in_channel = message.thread_id.is_none();
+    let pin = match message.pinned {
+        Some(_) => MessageCommand::Unpin,
+        None => MessageCommand::Pin,
+    };
+    [
+        Some(MessageCommand::Reply),
+        in_channel.then_some(MessageCommand::StartThread),
+        own.then_some(MessageCommand::Edit),
+        own.then_some(MessageCommand::Delete),
+        (own && has_embeds && !message.suppress_embeds).then_some(MessageCommand::RemoveEmbeds),
+        state.directory.can_pin_messages.then_some(pin),
+        Some(MessageCommand::CopyText),
+        on_server.then_some(MessageCommand::CopyLink),
+        on_server.then_some(MessageCommand::CopyId),
+        Some(MessageCommand::MarkUnread),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// The name a message is shown under.
+pub fn author_name(state: &State, message: &ChatMessage) -> String {
+    let member = message
+        .author_id
+        .as_deref()
+        .and_then(|id| state.directory.member(id));
+    match (member, &message.author) {
+        (Some(member), _) => member.name.clone(),
+        (None, Some(author)) => author.clone(),
+        (None, None) => tr!("message-you"),
+    }
+}
+
+/// A message with its toolbar while hovered, its context menu once right-clicked and the
+/// question before deleting it. Tinted with a bar on its start side when it mentions the
+/// user.
+fn message_view<'a>(
+    state: &'a State,
+    settings: &'a Settings,
+    message: &'a ChatMessage,
+) -> Element<'a, Message> {
+    let id = &message.id;
+    if let MessageKind::Pinned(pinned_id) = &message.kind {
+        return pin_notice(state, message, pinned_id);
+    }
+
+    let content: Element<'a, Message> = match &state.editing {
+        Some((editing, draft)) if editing == id => edit_input(draft),
+        _ => message_body(
+            &message.document,
+            &message.state,
+            &state.highlights,
+            &state.directory,
+            |action| Message::MessageAction(id.clone(), action),
+        )
+        .into(),
+    };
+    // The toolbar has a button to add the first one.
+    let reactions = message.reactions.iter().next().is_some().then(|| {
+        reaction_bar(
+            &message.reactions,
+            state.user_id.as_deref(),
+            &state.directory,
+            |emoji| Message::ReactionToggled(id.clone(), emoji),
+            Message::EmojiPickerToggled(PickerTarget::Reaction(id.clone())),
+        )
+    });
+    let thread = state
+        .threads
+        .iter()
+        .find(|thread| thread.parent_id == *id && !is_open(state, &thread.id))
+        .map(|thread| thread_link(state, thread));
+    let attachments = (!message.attachments.is_empty()).then(|| {
+        attachment_list(
+            &message.attachments,
+            &state.media,
+            &message.state.revealed_attachments,
+            |attachment| Message::AttachmentRevealed(id.clone(), attachment),
+            Message::OpenLightbox,
+            |url| Message::MessageAction(id.clone(), MessageAction::OpenUrl(url)),
+        )
+    });
+    let body = column![]
+        .push_maybe(message.reply_to.as_ref().map(reply_quote))
+        .push(message_header(state, message))
+        .push(content)
+        .push_maybe(attachments)
+        .push_maybe(embed_list(state, settings, message))
+        .push_maybe(reactions)
+        .push_maybe(thread)
+        .spacing(2);
+
+    let mentioned = state
+        .user_id
+        .as_deref()
+        .is_some_and(|user| mentions::mentions_user(&message.document, user, &state.directory));
+    let body = if mentioned {
+        mention_highlight(body)
+    } else {
+        body.into()
+    };
+    let jumped = state.jumped_to.as_ref().filter(|(jumped, _)| jumped == id);
+    let body: Element<'a, Message> = match jumped {
+        Some((_, at)) => {
+            let strength = if settings.reduced_motion {
+                1.0
+            } else {
+                1.0 - at.elapsed().as_secs_f32() / JUMP_FADE.as_secs_f32()
+            };
+            container(body)
+                .width(Fill)
+                .style(move |_t| container::Style {
+                    background: Some(
+                        Color::from(AppColorMain::AccentPrimary)
+                            .scale_alpha(0.1 * strength.max(0.0))
+                            .into(),
+                    ),
+                    ..Default::default()
+                })
+                .into()
+        }
+        None => body,
+    };
+
+    let menu = (state.context_menu.as_ref() == Some(id)).then(|| {
+        let menu = context_menu(menu_commands(state, message), |command| {
+            Message::MessageCommand(id.clone(), command)
+        });
+        container(menu)
+            .width(Fill)
+            .align_x(layout_direction().end())
+    });
+    let confirmation = state
+        .confirming_delete
+        .as_ref()
+        .filter(|confirming| *confirming == id)
+        .map(|_| delete_confirmation(id));
+
+    let area = mouse_area(body)
+        .on_enter(Message::MessageHovered(id.clone()))
+        .on_exit(Message::MessageUnhovered(id.clone()))
+        .on_right_press(Message::ContextMenuOpened(id.clone()));
+    column![area]
+        .push_maybe(confirmation)
+        .push_maybe(menu)
+        .spacing(4)
+        .into()
+}
+
+/// The author, when it was sent and whether it was edited or pinned, with the toolbar at the
+/// end while the message is hovered.
+fn message_header<'a>(state: &'a State, message: &'a ChatMessage) -> Element<'a, Message> {
+    let subtext = Color::from(AppColorForeground::SubtextPrimary);
+    let sent = caption(i18n::format_date(&message.sent, "date-time-format")).color(subtext);
+    let edited: Option<Element<'a, Message>> = message.edited.map(|edited| {
+        let date = i18n::format_date(&edited, "date-time-format");
+        let when = container(caption(tr!("message-edited-at", date = date)))
+            .padding([4, 8])
+            .style(tooltip_style);
+        let marker = caption(tr!("message-edited")).color(subtext);
+        tooltip(marker, when, tooltip::Position::Top).into()
+    });
+    let pinned = message.pinned.map(|_| caption("📌").into());
+    let toolbar = (state.hovered.as_ref() == Some(&message.id)).then(|| {
+        message_toolbar(toolbar_commands(state, message), |command| {
+            Message::MessageCommand(message.id.clone(), command)
+        })
+    });
+
+    let mut header = vec![label(author_name(state, message)).into(), sent.into()];
+    header.extend(edited);
+    header.extend(pinned);
+    header.push(horizontal_space().into());
+    header.extend(toolbar);
+    directional_row(header)
+        .spacing(8)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+/// The message a reply answers, in a line above the reply.
+fn reply_quote(reply: &ReplyTo) -> Element<'_, Message> {
+    directional_row([
+        caption("↪").into(),
+        caption(format!("@{}", reply.author))
+            .color(Color::from(AppColorMain::AccentPrimary))
+            .into(),
+        caption(reply.excerpt.as_str())
+            .color(Color::from(AppColorForeground::SubtextPrimary))
+            .into(),
+    ])
+    .spacing(6)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+/// Replaces the text of a message being edited. Enter saves, Escape cancels.
+fn edit_input(draft: &str) -> Element<'_, Message> {
+    let direction = Direction::of(draft).unwrap_or(layout_direction());
+    let input = text_input("", draft)
+        .on_input(Message::EditChanged)
+        .on_submit(Message::EditSubmitted)
+        .id(EDIT_INPUT)
+        .font(message_font())
+        .size(TypeScale::Body.size())
+        .align_x(direction.start())
+        .padding([6, 10])
+        .style(message_input_style);
+    let hint =
+        caption(tr!("message-edit-hint")).color(Color::from(AppColorForeground::SubtextPrimary));
+    column![input, hint].spacing(4).into()
+}
+
+/// Escape pressed in the edit input. The input captures the key, so the shortcut listening
+/// for keys nothing else took never sees it. Uncaptured presses are left to that shortcut.
+pub fn edit_escape(event: Event, status: event::Status, _window: window::Id) -> Option<Message> {
+    match (event, status) {
+        (
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::Escape),
+                ..
+            }),
+            event::Status::Captured,
+        ) => Some(Message::Shortcut(Action::CloseModal)),
+        _ => None,
+    }
+}
+
+/// Asks before deleting a message. Holding Shift while deleting skips this.
+fn delete_confirmation<'a>(id: &str) -> Element<'a, Message> {
+    let delete = button(text(tr!("message-delete")).size(TypeScale::Label.size()))
+        .padding([4, 10])
+        .on_press(Message::DeleteConfirmed(id.to_string()))
+        .style(button_danger_style);
+    let cancel = button(text(tr!("message-delete-cancel")).size(TypeScale::Label.size()))
+        .padding([4, 10])
+        .on_press(Message::DeleteCancelled)
+        .style(button_secondary_style);
+    let hint =
+        caption(tr!("message-delete-hint")).color(Color::from(AppColorForeground::SubtextPrimary));
+
+    let content = column![
+        caption(tr!("message-delete-confirm")),
+        directional_row([delete.into(), cancel.into()]).spacing(8),
+        hint,
+    ]
+    .spacing(6);
+    container(content)
+        .width(Fill)
+        .padding([6, 8])
+        .style(|_t| container::Style {
+            background: Some(Color::from(AppColorStatus::Failure).scale_alpha(0.1).into()),
+            border: Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+fn mention_highlight(body: Column<'_, Message>) -> Element<'_, Message> {
+    // The bar is the outer background showing through the padding, like a quote's.
+    let bar = match layout_direction() {
+        Direction::Ltr => Padding::ZERO.left(2.0),
+        Direction::Rtl => Padding::ZERO.right(2.0),
+    };
+    let tinted = container(body)
+        .width(Fill)
+        .padding([2, 8])
+        .style(|_t| container::Style {
+            background: Some(Color::from(AppColorStatus::Warning).scale_alpha(0.1).into()),
+            ..Default::default()
+        });
+    container(tinted)
+        .width(Fill)
+        .padding(bar)
+        .style(|_t| container::Style {
+            background: Some(AppColorStatus::Warning.to_bg()),
+            ..Default::default()
+        })
+        .into()
+}
+
+/// The emoji picker, above the composer of the channel (`None`) or the thread it was opened
+/// for.
+pub fn emoji_picker_in<'a>(
+    state: &'a State,
+    settings: &'a Settings,
+    thread_id: Option<&str>,
+) -> Option<Element<'a, Message>> {
+    let (target, picker) = state.emoji_picker.as_ref()?;
+    let opened_in = match target {
+        PickerTarget::Composer(thread_id) => thread_id.as_deref(),
+        PickerTarget::Reaction(message_id) => thread_of(state, message_id),
+    };
+    if opened_in != thread_id {
+        return None;
+    }
+    let recent = &settings.recent_emoji;
+    let picker = picker.view(settings.skin_tone, recent, &state.directory.emoji);
+    Some(
+        container(picker.map(Message::EmojiPicker))
+            .width(Fill)
+            .align_x(layout_direction().end())
+            .into(),
+    )
+}
+
+pub async fn edit_message(
+    access_token: String,
+    channel_id: String,
+    message_id: String,
+    content: String,
+) -> Result<(), String> {
+    let mut map = HashMap::new();
+    map.insert("content", content);
+
+    let client = reqwest::Client::new();
+    send_empty(
+        client
+            .patch(format!(
+                "{API_URL}/channels/{channel_id}/messages/{message_id}"
+            ))
+            .bearer_auth(access_token)
+            .json(&map),
+    )
+    .await
+}
+
+pub async fn delete_message(
+    access_token: String,
+    channel_id: String,
+    message_id: String,
+) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    send_empty(
+        client
+            .delete(format!(
+                "{API_URL}/channels/{channel_id}/messages/{message_id}"
+            ))
+            .bearer_auth(access_token),
+    )
+    .await
+}
+
+/// Does what was picked from a message's toolbar or context menu.
+pub fn message_command(
+    state: &mut State,
+    id: String,
+    command: MessageCommand,
+    access_token: &str,
+    settings: &Settings,
+) -> Task<Message> {
+    state.context_menu = None;
+    let Some(message) = state.messages.iter().find(|message| message.id == id) else {
+        return Task::none();
+    };
+    match command {
+        MessageCommand::Reply => {
+            let input = match message.thread_id {
+                Some(_) => THREAD_COMPOSER,
+                None => COMPOSER,
+            };
+            let reply = message.reply(author_name(state, message));
+            let thread_id = message.thread_id.clone();
+            if let Some(composer) = composer_mut(state, thread_id.as_deref()) {
+                composer.replying_to = Some(reply);
+            }
+            state.composing_in = thread_id;
+            text_input::focus(input)
+        }
+        MessageCommand::React => {
+            Task::done(Message::EmojiPickerToggled(PickerTarget::Reaction(id)))
+        }
+        MessageCommand::StartThread => start_thread(state, id, access_token, settings),
+        // Only the author may change a message.
+        MessageCommand::Edit | MessageCommand::Delete if !is_own(state, message) => Task::none(),
+        MessageCommand::Edit => {
+            state.editing = Some((id, message.content.clone()));
+            Task::batch([
+                text_input::focus(EDIT_INPUT),
+                text_input::move_cursor_to_end(EDIT_INPUT),
+            ])
+        }
+        MessageCommand::Delete if state.modifiers.shift() => {
+            Task::done(Message::DeleteConfirmed(id))
+        }
+        MessageCommand::Delete => {
+            state.confirming_delete = Some(id);
+            Task::none()
+        }
+        MessageCommand::Pin | MessageCommand::Unpin if !state.directory.can_pin_messages => {
+            Task::none()
+        }
+        MessageCommand::Pin => set_pinned(state, id, true, access_token),
+        MessageCommand::Unpin => set_pinned(state, id, false, access_token),
+        MessageCommand::CopyText => copy(message.content.clone(), tr!("message-text-copied")),
+        MessageCommand::CopyLink => match &message.channel_id {
+            Some(channel_id) => copy(
+                format!("{WEB_URL}/channels/{channel_id}/{id}"),
+                tr!("message-link-copied"),
+            ),
+            None => Task::none(),
+        },
+        MessageCommand::CopyId => copy(id, tr!("message-id-copied")),
+        MessageCommand::MarkUnread => {
+            state.unread_from = Some(id);
+            Task::none()
+        }
+        MessageCommand::RemoveEmbeds if !is_own(state, message) => Task::none(),
+        MessageCommand::RemoveEmbeds => remove_embeds(state, id, access_token),
+        MessageCommand::OpenMenu => {
+            state.context_menu = Some(id);
+            Task::none()
+        }
+    }
+}
+
+pub fn find_message<'a>(messages: &'a mut [ChatMessage], id: &str) -> Option<&'a mut ChatMessage> {
+    messages.iter_mut().find(|message| message.id == id)
+}
+
+/// Lets go of everything pointing at a message that's gone. Unread messages start at the one
+/// that took its place.
+pub fn forget(state: &mut State, id: &str, index: usize) {
+    state.hovered.take_if(|hovered| *hovered == id);
+    state.context_menu.take_if(|open| *open == id);
+    state
+        .confirming_delete
+        .take_if(|confirming| *confirming == id);
+    state.editing.take_if(|(editing, _)| *editing == id);
+    for composer in composers_mut(state) {
+        composer.replying_to.take_if(|reply| reply.message_id == id);
+    }
+    state.jumped_to.take_if(|(jumped_to, _)| *jumped_to == id);
+    state
+        .emoji_picker
+        .take_if(|(target, _)| matches!(target, PickerTarget::Reaction(on) if *on == id));
+    if state.unread_from.as_deref() == Some(id) {
+        state.unread_from = state.messages.get(index).map(|message| message.id.clone());
+    }
+    forget_highlights(state);
+}
+
+/// Drops the highlighting of code blocks that no message has anymore.
+fn forget_highlights(state: &mut State) {
+    let documents = state.messages.iter().map(|message| &message.document);
+    state.highlights.retain(documents);
+}
+
+pub fn highlight_code(cache: &mut highlight::Cache, document: &Document) -> Task<Message> {
+    cache
+        .request(document)
+        .map(|(key, highlighted)| Message::CodeHighlighted(key, highlighted))
+}
+
+/// Puts an emoji picked from the picker where the picker was opened for.
+pub fn emoji_picked(
+    state: &mut State,
+    target: PickerTarget,
+    emoji: ReactionEmoji,
+) -> Task<Message> {
+    let used = match &emoji {
+        ReactionEmoji::Unicode(glyph) => Task::done(Message::EmojiUsed(glyph.clone())),
+        ReactionEmoji::Custom { .. } => Task::none(),
+    };
+    let picked = match target {
+        PickerTarget::Composer(thread_id) => {
+            if let Some(composer) = composer_mut(state, thread_id.as_deref()) {
+                match &emoji {
+                    ReactionEmoji::Unicode(glyph) => composer.draft.push_str(glyph),
+                    ReactionEmoji::Custom { id, name } => {
+                        composer.draft.push_str(&format!("<:{name}:{id}>"));
+                    }
+                }
+            }
+            Task::none()
+        }
+        // Picking an emoji already reacted with keeps the reaction.
+        PickerTarget::Reaction(message_id) => {
+            let reacted = state
+                .messages
+                .iter()
+                .filter(|message| message.id == message_id)
+                .flat_map(|message| message.reactions.iter())
+                .any(|reaction| {
+                    reaction.emoji == emoji
+                        && state
+                            .user_id
+                            .as_deref()
+                            .is_some_and(|id| reaction.includes(id))
+                });
+            if reacted {
+                Task::none()
+            } else {
+                Task::done(Message::ReactionToggled(message_id, emoji))
+            }
+        }
+    };
+    Task::batch([used, picked])
+}
+
+pub fn update(
+    state: &mut State,
+    message: Message,
+    access_token: &str,
+    settings: &Settings,
+) -> Task<Message> {
+    match message {
+        Message::MessageAction(_, MessageAction::OpenUrl(url)) => match open::that_detached(&url) {
+            Ok(()) => Task::none(),
+            Err(e) => {
+                tracing::warn!(error = %e, %url, "Failed to open link");
+                let toast = Toast::new(ToastKind::Error, tr!("notify-link-failed", url = url));
+                Task::done(Message::Notify(toast))
+            }
+        },
+        Message::MessageAction(id, MessageAction::RevealSpoiler(spoiler)) => {
+            if let Some(message) = find_message(&mut state.messages, &id) {
+                message.state.revealed_spoilers.insert(spoiler);
+            }
+            Task::none()
+        }
+        Message::MessageAction(_, MessageAction::CopyCode(code)) => copy(code, tr!("code-copied")),
+        Message::MessageAction(id, MessageAction::ToggleCode(key)) => {
+            if let Some(message) = find_message(&mut state.messages, &id) {
+                let expanded = &mut message.state.expanded_code;
+                if !expanded.remove(&key) {
+                    expanded.insert(key);
+                }
+            }
+            Task::none()
+        }
+        Message::MessageHovered(id) => {
+            state.hovered = Some(id);
+            Task::none()
+        }
+        // Moving onto the next message may enter it before leaving this one.
+        Message::MessageUnhovered(id) => {
+            state.hovered.take_if(|hovered| *hovered == id);
+            state.jumped_to.take_if(|(jumped_to, _)| *jumped_to == id);
+            Task::none()
+        }
+        Message::ContextMenuOpened(id) => {
+            state.context_menu = Some(id);
+            Task::none()
+        }
+        Message::MessageCommand(id, command) => {
+            message_command(state, id, command, access_token, settings)
+        }
+        Message::EditChanged(draft) => {
+            if let Some((_, edited)) = &mut state.editing {
+                *edited = draft;
+            }
+            Task::none()
+        }
+        Message::EditSubmitted => {
+            let Some((id, content)) = state.editing.take() else {
+                return Task::none();
+            };
+            // Like everywhere else, taking all the text out of a message deletes it.
+            if content.trim().is_empty() {
+                return Task::done(Message::MessageCommand(id, MessageCommand::Delete));
+            }
+            let Some(message) =
+                find_message(&mut state.messages, &id).filter(|message| message.content != content)
+            else {
+                return Task::none();
+            };
+            // Shown right away, put back if the server doesn't take it.
+            let previous = message.clone();
+            message.set_content(content.clone());
+            message.edited = Some(Local::now());
+            let highlighted = Task::batch([
+                highlight_code(&mut state.highlights, &message.document),
+                request_previews(&mut state.previews, settings, message),
+            ]);
+            let channel_id = message.channel_id.clone();
+            forget_highlights(state);
+            let Some(channel_id) = channel_id else {
+                return highlighted;
+            };
+            let save = Task::perform(
+                edit_message(access_token.to_string(), channel_id, id, content),
+                move |result| Message::EditSaved(Box::new(previous.clone()), result),
+            );
+            Task::batch([highlighted, save])
+        }
+        Message::EditSaved(previous, Err(e)) => {
+            tracing::warn!(error = %e, message_id = %previous.id, "Failed to edit message");
+            let Some(message) = find_message(&mut state.messages, &previous.id) else {
+                return Task::none();
+            };
+            message.content = previous.content;
+            message.document = previous.document;
+            message.edited = previous.edited;
+            let highlighted = highlight_code(&mut state.highlights, &message.document);
+            forget_highlights(state);
+            let toast = Toast::new(ToastKind::Error, tr!("notify-edit-failed"));
+            Task::batch([highlighted, Task::done(Message::Notify(toast))])
+        }
+        Message::DeleteConfirmed(id) => {
+            state.confirming_delete = None;
+            let Some(index) = state.messages.iter().position(|message| message.id == id) else {
+                return Task::none();
+            };
+            // Gone right away, put back if the server doesn't take it.
+            let message = state.messages.remove(index);
+            forget(state, &id, index);
+            let Some(channel_id) = message.channel_id.clone() else {
+                return Task::none();
+            };
+            Task::perform(
+                delete_message(access_token.to_string(), channel_id, id),
+                move |result| Message::DeleteSaved(index, Box::new(message.clone()), result),
+            )
+        }
+        Message::DeleteCancelled => {
+            state.confirming_delete = None;
+            Task::none()
+        }
+        Message::DeleteSaved(index, message, Err(e)) => {
+            tracing::warn!(error = %e, message_id = %message.id, "Failed to delete message");
+            let index = index.min(state.messages.len());
+            let thumbnails = load_thumbnails(&mut state.media, &message);
+            let highlighted = highlight_code(&mut state.highlights, &message.document);
+            state.messages.insert(index, *message);
+            let toast = Toast::new(ToastKind::Error, tr!("notify-delete-failed"));
+            Task::batch([thumbnails, highlighted, Task::done(Message::Notify(toast))])
+        }
+        // Message lists are anchored to the bottom, offsets count up from there.
+        Message::JumpToMessage(id) => {
+            let Some(target) = state.messages.iter().find(|message| message.id == id) else {
+                return Task::none();
+            };
+            let thread_id = target.thread_id.clone();
+            // A thread's list starts with the message the thread was started from.
+            let skipped = usize::from(thread_id.is_some());
+            let mut listed = state
+                .messages
+                .iter()
+                .filter(|message| message.thread_id == thread_id);
+            let index = skipped
+                + listed
+                    .position(|message| message.id == id)
+                    .unwrap_or_default();
+            let last = (index + listed.count()).max(1);
+            let scrolled = match &thread_id {
+                Some(thread_id) => {
+                    open_thread(state, thread_id);
+                    THREAD_CONVERSATION
+                }
+                None => CONVERSATION,
+            };
+            let offset = RelativeOffset {
+                x: 0.0,
+                y: 1.0 - index as f32 / last as f32,
+            };
+            state.jumped_to = Some((id, Instant::now()));
+            Task::batch([
+                scrollable::snap_to(scrollable::Id::new(scrolled), offset),
+                Task::done(Message::ClosePins),
+            ])
+        }
+        Message::JumpFading(now) => {
+            state
+                .jumped_to
+                .take_if(|(_, at)| now.duration_since(*at) >= JUMP_FADE);
+            Task::none()
+        }
+        Message::EmojiPickerToggled(target) => {
+            state.emoji_picker = match state.emoji_picker.take() {
+                Some((open, _)) if open == target => None,
+                _ => Some((target, EmojiPicker::default())),
+            };
+            Task::none()
+        }
+        Message::EmojiPicker(msg) => {
+            let Some((target, picker)) = &mut state.emoji_picker else {
+                return Task::none();
+            };
+            match picker.update(msg) {
+                Some(PickerEvent::Picked(emoji)) => {
+                    let target = target.clone();
+                    state.emoji_picker = None;
+                    emoji_picked(state, target, emoji)
+                }
+                Some(PickerEvent::SkinToneChanged(tone)) => {
+                    Task::done(Message::SkinToneChanged(tone))
+                }
+                None => Task::none(),
+            }
+        }
+        Message::ReactionToggled(id, emoji) => {
+            let Some(user_id) = &state.user_id else {
+                return Task::none();
+            };
+            let Some(message) = find_message(&mut state.messages, &id) else {
+                return Task::none();
+            };
+            let added = message.reactions.toggle(emoji.clone(), user_id);
+            let Some(channel_id) = &message.channel_id else {
+                return Task::none();
+            };
+            // Shown right away, taken back if the server doesn't take it.
+            Task::perform(
+                reactions::set_reaction(
+                    access_token.to_string(),
+                    channel_id.clone(),
+                    id.clone(),
+                    emoji.clone(),
+                    added,
+                ),
+                move |result| Message::ReactionSaved(id.clone(), emoji.clone(), added, result),
+            )
+        }
+        Message::ReactionSaved(id, emoji, added, result) => match (result, &state.user_id) {
+            (Err(e), Some(user_id)) => {
+                tracing::warn!(error = %e, ?emoji, added, "Failed to save reaction");
+                if let Some(message) = find_message(&mut state.messages, &id) {
+                    if added {
+                        message.reactions.remove(&emoji, user_id);
+                    } else {
+                        message.reactions.add(emoji, user_id);
+                    }
+                }
+                let toast = Toast::new(ToastKind::Error, tr!("notify-reaction-failed"));
+                Task::done(Message::Notify(toast))
+            }
+            _ => Task::none(),
+        },
+        Message::CodeHighlighted(key, highlighted) => {
+            state.highlights.insert(key, highlighted);
+            Task::none()
+        }
+        _ => Task::none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::keyboard::{Location, Modifiers, key};
+
+    fn key_pressed(key: key::Named, code: key::Code) -> Event {
+        Event::Keyboard(keyboard::Event::KeyPressed {
+            key: keyboard::Key::Named(key),
+            modified_key: keyboard::Key::Named(key),
+            physical_key: key::Physical::Code(code),
+            location: Location::Standard,
+            modifiers: Modifiers::default(),
+            text: None,
+        })
+    }
+
+    #[test]
+    fn escape_taken_by_the_edit_input_cancels() {
+        let escape = key_pressed(key::Named::Escape, key::Code::Escape);
+        let message = edit_escape(escape, event::Status::Captured, window::Id::unique());
+        assert!(matches!(
+            message,
+            Some(Message::Shortcut(Action::CloseModal))
+        ));
+    }
+
+    #[test]
+    fn other_keys_and_uncaptured_escape_are_left_alone() {
+        let escape = key_pressed(key::Named::Escape, key::Code::Escape);
+        let enter = key_pressed(key::Named::Enter, key::Code::Enter);
+        let id = window::Id::unique();
+        assert!(edit_escape(escape, event::Status::Ignored, id).is_none());
+        assert!(edit_escape(enter, event::Status::Captured, id).is_none());
+    }
+}
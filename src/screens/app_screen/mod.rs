@@ -0,0 +1,896 @@
+mod attachments;
+mod composer;
+mod embeds;
+mod messages;
+mod pins;
+mod threads;
+
+pub use attachments::lightbox;
+pub use composer::Composer;
+pub use messages::{ChatMessage, MessageKind};
+pub use pins::pinned_messages;
+
+use crate::{
+    attachments::{File, Progress},
+    colors::{AppColorBackground, AppColorForeground, AppColorMain, AppColorStatus},
+    components::{EmojiPicker, MessageAction, MessageCommand, directional_row, emoji_picker},
+    config::Settings,
+    constants::API_URL,
+    embeds::{Embed, Previews},
+    emoji::SkinTone,
+    highlight::{self, Highlighted},
+    i18n::tr,
+    keybindings::Action,
+    media,
+    mentions::{self, Channel, Directory, Member},
+    notifications::{Toast, ToastAction, ToastKind},
+    reactions::ReactionEmoji,
+    threads::{AutoArchive, Thread},
+    typography::{TypeScale, caption, title},
+    utils::{send_empty, send_json},
+};
+use chrono::{DateTime, Local};
+use serde::Deserialize;
+use std::{
+    ops::Range,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use iced::{
+    Border, Color, Element, Event,
+    Length::Fill,
+    Padding, Subscription, Task, Theme, clipboard, event, keyboard, time,
+    widget::{
+        button, column, container, horizontal_space, stack, text, text_input, tooltip,
+        vertical_space,
+    },
+    window,
+};
+use validator::Validate;
+
+use self::{
+    attachments::{drop_overlay, load_thumbnails},
+    composer::{composer, composers_mut},
+    embeds::request_previews,
+    messages::{highlight_code, is_own, message_list},
+    threads::side_panel,
+};
+
+const COMPOSER: &str = "composer";
+const CONVERSATION: &str = "conversation";
+
+/// How often threads are checked for having gone quiet.
+const ARCHIVE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Default, Validate)]
+pub struct State {
+    /// ID of the logged in user, to pick out messages that mention them.
+    pub user_id: Option<String>,
+    pub is_loading: bool,
+    pub email_status: Option<EmailStatus>,
+    pub verification_resend: ResendStatus,
+    /// What's being written under the conversation. Threads have composers of their own.
+    pub composer: Composer,
+    /// The thread whose composer was typed in last, `None` for the channel's, see
+    /// [`composer::drop_target`].
+    pub composing_in: Option<String>,
+    /// Members, roles and channels of the current conversation, only those of the preview
+    /// until guilds and DMs are loaded.
+    pub directory: Directory,
+    /// The channel shown, `None` until channels are loaded.
+    pub channel: Option<Channel>,
+    /// The conversation, oldest first, with the user's edits and deletes applied. Only a
+    /// preview until messages are loaded.
+    pub messages: Vec<ChatMessage>,
+    /// Counts messages written here, for their made-up IDs.
+    pub local_messages: usize,
+    /// The message under the mouse, which shows its toolbar.
+    pub hovered: Option<String>,
+    /// The message whose context menu is open.
+    pub context_menu: Option<String>,
+    /// The message being edited in place, with its edited text.
+    pub editing: Option<(String, String)>,
+    /// The message waiting for the user to confirm it should be deleted.
+    pub confirming_delete: Option<String>,
+    /// The first message marked unread, with a divider above it.
+    pub unread_from: Option<String>,
+    /// The message jumped to from the pins or a mention, with when. Its mark fades out, or
+    /// stays until the mouse leaves it with reduced motion.
+    pub jumped_to: Option<(String, Instant)>,
+    /// Keys held down, Shift deletes without asking.
+    pub modifiers: keyboard::Modifiers,
+    /// The channel's threads, whose messages are in `messages` with the thread's ID.
+    pub threads: Vec<Thread>,
+    pub side_panel: Option<SidePanel>,
+    /// Attached images and link preview thumbnails, and later avatars and icons, see
+    /// [`media::Cache`].
+    pub media: media::Cache,
+    /// Previews of links the server didn't send an embed for, see [`Previews`].
+    pub previews: Previews,
+    /// The attached image open in the lightbox, by attachment ID.
+    pub lightbox: Option<String>,
+    /// Files are dragged over the window, to be dropped into the composer.
+    pub dropping_files: bool,
+    /// Open above the composer, with what the picked emoji goes to.
+    pub emoji_picker: Option<(PickerTarget, EmojiPicker)>,
+    /// Highlighted code blocks of every message shown, see [`highlight::Cache`].
+    pub highlights: highlight::Cache,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailStatus {
+    pub email: Option<String>,
+    pub verified: bool,
+}
+
+/// What's shown next to the conversation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SidePanel {
+    /// The channel's threads, active and archived.
+    Threads,
+    /// The thread with this ID, with its messages and composer.
+    Thread(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PickerTarget {
+    /// The channel's composer (`None`) or a thread's.
+    Composer(Option<String>),
+    /// A reaction to the message with this ID.
+    Reaction(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ResendStatus {
+    #[default]
+    Idle,
+    Sending,
+    Sent,
+    Failed,
+}
+
+impl State {
+    pub fn new(user_id: Option<String>) -> Self {
+        let preview = ChatMessage::local(
+            "preview".to_string(),
+            user_id.clone(),
+            "Messagesss".to_string(),
+            None,
+        );
+        let directory = preview_directory(user_id.as_deref());
+        Self {
+            user_id,
+            channel: None,
+            is_loading: false,
+            email_status: None,
+            verification_resend: ResendStatus::Idle,
+            composer: Composer::default(),
+            composing_in: None,
+            directory,
+            messages: vec![preview],
+            local_messages: 0,
+            hovered: None,
+            context_menu: None,
+            editing: None,
+            confirming_delete: None,
+            unread_from: None,
+            jumped_to: None,
+            modifiers: keyboard::Modifiers::default(),
+            threads: Vec::new(),
+            side_panel: None,
+            media: media::Cache::default(),
+            previews: Previews::default(),
+            lightbox: None,
+            dropping_files: false,
+            emoji_picker: None,
+            highlights: highlight::Cache::default(),
+        }
+    }
+}
+
+/// The preview is the user's own conversation until guilds load, where they can mention and
+/// pin anything as in a DM.
+fn preview_directory(user_id: Option<&str>) -> Directory {
+    let you = user_id.map(|id| Member {
+        id: id.to_string(),
+        name: tr!("preview-member-you"),
+        roles: Vec::new(),
+    });
+    Directory {
+        members: you.into_iter().collect(),
+        roles: Vec::new(),
+        channels: vec![Channel {
+            id: "preview".to_string(),
+            name: tr!("preview-channel"),
+        }],
+        emoji: Vec::new(),
+        can_mention_everyone: true,
+        can_pin_messages: true,
+    }
+}
+
+// Chat messages are what the screen is about, plenty of variants are named after them.
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone)]
+pub enum Message {
+    OpenSettings,
+    ToggleInbox,
+    Load,
+    EmailStatusLoaded(Result<EmailStatus, String>),
+    ResendVerificationPressed,
+    VerificationResent(Result<(), String>),
+    /// Typed in the channel's composer (`None`) or a thread's, like the other composer
+    /// messages.
+    DraftChanged(Option<String>, String),
+    DraftSubmitted(Option<String>),
+    SuggestionPicked(Option<String>, usize),
+    ReplyCancelled(Option<String>),
+    MessageAction(String, MessageAction),
+    MessageHovered(String),
+    MessageUnhovered(String),
+    ContextMenuOpened(String),
+    MessageCommand(String, MessageCommand),
+    EditChanged(String),
+    EditSubmitted,
+    /// The server's answer to an edit, with the message as it was to put back on failure.
+    EditSaved(Box<ChatMessage>, Result<(), String>),
+    DeleteConfirmed(String),
+    DeleteCancelled,
+    /// The server's answer to a delete, with the message and where it was to put it back on
+    /// failure.
+    DeleteSaved(usize, Box<ChatMessage>, Result<(), String>),
+    ModifiersChanged(keyboard::Modifiers),
+    /// The server's answer to pinning or unpinning, with when the message was pinned before
+    /// to put back on failure.
+    PinSaved(String, Option<DateTime<Local>>, Result<(), String>),
+    /// Opens or closes the pinned messages, which the [`crate::App`] shows as a dialog.
+    TogglePins,
+    ClosePins,
+    JumpToMessage(String),
+    /// A frame while the mark on the message jumped to fades out.
+    JumpFading(Instant),
+    /// The server's answer to starting a thread, with the thread to add once it has the ID.
+    ThreadStarted(Box<Thread>, Result<String, String>),
+    ToggleThreadList,
+    OpenThread(String),
+    CloseSidePanel,
+    ThreadArchiveToggled(String),
+    AutoArchiveSelected(String, AutoArchive),
+    /// The server's answer to archiving a thread or changing when it archives itself.
+    ThreadSaved(String, Result<(), String>),
+    /// For the [`crate::App`] to save as the default for new threads.
+    AutoArchiveChanged(AutoArchive),
+    /// Archives the threads that have gone quiet.
+    ArchiveIdleThreads,
+    EmojiPickerToggled(PickerTarget),
+    EmojiPicker(emoji_picker::Message),
+    ReactionToggled(String, ReactionEmoji),
+    /// The server's answer to adding (`true`) or removing a reaction.
+    ReactionSaved(String, ReactionEmoji, bool, Result<(), String>),
+    /// An emoji was picked, for the [`crate::App`] to remember as recently used.
+    EmojiUsed(String),
+    /// For the [`crate::App`] to save.
+    SkinToneChanged(SkinTone),
+    CodeHighlighted(u64, Option<Arc<Highlighted>>),
+    AttachPressed(Option<String>),
+    FilesPicked(Option<String>, Vec<PathBuf>),
+    /// Files are dragged over the window (`true`) or no longer.
+    FilesHovered(bool),
+    FileDropped(PathBuf),
+    /// Ctrl+V, for an image on the clipboard. The composer pastes text by itself.
+    PasteRequested,
+    FileRead(Option<String>, Result<File, String>),
+    UploadProgress(String, Progress),
+    SpoilerToggled(String),
+    AttachmentRemoved(String),
+    /// A spoiler clicked open, by message and attachment ID.
+    AttachmentRevealed(String, String),
+    MediaLoaded(media::Key, Result<media::Decoded, String>),
+    /// A message list scrolled, by its ID, with the part of it in view as fractions of its
+    /// height. The images of the messages coming into view are loaded again if they were
+    /// dropped from memory or failed to load, see [`media::Cache`].
+    Scrolled(&'static str, Range<f32>),
+    /// A preview made here of the page at a URL, `None` when there's nothing to preview.
+    PreviewLoaded(String, Option<Embed>),
+    /// The server's answer to removing a message's embeds.
+    EmbedsSaved(String, Result<(), String>),
+    /// Opens an attached image in the lightbox, which the [`crate::App`] shows as a dialog.
+    OpenLightbox(String),
+    CloseLightbox,
+    /// Feedback for the [`crate::App`] to show as a toast.
+    Notify(Toast<Message>),
+    /// A keybinding for something on this screen, see [`crate::keybindings`].
+    Shortcut(Action),
+}
+
+fn verification_banner(state: &State) -> Option<Element<'_, Message>> {
+    let status = state.email_status.as_ref()?;
+    let email = status.email.as_deref().filter(|_| !status.verified)?;
+
+    let resend_label = match &state.verification_resend {
+        ResendStatus::Idle | ResendStatus::Failed => tr!("verify-email-resend"),
+        ResendStatus::Sending => tr!("verify-email-sending"),
+        ResendStatus::Sent => tr!("verify-email-sent"),
+    };
+    let can_resend = matches!(
+        state.verification_resend,
+        ResendStatus::Idle | ResendStatus::Failed
+    );
+
+    let content = directional_row([
+        text(tr!("verify-email-banner", email = email))
+            .size(TypeScale::Label.size())
+            .color(Color::from(AppColorBackground::Primary))
+            .into(),
+        horizontal_space().into(),
+        button(text(resend_label).size(TypeScale::Caption.size()))
+            .on_press_maybe(can_resend.then_some(Message::ResendVerificationPressed))
+            .padding([4, 10])
+            .style(|_t, _s| button::Style {
+                background: Some(AppColorBackground::Primary.to_bg()),
+                text_color: AppColorForeground::Primary.into(),
+                border: Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .into(),
+    ])
+    .spacing(12)
+    .align_y(iced::Alignment::Center);
+
+    Some(
+        container(content)
+            .width(Fill)
+            .padding([6, 16])
+            .style(|_t| container::Style {
+                background: Some(AppColorStatus::Warning.to_bg()),
+                ..Default::default()
+            })
+            .into(),
+    )
+}
+
+/// Opens the notification inbox, with a badge counting what hasn't been read yet.
+fn inbox_button<'a>(unread: usize) -> Element<'a, Message> {
+    let bell = button(
+        text("🔔")
+            .size(TypeScale::Title.size())
+            .width(Fill)
+            .height(Fill)
+            .center(),
+    )
+    .width(40)
+    .height(40)
+    .on_press(Message::ToggleInbox)
+    .style(|_t, _s| button::Style {
+        border: Border {
+            radius: 10.0.into(),
+            ..Default::default()
+        },
+        background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+        text_color: AppColorForeground::Secondary.into(),
+        ..Default::default()
+    });
+
+    stack![bell].push_maybe(badge(unread)).into()
+}
+
+/// A count in the top corner of a 40px button, `None` for nothing to count.
+fn badge<'a>(count: usize) -> Option<Element<'a, Message>> {
+    Some(container(pill(count)?).align_right(40).into())
+}
+
+/// A count of what hasn't been read, `None` for nothing to count.
+fn pill<'a>(count: usize) -> Option<Element<'a, Message>> {
+    let count = match count {
+        0 => return None,
+        1..=99 => count.to_string(),
+        _ => "99+".to_string(),
+    };
+    let pill = container(text(count).size(TypeScale::Small.size()))
+        .padding([1, 5])
+        .style(|_t| container::Style {
+            background: Some(AppColorStatus::Failure.to_bg()),
+            text_color: Some(AppColorBackground::Primary.into()),
+            border: Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    Some(pill.into())
+}
+
+fn tooltip_style(_theme: &Theme) -> container::Style {
+    container::Style {
+        background: Some(AppColorBackground::Primary.to_bg()),
+        text_color: Some(AppColorForeground::Primary.into()),
+        border: Border {
+            radius: 4.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn message_input_style(_theme: &Theme, _status: text_input::Status) -> text_input::Style {
+    text_input::Style {
+        background: AppColorBackground::SurfacePrimary.to_bg(),
+        border: Border {
+            radius: 8.0.into(),
+            ..Default::default()
+        },
+        icon: AppColorForeground::Secondary.into(),
+        placeholder: AppColorForeground::Secondary.into(),
+        value: AppColorForeground::Primary.into(),
+        selection: AppColorMain::Secondary.into(),
+    }
+}
+
+/// A small button at the end of a side panel's header.
+fn panel_button<'a>(icon: &'a str, on_press: Message) -> Element<'a, Message> {
+    button(text(icon).size(TypeScale::Body.size()))
+        .on_press(on_press)
+        .padding([0, 6])
+        .style(|_t, _s| button::Style {
+            text_color: AppColorForeground::Secondary.into(),
+            ..Default::default()
+        })
+        .into()
+}
+
+/// The channel's name with buttons for its threads, counting their unread messages, and for
+/// its pinned messages, counting them.
+fn channel_header(state: &State) -> Element<'_, Message> {
+    let name = state
+        .channel
+        .as_ref()
+        .map(|channel| title(format!("# {}", channel.name)));
+    let unread = state.threads.iter().map(|thread| thread.unread).sum();
+    let threads = header_button(
+        "🧵",
+        tr!("threads-title"),
+        unread,
+        Message::ToggleThreadList,
+    );
+    let pinned = state.messages.iter().filter(|m| m.pinned.is_some()).count();
+    let pins = header_button("📌", tr!("pins-title"), pinned, Message::TogglePins);
+
+    let mut header: Vec<Element<'_, Message>> = name.into_iter().map(Element::from).collect();
+    header.extend([horizontal_space().into(), threads, pins]);
+    directional_row(header)
+        .spacing(8)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+/// A 40px button in the channel header with a badge, named by its tooltip.
+fn header_button<'a>(
+    icon: &'a str,
+    name: String,
+    count: usize,
+    on_press: Message,
+) -> Element<'a, Message> {
+    let icon = button(
+        text(icon)
+            .size(TypeScale::Title.size())
+            .width(Fill)
+            .height(Fill)
+            .center(),
+    )
+    .width(40)
+    .height(40)
+    .on_press(on_press)
+    .style(|_t, _s| button::Style {
+        border: Border {
+            radius: 10.0.into(),
+            ..Default::default()
+        },
+        background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+        text_color: AppColorForeground::Secondary.into(),
+        ..Default::default()
+    });
+    tooltip(
+        stack![icon].push_maybe(badge(count)),
+        container(caption(name))
+            .padding([4, 8])
+            .style(tooltip_style),
+        tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+pub fn view<'a>(state: &'a State, settings: &'a Settings, unread: usize) -> Element<'a, Message> {
+    let density = settings.density;
+    let home = button(
+        text("A")
+            .size(TypeScale::Title.size())
+            .width(Fill)
+            .height(Fill)
+            .center(),
+    )
+    .width(40)
+    .height(40)
+    .style(|_t, _s| button::Style {
+        border: Border {
+            radius: 10.0.into(),
+            ..Default::default()
+        },
+        background: Some(AppColorMain::Secondary.to_bg()),
+        ..Default::default()
+    });
+    let unread_mentions = mentions_of_user(state)
+        .filter(|(index, _)| *index >= unread_from(state))
+        .count();
+    let channel_messages = state
+        .messages
+        .iter()
+        .filter(|message| message.thread_id.is_none());
+
+    let mut panes: Vec<Element<'a, Message>> = vec![
+        container(
+            column![
+                stack![home].push_maybe(badge(unread_mentions)),
+                vertical_space(),
+                inbox_button(unread),
+                button(
+                    text("⚙")
+                        .size(TypeScale::Title.size())
+                        .width(Fill)
+                        .height(Fill)
+                        .center()
+                )
+                .width(40)
+                .height(40)
+                .on_press(Message::OpenSettings)
+                .style(|_t, _s| {
+                    button::Style {
+                        border: Border {
+                            radius: 10.0.into(),
+                            ..Default::default()
+                        },
+                        background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+                        text_color: AppColorForeground::Secondary.into(),
+                        ..Default::default()
+                    }
+                }),
+            ]
+            .spacing(12),
+        )
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::Primary.to_bg()),
+            ..Default::default()
+        })
+        .center_x(72)
+        .padding(Padding {
+            top: 12.0,
+            bottom: 12.0,
+            left: 16.0,
+            right: 16.0,
+        })
+        .height(Fill)
+        .into(),
+        container(
+            column![
+                button(
+                    text(tr!("app-search"))
+                        .width(Fill)
+                        .height(40)
+                        .size(TypeScale::Body.size())
+                        .color(Color::from(AppColorForeground::Secondary))
+                        .center()
+                )
+                .padding(0)
+                .width(Fill)
+                .style(|_t, _s| {
+                    button::Style {
+                        background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+                        border: Border {
+                            radius: 8.0.into(),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }
+                }),
+                vertical_space()
+            ]
+            .spacing(12),
+        )
+        .width(260)
+        .height(Fill)
+        .padding(12)
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::Secondary.to_bg()),
+            ..Default::default()
+        })
+        .into(),
+        container(
+            column![
+                channel_header(state),
+                message_list(state, settings, channel_messages, CONVERSATION)
+            ]
+            .push(composer(state, settings, None))
+            .spacing(density.message_spacing()),
+        )
+        .padding(density.message_padding())
+        .width(Fill)
+        .height(Fill)
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::Tertiary.to_bg()),
+            ..Default::default()
+        })
+        .into(),
+    ];
+    panes.extend(side_panel(state, settings));
+    let layout = directional_row(panes).width(Fill).height(Fill);
+    let layout = stack![layout].push_maybe(state.dropping_files.then(drop_overlay));
+
+    column![]
+        .push_maybe(verification_banner(state))
+        .push(layout)
+        .width(Fill)
+        .height(Fill)
+        .into()
+}
+
+pub async fn fetch_email_status(access_token: String) -> Result<EmailStatus, String> {
+    let client = reqwest::Client::new();
+    send_json(
+        client
+            .get(format!("{API_URL}/users/@me/email"))
+            .bearer_auth(access_token),
+    )
+    .await
+}
+
+pub async fn resend_verification_email(access_token: String) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    send_empty(
+        client
+            .post(format!("{API_URL}/auth/email/verify/resend"))
+            .bearer_auth(access_token),
+    )
+    .await
+}
+
+/// Where the unread messages start in the conversation, its end when everything's read.
+fn unread_from(state: &State) -> usize {
+    state
+        .unread_from
+        .as_ref()
+        .and_then(|id| state.messages.iter().position(|message| message.id == *id))
+        .unwrap_or(state.messages.len())
+}
+
+/// The messages mentioning the user, with where they are in the conversation.
+fn mentions_of_user(
+    state: &State,
+) -> impl DoubleEndedIterator<Item = (usize, &ChatMessage)> + Clone {
+    state
+        .messages
+        .iter()
+        .enumerate()
+        .filter(move |(_, message)| {
+            state.user_id.as_deref().is_some_and(|user_id| {
+                mentions::mentions_user(&message.document, user_id, &state.directory)
+            })
+        })
+}
+
+/// Follows Shift for deleting without asking, attaches files dropped on the window and images
+/// pasted with Ctrl+V, and catches Escape in the edit input. Also archives threads gone quiet
+/// and, without reduced motion, fades out the mark on a message jumped to.
+pub fn subscription(state: &State, reduced_motion: bool) -> Subscription<Message> {
+    let modifiers = event::listen_with(|event, _status, _window| match event {
+        Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+            Some(Message::ModifiersChanged(modifiers))
+        }
+        Event::Keyboard(keyboard::Event::KeyPressed {
+            key: keyboard::Key::Character(key),
+            modifiers,
+            ..
+        }) if key.eq_ignore_ascii_case("v") && modifiers.command() => Some(Message::PasteRequested),
+        Event::Window(window::Event::FileHovered(_)) => Some(Message::FilesHovered(true)),
+        Event::Window(window::Event::FilesHoveredLeft) => Some(Message::FilesHovered(false)),
+        Event::Window(window::Event::FileDropped(path)) => Some(Message::FileDropped(path)),
+        _ => None,
+    });
+    let archive = state
+        .threads
+        .iter()
+        .any(|thread| !thread.archived)
+        .then(|| time::every(ARCHIVE_CHECK_INTERVAL).map(|_| Message::ArchiveIdleThreads));
+    let fading = (state.jumped_to.is_some() && !reduced_motion)
+        .then(|| window::frames().map(Message::JumpFading));
+    let editing = state
+        .editing
+        .is_some()
+        .then(|| event::listen_with(messages::edit_escape));
+    Subscription::batch(
+        [modifiers]
+            .into_iter()
+            .chain(archive)
+            .chain(fading)
+            .chain(editing),
+    )
+}
+
+pub fn update(
+    state: &mut State,
+    message: Message,
+    access_token: &str,
+    settings: &Settings,
+) -> Task<Message> {
+    match message {
+        Message::Load => {
+            let highlights = state
+                .messages
+                .iter()
+                .map(|message| highlight_code(&mut state.highlights, &message.document));
+            let thumbnails = state
+                .messages
+                .iter()
+                .map(|message| load_thumbnails(&mut state.media, message));
+            let previews = state
+                .messages
+                .iter()
+                .map(|message| request_previews(&mut state.previews, settings, message));
+            let email = Task::perform(
+                fetch_email_status(access_token.to_string()),
+                Message::EmailStatusLoaded,
+            );
+            Task::batch(highlights.chain(thumbnails).chain(previews).chain([email]))
+        }
+        Message::EmailStatusLoaded(result) => {
+            match result {
+                Ok(status) => state.email_status = Some(status),
+                Err(e) => tracing::warn!(error = %e, "Error getting email status"),
+            }
+            Task::none()
+        }
+        Message::ResendVerificationPressed => {
+            state.verification_resend = ResendStatus::Sending;
+            Task::perform(
+                resend_verification_email(access_token.to_string()),
+                Message::VerificationResent,
+            )
+        }
+        Message::VerificationResent(result) => match result {
+            Ok(()) => {
+                state.verification_resend = ResendStatus::Sent;
+                Task::none()
+            }
+            Err(e) => {
+                state.verification_resend = ResendStatus::Failed;
+                let toast = Toast::new(
+                    ToastKind::Error,
+                    tr!("notify-verification-failed", details = e),
+                )
+                .with_action(ToastAction::Retry, Message::ResendVerificationPressed);
+                Task::done(Message::Notify(toast))
+            }
+        },
+        // Handled by the `App`, which sees these when they come back from here.
+        Message::OpenSettings => Task::done(Message::OpenSettings),
+        Message::ToggleInbox => Task::done(Message::ToggleInbox),
+        Message::TogglePins => Task::done(Message::TogglePins),
+        Message::ClosePins => Task::done(Message::ClosePins),
+        Message::ModifiersChanged(modifiers) => {
+            state.modifiers = modifiers;
+            Task::none()
+        }
+        // Closes what was opened last, one thing per press.
+        Message::Shortcut(Action::CloseModal) => {
+            if state.editing.is_some() {
+                state.editing = None;
+            } else if state.confirming_delete.is_some() {
+                state.confirming_delete = None;
+            } else if state.context_menu.is_some() {
+                state.context_menu = None;
+            } else if state.emoji_picker.is_some() {
+                state.emoji_picker = None;
+            } else if let Some(composer) =
+                composers_mut(state).find(|composer| composer.replying_to.is_some())
+            {
+                composer.replying_to = None;
+            } else {
+                state.side_panel = None;
+            }
+            Task::none()
+        }
+        Message::Shortcut(Action::EditLastMessage) if state.composer.draft.is_empty() => {
+            let last = state
+                .messages
+                .iter()
+                .rev()
+                .filter(|message| message.thread_id.is_none())
+                .find(|message| message.kind == MessageKind::Normal && is_own(state, message));
+            match last {
+                Some(message) => Task::done(Message::MessageCommand(
+                    message.id.clone(),
+                    MessageCommand::Edit,
+                )),
+                None => Task::none(),
+            }
+        }
+        // The first mention since the unread divider, or the latest one when they're all read.
+        Message::Shortcut(Action::JumpToMentions) => {
+            let unread_from = unread_from(state);
+            let mut mentions = mentions_of_user(state);
+            let unread = mentions.clone().find(|(index, _)| *index >= unread_from);
+            match unread.or_else(|| mentions.next_back()) {
+                Some((_, message)) => Task::done(Message::JumpToMessage(message.id.clone())),
+                None => Task::none(),
+            }
+        }
+        // There's no channel list yet, the conversation shown is the only channel to read.
+        Message::Shortcut(Action::MarkRead) => {
+            state.unread_from = None;
+            Task::none()
+        }
+        Message::Shortcut(action) => {
+            // Not offered until the channel list is there, see `Action::is_available`.
+            tracing::debug!(?action, "No handler for shortcut on this screen");
+            Task::none()
+        }
+        Message::MessageAction(..)
+        | Message::MessageHovered(..)
+        | Message::MessageUnhovered(..)
+        | Message::ContextMenuOpened(..)
+        | Message::MessageCommand(..)
+        | Message::EditChanged(..)
+        | Message::EditSubmitted
+        | Message::EditSaved(..)
+        | Message::DeleteConfirmed(..)
+        | Message::DeleteCancelled
+        | Message::DeleteSaved(..)
+        | Message::JumpToMessage(..)
+        | Message::JumpFading(..)
+        | Message::EmojiPickerToggled(..)
+        | Message::EmojiPicker(..)
+        | Message::ReactionToggled(..)
+        | Message::ReactionSaved(..)
+        | Message::CodeHighlighted(..) => messages::update(state, message, access_token, settings),
+        Message::PinSaved(..) => pins::update(state, message),
+        Message::ThreadStarted(..)
+        | Message::ToggleThreadList
+        | Message::OpenThread(..)
+        | Message::CloseSidePanel
+        | Message::ThreadArchiveToggled(..)
+        | Message::AutoArchiveSelected(..)
+        | Message::ThreadSaved(..)
+        | Message::ArchiveIdleThreads => threads::update(state, message, access_token),
+        Message::EmbedsSaved(..) | Message::PreviewLoaded(..) => embeds::update(state, message),
+        Message::DraftChanged(..)
+        | Message::DraftSubmitted(..)
+        | Message::SuggestionPicked(..)
+        | Message::ReplyCancelled(..) => composer::update(state, message, settings),
+        Message::AttachPressed(..)
+        | Message::FilesPicked(..)
+        | Message::FilesHovered(..)
+        | Message::FileDropped(..)
+        | Message::PasteRequested
+        | Message::FileRead(..)
+        | Message::UploadProgress(..)
+        | Message::SpoilerToggled(..)
+        | Message::AttachmentRemoved(..)
+        | Message::AttachmentRevealed(..)
+        | Message::MediaLoaded(..)
+        | Message::Scrolled(..)
+        | Message::OpenLightbox(..)
+        | Message::CloseLightbox => attachments::update(state, message, access_token),
+        _ => Task::none(),
+    }
+}
+
+fn copy(content: String, confirmation: String) -> Task<Message> {
+    let toast = Toast::new(ToastKind::Success, confirmation);
+    Task::batch([
+        clipboard::write(content),
+        Task::done(Message::Notify(toast)),
+    ])
+}
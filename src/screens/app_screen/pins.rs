@@ -0,0 +1,249 @@
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorMain},
+    components::{MessageCommand, directional_row, message_body},
+    constants::API_URL,
+    i18n::{self, layout_direction, tr},
+    notifications::{Toast, ToastKind},
+    styles::button_secondary_style,
+    typography::{TypeScale, caption, label, title},
+    utils::send_empty,
+};
+use chrono::Local;
+use std::cmp::Reverse;
+
+use iced::{
+    Border, Color, Element,
+    Length::Fill,
+    Padding, Shadow, Task, Vector,
+    widget::{button, column, container, horizontal_space, scrollable, text},
+};
+
+use super::{
+    Message, State,
+    messages::{ChatMessage, MessageKind, author_name, find_message},
+    threads::thread_of,
+};
+
+const PINS_WIDTH: f32 = 420.0;
+
+/// The pinned messages of the channel, newest pin first, drawn by the [`crate::App`] over
+/// the screen while its pinned messages dialog is open.
+pub fn pinned_messages(state: &State) -> Element<'_, Message> {
+    let header = directional_row([
+        title(tr!("pins-title")).into(),
+        horizontal_space().into(),
+        button(text("×").size(TypeScale::Body.size()))
+            .on_press(Message::ClosePins)
+            .padding([0, 6])
+            .style(|_t, _s| button::Style {
+                text_color: AppColorForeground::Secondary.into(),
+                ..Default::default()
+            })
+            .into(),
+    ])
+    .spacing(8)
+    .align_y(iced::Alignment::Center);
+
+    let mut pinned: Vec<&ChatMessage> = state
+        .messages
+        .iter()
+        .filter(|message| message.pinned.is_some())
+        .collect();
+    pinned.sort_by_key(|message| Reverse(message.pinned));
+
+    let list: Element<'_, Message> = if pinned.is_empty() {
+        caption(tr!("pins-empty"))
+            .color(Color::from(AppColorForeground::SubtextPrimary))
+            .into()
+    } else {
+        let pins = pinned.into_iter().map(|message| pin(state, message));
+        scrollable(column(pins).spacing(8)).into()
+    };
+
+    let panel = container(column![header, list].spacing(16))
+        .width(PINS_WIDTH)
+        .max_height(520.0)
+        .padding(16)
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::Secondary.to_bg()),
+            border: Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            shadow: Shadow {
+                color: Color::BLACK.scale_alpha(0.3),
+                offset: Vector::new(0.0, 4.0),
+                blur_radius: 16.0,
+            },
+            ..Default::default()
+        });
+
+    container(panel)
+        .width(Fill)
+        .height(Fill)
+        .padding(Padding::ZERO.top(64.0).left(16.0).right(16.0))
+        .align_x(layout_direction().end())
+        .into()
+}
+
+/// A pinned message in the pins dialog, with buttons to jump to it and to unpin it.
+pub fn pin<'a>(state: &'a State, message: &'a ChatMessage) -> Element<'a, Message> {
+    let subtext = Color::from(AppColorForeground::SubtextPrimary);
+    let jump = button(text(tr!("pins-jump")).size(TypeScale::Caption.size()))
+        .on_press(Message::JumpToMessage(message.id.clone()))
+        .padding([2, 8])
+        .style(button_secondary_style);
+    let unpin: Option<Element<'a, Message>> = state.directory.can_pin_messages.then(|| {
+        button(text("×").size(TypeScale::Label.size()))
+            .on_press(Message::MessageCommand(
+                message.id.clone(),
+                MessageCommand::Unpin,
+            ))
+            .padding([2, 8])
+            .style(button_secondary_style)
+            .into()
+    });
+
+    let mut header: Vec<Element<'a, Message>> = vec![
+        label(author_name(state, message)).into(),
+        caption(i18n::format_date(&message.sent, "date-time-format"))
+            .color(subtext)
+            .into(),
+        horizontal_space().into(),
+        jump.into(),
+    ];
+    header.extend(unpin);
+    let body = message_body(
+        &message.document,
+        &message.state,
+        &state.highlights,
+        &state.directory,
+        |action| Message::MessageAction(message.id.clone(), action),
+    );
+
+    container(column![directional_row(header).spacing(8), body].spacing(4))
+        .width(Fill)
+        .padding([8, 10])
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::SurfacePrimary.to_bg()),
+            border: Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Posted in the channel when a message is pinned, linking to the message and the pins.
+pub fn pin_notice<'a>(
+    state: &'a State,
+    notice: &'a ChatMessage,
+    pinned_id: &'a str,
+) -> Element<'a, Message> {
+    let link = |content: String, on_press: Message| -> Element<'a, Message> {
+        button(caption(content))
+            .padding(0)
+            .on_press(on_press)
+            .style(|_t, _s| button::Style {
+                text_color: AppColorMain::AccentPrimary.into(),
+                ..Default::default()
+            })
+            .into()
+    };
+    directional_row([
+        caption("📌").into(),
+        caption(tr!("pin-notice", author = author_name(state, notice))).into(),
+        link(
+            tr!("pin-notice-message"),
+            Message::JumpToMessage(pinned_id.to_string()),
+        ),
+        link(tr!("pin-notice-all"), Message::TogglePins),
+        caption(i18n::format_date(&notice.sent, "date-time-format"))
+            .color(Color::from(AppColorForeground::SubtextPrimary))
+            .into(),
+    ])
+    .spacing(6)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+pub async fn set_pin(
+    access_token: String,
+    channel_id: String,
+    message_id: String,
+    pinned: bool,
+) -> Result<(), String> {
+    let url = format!("{API_URL}/channels/{channel_id}/pins/{message_id}");
+    let client = reqwest::Client::new();
+    let request = if pinned {
+        client.put(url)
+    } else {
+        client.delete(url)
+    };
+    send_empty(request.bearer_auth(access_token)).await
+}
+
+/// Pins or unpins a message right away, and on the server for messages there. The notice
+/// for a pin is posted once the server has it, straight away for messages written here.
+pub fn set_pinned(
+    state: &mut State,
+    id: String,
+    pinned: bool,
+    access_token: &str,
+) -> Task<Message> {
+    let Some(message) = find_message(&mut state.messages, &id) else {
+        return Task::none();
+    };
+    let previous = message.pinned;
+    if previous.is_some() == pinned {
+        return Task::none();
+    }
+    message.pinned = pinned.then(Local::now);
+    let Some(channel_id) = message.channel_id.clone() else {
+        if pinned {
+            let user_id = state.user_id.clone();
+            post_pin_notice(state, user_id, id);
+        }
+        return Task::none();
+    };
+    Task::perform(
+        set_pin(access_token.to_string(), channel_id, id.clone(), pinned),
+        move |result| Message::PinSaved(id.clone(), previous, result),
+    )
+}
+
+/// Tells the channel, or the thread the message is in, that someone pinned the message with
+/// `pinned_id`.
+pub fn post_pin_notice(state: &mut State, author_id: Option<String>, pinned_id: String) {
+    state.local_messages += 1;
+    let mut notice = ChatMessage::local(
+        format!("local-{}", state.local_messages),
+        author_id,
+        String::new(),
+        None,
+    );
+    notice.thread_id = thread_of(state, &pinned_id).map(str::to_string);
+    notice.kind = MessageKind::Pinned(pinned_id);
+    state.messages.push(notice);
+}
+
+pub fn update(state: &mut State, message: Message) -> Task<Message> {
+    match message {
+        // Wasn't pinned before, so this was a pin and not an unpin.
+        Message::PinSaved(id, None, Ok(())) => {
+            let user_id = state.user_id.clone();
+            post_pin_notice(state, user_id, id);
+            Task::none()
+        }
+        Message::PinSaved(id, previous, Err(e)) => {
+            tracing::warn!(error = %e, message_id = %id, "Failed to pin or unpin message");
+            if let Some(message) = find_message(&mut state.messages, &id) {
+                message.pinned = previous;
+            }
+            let toast = Toast::new(ToastKind::Error, tr!("notify-pin-failed"));
+            Task::done(Message::Notify(toast))
+        }
+        _ => Task::none(),
+    }
+}
@@ -0,0 +1,430 @@
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorMain},
+    components::directional_row,
+    config::Settings,
+    i18n::tr,
+    notifications::{Toast, ToastKind},
+    styles::button_secondary_style,
+    threads::{AutoArchive, Thread},
+    typography::{TypeScale, caption, label, title},
+};
+use chrono::Local;
+use std::cmp::Reverse;
+
+use iced::{
+    Border, Color, Element,
+    Length::Fill,
+    Task,
+    widget::{
+        button, column, container, horizontal_space, pick_list, scrollable, text, text_input,
+    },
+};
+
+use super::{
+    Message, SidePanel, State,
+    attachments::load_thumbnails,
+    composer::composer,
+    embeds::request_previews,
+    messages::{ChatMessage, MessageKind, highlight_code, is_own, message_list},
+    panel_button, pill,
+};
+
+pub const THREAD_COMPOSER: &str = "thread-composer";
+pub const THREAD_CONVERSATION: &str = "thread-conversation";
+const SIDE_PANEL_WIDTH: f32 = 360.0;
+
+/// How many characters of the message a thread is started from go into its name.
+const THREAD_NAME_LENGTH: usize = 40;
+
+fn thread_messages<'a>(
+    state: &'a State,
+    thread_id: &'a str,
+) -> impl Iterator<Item = &'a ChatMessage> {
+    state
+        .messages
+        .iter()
+        .filter(move |message| message.thread_id.as_deref() == Some(thread_id))
+}
+
+/// The message the thread was started from followed by the thread's messages, as its pane
+/// shows them.
+pub fn thread_conversation<'a>(
+    state: &'a State,
+    thread: &'a Thread,
+) -> impl Iterator<Item = &'a ChatMessage> {
+    let parent = state
+        .messages
+        .iter()
+        .filter(|message| message.id == thread.parent_id);
+    parent.chain(thread_messages(state, &thread.id))
+}
+
+/// The thread a message was posted in, `None` for the channel or a message that's gone.
+pub fn thread_of<'a>(state: &'a State, message_id: &str) -> Option<&'a str> {
+    let message = state
+        .messages
+        .iter()
+        .find(|message| message.id == message_id)?;
+    message.thread_id.as_deref()
+}
+
+pub fn is_open(state: &State, thread_id: &str) -> bool {
+    matches!(&state.side_panel, Some(SidePanel::Thread(open)) if open == thread_id)
+}
+
+/// The thread list or the open thread, next to the conversation.
+pub fn side_panel<'a>(state: &'a State, settings: &'a Settings) -> Option<Element<'a, Message>> {
+    let content = match state.side_panel.as_ref()? {
+        SidePanel::Threads => thread_list(state),
+        SidePanel::Thread(id) => {
+            let thread = state.threads.iter().find(|thread| thread.id == *id)?;
+            thread_pane(state, settings, thread)
+        }
+    };
+    Some(
+        container(content)
+            .width(SIDE_PANEL_WIDTH)
+            .height(Fill)
+            .padding(settings.density.message_padding())
+            .style(|_t| container::Style {
+                background: Some(AppColorBackground::Secondary.to_bg()),
+                ..Default::default()
+            })
+            .into(),
+    )
+}
+
+/// The channel's threads, most recently active first, the archived ones after the others.
+fn thread_list(state: &State) -> Element<'_, Message> {
+    let subtext = Color::from(AppColorForeground::SubtextPrimary);
+    let header = directional_row([
+        title(tr!("threads-title")).into(),
+        horizontal_space().into(),
+        panel_button("×", Message::CloseSidePanel),
+    ])
+    .spacing(8)
+    .align_y(iced::Alignment::Center);
+
+    if state.threads.is_empty() {
+        let empty = caption(tr!("threads-empty")).color(subtext);
+        return column![header, empty].spacing(16).into();
+    }
+
+    let mut threads: Vec<&Thread> = state.threads.iter().collect();
+    threads.sort_by_key(|thread| Reverse(thread.last_activity));
+    let (archived, active): (Vec<&Thread>, Vec<&Thread>) =
+        threads.into_iter().partition(|thread| thread.archived);
+    let sections = [
+        (tr!("threads-active"), active),
+        (tr!("threads-archived"), archived),
+    ]
+    .into_iter()
+    .filter(|(_, threads)| !threads.is_empty())
+    .map(|(name, threads)| -> Element<'_, Message> {
+        let rows = threads.into_iter().map(|thread| thread_row(state, thread));
+        column![caption(name).color(subtext)]
+            .extend(rows)
+            .spacing(4)
+            .into()
+    });
+
+    column![header, scrollable(column(sections).spacing(16))]
+        .spacing(16)
+        .into()
+}
+
+/// A thread in the thread list, opened by clicking it.
+fn thread_row<'a>(state: &'a State, thread: &'a Thread) -> Element<'a, Message> {
+    let count = thread_messages(state, &thread.id).count();
+    let summary = column![
+        label(format!("🧵 {}", thread.name)),
+        caption(tr!("thread-messages", count = count))
+            .color(Color::from(AppColorForeground::SubtextPrimary)),
+    ]
+    .spacing(2);
+    let mut content: Vec<Element<'a, Message>> = vec![summary.into(), horizontal_space().into()];
+    content.extend(pill(thread.unread));
+
+    button(
+        directional_row(content)
+            .spacing(8)
+            .align_y(iced::Alignment::Center),
+    )
+    .width(Fill)
+    .padding([6, 10])
+    .on_press(Message::OpenThread(thread.id.clone()))
+    .style(|_t, status| {
+        let background = match status {
+            button::Status::Hovered => AppColorBackground::SurfaceSecondary,
+            _ => AppColorBackground::SurfacePrimary,
+        };
+        button::Style {
+            background: Some(background.to_bg()),
+            text_color: AppColorForeground::Primary.into(),
+            border: Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    })
+    .into()
+}
+
+/// An open thread: the message it was started from and the thread's messages, with a
+/// composer of its own and when the thread archives itself.
+fn thread_pane<'a>(
+    state: &'a State,
+    settings: &'a Settings,
+    thread: &'a Thread,
+) -> Element<'a, Message> {
+    let archive = if thread.archived {
+        tr!("thread-unarchive")
+    } else {
+        tr!("thread-archive")
+    };
+    let header = directional_row([
+        title(format!("🧵 {}", thread.name)).into(),
+        horizontal_space().into(),
+        button(text(archive).size(TypeScale::Caption.size()))
+            .on_press(Message::ThreadArchiveToggled(thread.id.clone()))
+            .padding([2, 8])
+            .style(button_secondary_style)
+            .into(),
+        panel_button("≡", Message::ToggleThreadList),
+        panel_button("×", Message::CloseSidePanel),
+    ])
+    .spacing(8)
+    .align_y(iced::Alignment::Center);
+
+    let id = thread.id.clone();
+    let auto_archive = directional_row([
+        caption(tr!("thread-auto-archive")).into(),
+        pick_list(AutoArchive::ALL, Some(thread.auto_archive), move |after| {
+            Message::AutoArchiveSelected(id.clone(), after)
+        })
+        .text_size(TypeScale::Caption.size())
+        .padding([4, 8])
+        .into(),
+    ])
+    .spacing(8)
+    .align_y(iced::Alignment::Center);
+    let archived = thread.archived.then(|| {
+        caption(tr!("thread-archived-notice"))
+            .color(Color::from(AppColorForeground::SubtextPrimary))
+    });
+
+    let messages = thread_conversation(state, thread);
+
+    column![header, auto_archive]
+        .push_maybe(archived)
+        .push(message_list(state, settings, messages, THREAD_CONVERSATION))
+        .push(composer(state, settings, Some(thread)))
+        .spacing(settings.density.message_spacing())
+        .into()
+}
+
+/// Under the message a thread was started from, opens the thread. Left out while the thread
+/// is open.
+pub fn thread_link<'a>(state: &'a State, thread: &'a Thread) -> Element<'a, Message> {
+    let count = thread_messages(state, &thread.id).count();
+    let link = button(caption(format!("🧵 {}", thread.name)))
+        .padding(0)
+        .on_press(Message::OpenThread(thread.id.clone()))
+        .style(|_t, _s| button::Style {
+            text_color: AppColorMain::AccentPrimary.into(),
+            ..Default::default()
+        });
+    let mut content: Vec<Element<'a, Message>> = vec![
+        link.into(),
+        caption(tr!("thread-messages", count = count))
+            .color(Color::from(AppColorForeground::SubtextPrimary))
+            .into(),
+    ];
+    content.extend(pill(thread.unread));
+    directional_row(content)
+        .spacing(6)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+/// Opens the thread started from a message, starting one first if there's none yet. Threads
+/// are named after the start of the message.
+pub fn start_thread(
+    state: &mut State,
+    parent_id: String,
+    access_token: &str,
+    settings: &Settings,
+) -> Task<Message> {
+    if let Some(thread) = state
+        .threads
+        .iter()
+        .find(|thread| thread.parent_id == parent_id)
+    {
+        return Task::done(Message::OpenThread(thread.id.clone()));
+    }
+    let Some(parent) = state
+        .messages
+        .iter()
+        .filter(|message| message.thread_id.is_none() && message.kind == MessageKind::Normal)
+        .find(|message| message.id == parent_id)
+    else {
+        return Task::none();
+    };
+    let name = Some(parent.excerpt(THREAD_NAME_LENGTH))
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| tr!("thread-untitled"));
+    let channel_id = parent.channel_id.clone();
+    let auto_archive = settings.thread_auto_archive;
+
+    let Some(channel_id) = channel_id else {
+        // A message that isn't on the server gets a thread that isn't either, made up like it.
+        state.local_messages += 1;
+        let id = format!("local-{}", state.local_messages);
+        let thread = Thread::new(id.clone(), None, parent_id, name, auto_archive);
+        state.threads.push(thread);
+        return Task::done(Message::OpenThread(id));
+    };
+    let thread = Thread::new(
+        String::new(),
+        Some(channel_id.clone()),
+        parent_id.clone(),
+        name.clone(),
+        auto_archive,
+    );
+    Task::perform(
+        crate::threads::start_thread(
+            access_token.to_string(),
+            channel_id,
+            parent_id,
+            name,
+            auto_archive,
+        ),
+        move |result| Message::ThreadStarted(Box::new(thread.clone()), result),
+    )
+}
+
+/// Shows a thread in the side panel, which reads its messages.
+pub fn open_thread(state: &mut State, id: &str) {
+    if let Some(thread) = state.threads.iter_mut().find(|thread| thread.id == id) {
+        thread.unread = 0;
+        state.side_panel = Some(SidePanel::Thread(id.to_string()));
+    }
+}
+
+/// Adds a message to its thread, bringing the thread back if it was archived. It's unread
+/// unless the thread is open or the user wrote it.
+pub fn post_in_thread(
+    state: &mut State,
+    message: ChatMessage,
+    settings: &Settings,
+) -> Task<Message> {
+    let Some(thread_id) = message.thread_id.as_deref() else {
+        return Task::none();
+    };
+    let unread = !is_open(state, thread_id) && !is_own(state, &message);
+    let Some(thread) = state
+        .threads
+        .iter_mut()
+        .find(|thread| thread.id == thread_id)
+    else {
+        return Task::none();
+    };
+    thread.archived = false;
+    thread.last_activity = message.sent;
+    if unread {
+        thread.unread += 1;
+    }
+    let highlight = highlight_code(&mut state.highlights, &message.document);
+    let thumbnails = load_thumbnails(&mut state.media, &message);
+    let previews = request_previews(&mut state.previews, settings, &message);
+    state.messages.push(message);
+    Task::batch([highlight, thumbnails, previews])
+}
+
+/// Saves whether a thread is archived and when it archives itself, for threads on the server.
+pub fn save_thread(thread: &Thread, access_token: &str) -> Task<Message> {
+    if thread.channel_id.is_none() {
+        return Task::none();
+    }
+    let id = thread.id.clone();
+    Task::perform(
+        crate::threads::save_thread(
+            access_token.to_string(),
+            thread.id.clone(),
+            thread.archived,
+            thread.auto_archive,
+        ),
+        move |result| Message::ThreadSaved(id.clone(), result),
+    )
+}
+
+pub fn update(state: &mut State, message: Message, access_token: &str) -> Task<Message> {
+    match message {
+        Message::ThreadStarted(thread, Ok(id)) => {
+            state.threads.push(Thread {
+                id: id.clone(),
+                ..*thread
+            });
+            open_thread(state, &id);
+            text_input::focus(THREAD_COMPOSER)
+        }
+        Message::ThreadStarted(thread, Err(e)) => {
+            tracing::warn!(error = %e, parent_id = %thread.parent_id, "Failed to start thread");
+            let toast = Toast::new(ToastKind::Error, tr!("notify-thread-failed"));
+            Task::done(Message::Notify(toast))
+        }
+        Message::ToggleThreadList => {
+            state.side_panel = match state.side_panel {
+                Some(SidePanel::Threads) => None,
+                _ => Some(SidePanel::Threads),
+            };
+            Task::none()
+        }
+        Message::OpenThread(id) => {
+            open_thread(state, &id);
+            text_input::focus(THREAD_COMPOSER)
+        }
+        Message::CloseSidePanel => {
+            state.side_panel = None;
+            Task::none()
+        }
+        Message::ThreadArchiveToggled(id) => {
+            let Some(thread) = state.threads.iter_mut().find(|thread| thread.id == id) else {
+                return Task::none();
+            };
+            thread.archived = !thread.archived;
+            // A thread brought back starts its wait to be archived again from now.
+            if !thread.archived {
+                thread.last_activity = Local::now();
+            }
+            save_thread(thread, access_token)
+        }
+        Message::AutoArchiveSelected(id, after) => {
+            let Some(thread) = state.threads.iter_mut().find(|thread| thread.id == id) else {
+                return Task::none();
+            };
+            thread.auto_archive = after;
+            Task::batch([
+                save_thread(thread, access_token),
+                Task::done(Message::AutoArchiveChanged(after)),
+            ])
+        }
+        Message::ThreadSaved(id, Err(e)) => {
+            tracing::warn!(error = %e, thread_id = %id, "Failed to save thread");
+            let toast = Toast::new(ToastKind::Error, tr!("notify-thread-failed"));
+            Task::done(Message::Notify(toast))
+        }
+        // The server archives its threads by itself, this keeps what's shown in step.
+        Message::ArchiveIdleThreads => {
+            let now = Local::now();
+            for thread in &mut state.threads {
+                if thread.is_idle(now) {
+                    thread.archived = true;
+                }
+            }
+            Task::none()
+        }
+        _ => Task::none(),
+    }
+}
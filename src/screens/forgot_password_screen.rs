@@ -0,0 +1,340 @@
+use crate::{
+    colors::{AppColorForeground, AppColorMain},
+    components::{password_score, password_strength, styled_input},
+    constants::{API_URL, FORM_WIDTH},
+    i18n::{self, tr},
+    styles::{button_style, container_style},
+    typography::{body, caption, heading},
+    utils::{Redacted, send_empty},
+};
+use std::collections::HashMap;
+
+use iced::{
+    Color, Element,
+    Length::Fill,
+    Task,
+    widget::{button, column, container, row},
+};
+use validator::{Validate, ValidateEmail};
+
+#[derive(Debug, Clone, Default, Validate)]
+pub struct State {
+    pub step: Step,
+    pub email: String,
+    pub code: Redacted<String>,
+    #[validate(length(min = 1, max = 72, code = "password-length"))]
+    pub password: Redacted<String>,
+    #[validate(length(min = 1, max = 72, code = "password-length"))]
+    pub password_confirm: Redacted<String>,
+    pub is_loading: bool,
+    pub password_score: Option<u8>,
+    pub email_error: Option<String>,
+    pub code_error: Option<String>,
+    pub password_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Step {
+    #[default]
+    RequestEmail,
+    /// The email was sent, the user now pastes the code from it and picks a new password.
+    Reset,
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self {
+            step: Step::RequestEmail,
+            email: String::new(),
+            code: Redacted::default(),
+            password: Redacted::default(),
+            password_confirm: Redacted::default(),
+            is_loading: false,
+            password_score: None,
+            email_error: None,
+            code_error: None,
+            password_error: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    EmailInputChanged(String),
+    CodeInputChanged(String),
+    PasswordInputChanged(String),
+    PasswordConfirmInputChanged(String),
+    SendEmailPressed,
+    EmailSent(Result<(), String>),
+    ResetPasswordPressed,
+    PasswordReset(Result<(), String>),
+    ResendEmail,
+    LoginInstead,
+    RequestScreenChange(Box<crate::CurrentScreen>),
+}
+
+pub fn view(state: &State) -> Element<'_, Message> {
+    let content = match state.step {
+        Step::RequestEmail => request_view(state),
+        Step::Reset => reset_view(state),
+    };
+
+    let login_link =
+        button(caption(tr!("back-to-login")).color(Color::from(AppColorMain::Secondary)))
+            .on_press(Message::LoginInstead)
+            .style(button::text)
+            .height(14)
+            .padding(0);
+
+    container(
+        column![content, row![login_link]]
+            .width(FORM_WIDTH)
+            .padding(24)
+            .spacing(8),
+    )
+    .style(container_style)
+    .into()
+}
+
+fn submit_button<'a>(
+    label: String,
+    loading_label: String,
+    state: &State,
+    message: Message,
+) -> Element<'a, Message> {
+    button(
+        body(if state.is_loading {
+            loading_label
+        } else {
+            label
+        })
+        .width(Fill)
+        .center(),
+    )
+    .on_press_maybe((!state.is_loading).then_some(message))
+    .height(44)
+    .width(Fill)
+    .style(button_style)
+    .padding(10)
+    .into()
+}
+
+fn request_view(state: &State) -> Element<'_, Message> {
+    column![
+        column![
+            heading(tr!("forgot-title")).width(Fill).center(),
+            caption(tr!("forgot-hint"))
+                .width(Fill)
+                .center()
+                .color(Color::from(AppColorForeground::SubtextPrimary)),
+        ]
+        .spacing(8),
+        styled_input(
+            &tr!("forgot-email"),
+            &state.email,
+            state.email_error.as_deref(),
+            Message::EmailInputChanged,
+            None,
+            Some(true),
+            None,
+        ),
+        submit_button(
+            tr!("forgot-submit"),
+            tr!("forgot-submit-busy"),
+            state,
+            Message::SendEmailPressed,
+        ),
+    ]
+    .spacing(24)
+    .into()
+}
+
+fn reset_view(state: &State) -> Element<'_, Message> {
+    let resend_link =
+        button(caption(tr!("reset-resend")).color(Color::from(AppColorMain::Secondary)))
+            .on_press_maybe((!state.is_loading).then_some(Message::ResendEmail))
+            .style(button::text)
+            .height(14)
+            .padding(0);
+
+    column![
+        column![
+            heading(tr!("reset-title")).width(Fill).center(),
+            caption(tr!("reset-hint", email = state.email.as_str()))
+                .width(Fill)
+                .center()
+                .color(Color::from(AppColorForeground::SubtextPrimary)),
+        ]
+        .spacing(8),
+        column![
+            styled_input(
+                &tr!("reset-code"),
+                state.code.expose(),
+                state.code_error.as_deref(),
+                Message::CodeInputChanged,
+                None,
+                Some(true),
+                None,
+            ),
+            row![
+                caption(tr!("reset-no-email"))
+                    .color(Color::from(AppColorForeground::SubtextPrimary)),
+                resend_link
+            ],
+        ]
+        .spacing(8),
+        column![
+            styled_input(
+                &tr!("reset-password"),
+                state.password.expose(),
+                state.password_error.as_deref(),
+                Message::PasswordInputChanged,
+                Some(true),
+                Some(true),
+                None,
+            ),
+            password_strength(state.password_score),
+        ]
+        .spacing(8),
+        styled_input(
+            &tr!("reset-password-confirm"),
+            state.password_confirm.expose(),
+            state.password_error.as_deref(),
+            Message::PasswordConfirmInputChanged,
+            Some(true),
+            Some(true),
+            None,
+        ),
+        submit_button(
+            tr!("reset-submit"),
+            tr!("reset-submit-busy"),
+            state,
+            Message::ResetPasswordPressed,
+        ),
+    ]
+    .spacing(24)
+    .into()
+}
+
+pub async fn request_password_reset(email: String) -> Result<(), String> {
+    tracing::info!("Requesting a password reset email");
+
+    let mut map = HashMap::new();
+    map.insert("email", email);
+
+    let client = reqwest::Client::new();
+    send_empty(
+        client
+            .post(format!("{API_URL}/auth/password/forgot"))
+            .json(&map),
+    )
+    .await
+}
+
+pub async fn reset_password(email: String, code: String, password: String) -> Result<(), String> {
+    tracing::info!("Attempting to reset password with an emailed code");
+
+    let mut map = HashMap::new();
+    map.insert("email", email);
+    map.insert("code", code);
+    map.insert("password", password);
+
+    let client = reqwest::Client::new();
+    send_empty(
+        client
+            .post(format!("{API_URL}/auth/password/reset"))
+            .json(&map),
+    )
+    .await
+}
+
+pub fn update(state: &mut State, message: Message) -> Task<Message> {
+    match message {
+        Message::EmailInputChanged(value) => {
+            state.email = value;
+            state.email_error = None;
+            Task::none()
+        }
+        Message::CodeInputChanged(value) => {
+            state.code = Redacted::new(value);
+            state.code_error = None;
+            Task::none()
+        }
+        Message::PasswordInputChanged(value) => {
+            state.password_score = password_score(&value);
+            state.password = Redacted::new(value);
+            state.password_error = None;
+            Task::none()
+        }
+        Message::PasswordConfirmInputChanged(value) => {
+            state.password_confirm = Redacted::new(value);
+            state.password_error = None;
+            Task::none()
+        }
+        Message::SendEmailPressed | Message::ResendEmail => {
+            let email = state.email.trim().to_string();
+            if email.is_empty() || !email.validate_email() {
+                state.email_error = Some(tr!("error-invalid-email"));
+                return Task::none();
+            }
+            state.is_loading = true;
+            Task::perform(request_password_reset(email), Message::EmailSent)
+        }
+        Message::EmailSent(result) => {
+            state.is_loading = false;
+            match result {
+                Ok(()) => {
+                    state.step = Step::Reset;
+                    state.code_error = None;
+                }
+                Err(e) if state.step == Step::Reset => state.code_error = Some(e),
+                Err(e) => state.email_error = Some(e),
+            }
+            Task::none()
+        }
+        Message::ResetPasswordPressed => {
+            if state.code.expose().trim().is_empty() {
+                state.code_error = Some(tr!("field-required"));
+                return Task::none();
+            }
+            if let Err(e) = state.validate() {
+                state.password_error = i18n::field_error(&e, "password");
+                return Task::none();
+            }
+            if state.password != state.password_confirm {
+                state.password_error = Some(tr!("error-passwords-differ"));
+                return Task::none();
+            }
+            state.is_loading = true;
+            Task::perform(
+                reset_password(
+                    state.email.trim().to_string(),
+                    state.code.expose().trim().to_string(),
+                    state.password.expose().clone(),
+                ),
+                Message::PasswordReset,
+            )
+        }
+        Message::PasswordReset(result) => {
+            state.is_loading = false;
+            match result {
+                Ok(()) => Task::done(Message::RequestScreenChange(Box::new(
+                    crate::CurrentScreen::Login(crate::login_screen::State::with_notice(&tr!(
+                        "reset-done"
+                    ))),
+                ))),
+                Err(e) => {
+                    state.code_error = Some(e);
+                    Task::none()
+                }
+            }
+        }
+        Message::LoginInstead => {
+            state.is_loading = false;
+            Task::done(Message::RequestScreenChange(Box::new(
+                crate::CurrentScreen::Login(crate::login_screen::State::new()),
+            )))
+        }
+        _ => Task::none(),
+    }
+}
@@ -1 +1,3 @@
 pub mod app_screen;
+pub mod forgot_password_screen;
+pub mod settings_screen;
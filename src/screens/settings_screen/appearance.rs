@@ -0,0 +1,237 @@
+use crate::{
+    colors::AppColorForeground,
+    config::{Appearance, Density, MAX_UI_SCALE, MIN_UI_SCALE, Settings, UI_SCALE_STEP},
+    i18n::tr,
+    theme::{ColorScheme, ThemeEntry, themes_dir},
+    typography::{FontChoice, MAX_BASE_SIZE, MIN_BASE_SIZE, caption, label, title},
+};
+
+use iced::{
+    Color, Element,
+    Length::Fill,
+    widget::{checkbox, column, pick_list, row, slider},
+};
+
+// `MessageFontSelected` is about the font of chat messages.
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone)]
+pub enum Message {
+    AppearanceSelected(Appearance),
+    LightThemeSelected(ThemeEntry),
+    DarkThemeSelected(ThemeEntry),
+    SystemFallbackSelected(ColorScheme),
+    UiFontSelected(FontChoice),
+    MessageFontSelected(FontChoice),
+    MonoFontSelected(FontChoice),
+    UiScaleChanged(f64),
+    BaseFontSizeChanged(u16),
+    DensitySelected(Density),
+    ReducedMotionToggled(bool),
+    HighContrastToggled(bool),
+}
+
+fn hint<'a>(content: String) -> Element<'a, Message> {
+    caption(content)
+        .color(Color::from(AppColorForeground::SubtextPrimary))
+        .into()
+}
+
+fn theme_picker<'a>(
+    name: String,
+    themes: &'a [ThemeEntry],
+    scheme: ColorScheme,
+    selected_id: &str,
+    on_select: fn(ThemeEntry) -> Message,
+) -> Element<'a, Message> {
+    let dark = scheme == ColorScheme::Dark;
+    let options: Vec<ThemeEntry> = themes
+        .iter()
+        .filter(|entry| entry.palette.dark == dark || entry.id == selected_id)
+        .cloned()
+        .collect();
+    let selected = options
+        .iter()
+        .find(|entry| entry.id == selected_id)
+        .cloned();
+
+    column![
+        label(name),
+        pick_list(options, selected, on_select).padding([8, 12]),
+    ]
+    .spacing(8)
+    .into()
+}
+
+fn font_picker<'a>(
+    name: String,
+    choices: &'static [FontChoice],
+    selected: FontChoice,
+    on_select: fn(FontChoice) -> Message,
+) -> Element<'a, Message> {
+    column![
+        label(name),
+        pick_list(choices, Some(selected), on_select).padding([8, 12]),
+    ]
+    .spacing(8)
+    .width(Fill)
+    .into()
+}
+
+fn accessibility(settings: &Settings) -> Element<'_, Message> {
+    column![
+        title(tr!("appearance-accessibility")),
+        column![
+            label(tr!(
+                "appearance-zoom",
+                percent = format!("{:.0}", settings.scale_factor() * 100.0)
+            )),
+            slider(
+                MIN_UI_SCALE..=MAX_UI_SCALE,
+                settings.scale_factor(),
+                Message::UiScaleChanged
+            )
+            .step(UI_SCALE_STEP),
+            hint(tr!("appearance-zoom-hint")),
+        ]
+        .spacing(8),
+        column![
+            label(tr!(
+                "appearance-font-size",
+                size = settings.typography().base_size
+            )),
+            slider(
+                MIN_BASE_SIZE..=MAX_BASE_SIZE,
+                settings.typography().base_size,
+                Message::BaseFontSizeChanged
+            ),
+        ]
+        .spacing(8),
+        column![
+            label(tr!("appearance-density")),
+            pick_list(
+                Density::ALL,
+                Some(settings.density),
+                Message::DensitySelected
+            )
+            .padding([8, 12]),
+        ]
+        .spacing(8),
+        column![
+            checkbox(tr!("appearance-reduced-motion"), settings.reduced_motion)
+                .on_toggle(Message::ReducedMotionToggled),
+            hint(tr!("appearance-reduced-motion-hint")),
+        ]
+        .spacing(8),
+        column![
+            checkbox(tr!("appearance-high-contrast"), settings.high_contrast)
+                .on_toggle(Message::HighContrastToggled),
+            hint(tr!("appearance-high-contrast-hint")),
+        ]
+        .spacing(8),
+    ]
+    .spacing(16)
+    .into()
+}
+
+pub fn view<'a>(
+    settings: &'a Settings,
+    themes: &'a [ThemeEntry],
+    system_scheme: Option<ColorScheme>,
+) -> Element<'a, Message> {
+    let system_status = match settings.appearance {
+        Appearance::System => Some(match system_scheme {
+            Some(scheme) => hint(tr!("appearance-system-scheme", scheme = scheme.to_string())),
+            None => column![
+                hint(tr!("appearance-no-system-scheme")),
+                pick_list(
+                    ColorScheme::ALL,
+                    Some(settings.system_fallback),
+                    Message::SystemFallbackSelected,
+                )
+                .padding([8, 12]),
+            ]
+            .spacing(8)
+            .into(),
+        }),
+        _ => None,
+    };
+    let themes_hint = match themes_dir() {
+        Some(dir) => tr!("appearance-themes-hint", dir = dir.display().to_string()),
+        None => String::new(),
+    };
+
+    column![
+        title(tr!("appearance-title")),
+        column![
+            label(tr!("appearance-mode")),
+            pick_list(
+                Appearance::ALL,
+                Some(settings.appearance),
+                Message::AppearanceSelected
+            )
+            .padding([8, 12]),
+        ]
+        .spacing(8)
+        .push_maybe(system_status),
+        theme_picker(
+            tr!("appearance-light-theme"),
+            themes,
+            ColorScheme::Light,
+            &settings.light_theme,
+            Message::LightThemeSelected,
+        ),
+        theme_picker(
+            tr!("appearance-dark-theme"),
+            themes,
+            ColorScheme::Dark,
+            &settings.dark_theme,
+            Message::DarkThemeSelected,
+        ),
+        row![
+            font_picker(
+                tr!("appearance-ui-font"),
+                &FontChoice::PROPORTIONAL,
+                settings.ui_font,
+                Message::UiFontSelected,
+            ),
+            font_picker(
+                tr!("appearance-message-font"),
+                &FontChoice::PROPORTIONAL,
+                settings.message_font,
+                Message::MessageFontSelected,
+            ),
+            font_picker(
+                tr!("appearance-code-font"),
+                &FontChoice::MONOSPACE,
+                settings.mono_font,
+                Message::MonoFontSelected,
+            ),
+        ]
+        .spacing(16),
+        accessibility(settings),
+        column![
+            hint(tr!("appearance-saved-hint")),
+            caption(themes_hint).color(Color::from(AppColorForeground::SubtextTertiary)),
+        ]
+        .spacing(8),
+    ]
+    .spacing(16)
+    .into()
+}
+
+pub fn update(settings: &mut Settings, message: Message) {
+    match message {
+        Message::AppearanceSelected(appearance) => settings.appearance = appearance,
+        Message::LightThemeSelected(entry) => settings.light_theme = entry.id,
+        Message::DarkThemeSelected(entry) => settings.dark_theme = entry.id,
+        Message::SystemFallbackSelected(scheme) => settings.system_fallback = scheme,
+        Message::UiFontSelected(font) => settings.ui_font = font,
+        Message::MessageFontSelected(font) => settings.message_font = font,
+        Message::MonoFontSelected(font) => settings.mono_font = font,
+        Message::UiScaleChanged(scale) => settings.ui_scale = (scale * 10.0).round() / 10.0,
+        Message::BaseFontSizeChanged(size) => settings.base_font_size = size,
+        Message::DensitySelected(density) => settings.density = density,
+        Message::ReducedMotionToggled(enabled) => settings.reduced_motion = enabled,
+        Message::HighContrastToggled(enabled) => settings.high_contrast = enabled,
+    }
+}
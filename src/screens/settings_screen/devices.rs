@@ -0,0 +1,211 @@
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorMain, AppColorStatus},
+    constants::{API_URL, BORDER_RADIUS},
+    i18n::{self, tr},
+    styles::button_danger_style,
+    typography::{body, caption, title},
+    utils::{send_empty, send_json},
+};
+use chrono::{DateTime, Local, Utc};
+use serde::Deserialize;
+
+use iced::{
+    Border, Color, Element,
+    Length::Fill,
+    Task,
+    widget::{button, column, container, horizontal_space, row},
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    pub sessions: Option<Vec<DeviceSession>>,
+    pub revoking: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceSession {
+    pub id: String,
+    pub device_name: String,
+    pub platform: String,
+    pub location: Option<String>,
+    pub last_active: DateTime<Utc>,
+    /// Set by the server on the session the request was made with.
+    pub current: bool,
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Load,
+    SessionsLoaded(Result<Vec<DeviceSession>, String>),
+    RevokePressed(String),
+    SessionRevoked(String, Result<(), String>),
+}
+
+fn format_last_active(last_active: DateTime<Utc>) -> String {
+    if Utc::now().signed_duration_since(last_active).num_minutes() < 5 {
+        return tr!("devices-active-now");
+    }
+    let date = i18n::format_date(&last_active.with_timezone(&Local), "date-time-format");
+    tr!("devices-last-active", date = date)
+}
+
+fn session_row<'a>(state: &'a State, session: &'a DeviceSession) -> Element<'a, Message> {
+    let details = [Some(session.platform.as_str()), session.location.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+    let info = column![
+        row![body(&session.device_name)]
+            .push_maybe(session.current.then(|| {
+                caption(tr!("devices-this-device")).color(Color::from(AppColorMain::Secondary))
+            }))
+            .spacing(8),
+        caption(details).color(Color::from(AppColorForeground::SubtextPrimary)),
+        caption(format_last_active(session.last_active))
+            .color(Color::from(AppColorForeground::SubtextTertiary)),
+    ]
+    .spacing(4);
+
+    let is_revoking = state.revoking.as_deref() == Some(session.id.as_str());
+    let action: Element<Message> = if session.current {
+        column![].into()
+    } else {
+        button(caption(if is_revoking {
+            tr!("devices-logging-out")
+        } else {
+            tr!("devices-log-out")
+        }))
+        .on_press_maybe(
+            state
+                .revoking
+                .is_none()
+                .then(|| Message::RevokePressed(session.id.clone())),
+        )
+        .style(button_danger_style)
+        .padding([6, 12])
+        .into()
+    };
+
+    let is_current = session.current;
+    container(
+        row![info, horizontal_space(), action]
+            .spacing(12)
+            .align_y(iced::Alignment::Center),
+    )
+    .width(Fill)
+    .padding(12)
+    .style(move |_t| container::Style {
+        background: Some(AppColorBackground::Secondary.to_bg()),
+        border: Border {
+            radius: BORDER_RADIUS.into(),
+            width: if is_current { 1.0 } else { 0.0 },
+            color: Color::from(AppColorMain::Secondary),
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+pub fn view(state: &State) -> Element<'_, Message> {
+    let mut content = column![
+        title(tr!("devices-title")),
+        caption(tr!("devices-hint")).color(Color::from(AppColorForeground::SubtextPrimary)),
+    ]
+    .spacing(16);
+
+    if let Some(error) = &state.error {
+        content = content.push(caption(error).color(Color::from(AppColorStatus::Failure)));
+    }
+
+    match &state.sessions {
+        None => content
+            .push(
+                caption(tr!("devices-loading"))
+                    .color(Color::from(AppColorForeground::SubtextPrimary)),
+            )
+            .into(),
+        Some(sessions) => content
+            .push(
+                caption(tr!("devices-count", count = sessions.len()))
+                    .color(Color::from(AppColorForeground::SubtextTertiary)),
+            )
+            .push(column(sessions.iter().map(|session| session_row(state, session))).spacing(8))
+            .into(),
+    }
+}
+
+pub async fn fetch_sessions(access_token: String) -> Result<Vec<DeviceSession>, String> {
+    let client = reqwest::Client::new();
+    send_json(
+        client
+            .get(format!("{API_URL}/auth/sessions"))
+            .bearer_auth(access_token),
+    )
+    .await
+}
+
+pub async fn revoke_session(access_token: String, id: String) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    send_empty(
+        client
+            .delete(format!("{API_URL}/auth/sessions/{id}"))
+            .bearer_auth(access_token),
+    )
+    .await
+}
+
+pub fn update(state: &mut State, message: Message, access_token: &str) -> Task<Message> {
+    match message {
+        Message::Load => {
+            state.error = None;
+            Task::perform(
+                fetch_sessions(access_token.to_string()),
+                Message::SessionsLoaded,
+            )
+        }
+        Message::SessionsLoaded(result) => {
+            match result {
+                Ok(mut sessions) => {
+                    // Current session first, then most recently active.
+                    sessions.sort_by(|a, b| {
+                        b.current
+                            .cmp(&a.current)
+                            .then(b.last_active.cmp(&a.last_active))
+                    });
+                    state.sessions = Some(sessions);
+                }
+                Err(e) => state.error = Some(e),
+            }
+            Task::none()
+        }
+        Message::RevokePressed(id) => {
+            state.revoking = Some(id.clone());
+            state.error = None;
+            Task::perform(
+                revoke_session(access_token.to_string(), id.clone()),
+                move |result| Message::SessionRevoked(id.clone(), result),
+            )
+        }
+        Message::SessionRevoked(id, result) => {
+            state.revoking = None;
+            match result {
+                Ok(()) => {
+                    if let Some(sessions) = &mut state.sessions {
+                        sessions.retain(|session| session.id != id);
+                    }
+                }
+                Err(e) => state.error = Some(e),
+            }
+            Task::none()
+        }
+    }
+}
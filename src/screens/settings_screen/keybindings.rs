@@ -0,0 +1,258 @@
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorStatus},
+    config::Settings,
+    constants::BORDER_RADIUS_SMALL,
+    i18n::tr,
+    keybindings::{Action, KeyBinding, Keybindings},
+    styles::{button_secondary_style, button_style},
+    typography::{body, caption, code, title},
+};
+
+use iced::{
+    Border, Color, Element,
+    Length::Fill,
+    widget::{button, column, container, horizontal_space, row},
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    /// The action waiting for the next key press, which `App` sends here instead of
+    /// treating it as a shortcut.
+    pub recording: Option<Action>,
+    pub pending: Option<Conflict>,
+    pub error: Option<String>,
+}
+
+/// A recorded key that is already bound to another action, waiting for the user to decide.
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub action: Action,
+    pub binding: KeyBinding,
+    pub bound_to: Action,
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    RecordPressed(Action),
+    CancelRecording,
+    KeyCaptured(KeyBinding),
+    ReplaceConfirmed,
+    RemovePressed(Action, KeyBinding),
+    ResetPressed(Action),
+    ResetAllPressed,
+}
+
+fn key_pill(action: Action, binding: &KeyBinding) -> Element<'_, Message> {
+    button(row![code(binding.to_string()), caption("×")].spacing(6))
+        .on_press(Message::RemovePressed(action, binding.clone()))
+        .padding([2, 8])
+        .style(|_t, status| button::Style {
+            background: Some(match status {
+                button::Status::Hovered => AppColorBackground::SurfaceSecondary.to_bg(),
+                _ => AppColorBackground::SurfacePrimary.to_bg(),
+            }),
+            text_color: AppColorForeground::Primary.into(),
+            border: Border {
+                radius: BORDER_RADIUS_SMALL.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+fn action_row<'a>(
+    state: &'a State,
+    keybindings: &'a Keybindings,
+    action: Action,
+) -> Element<'a, Message> {
+    let conflicts = keybindings.conflicts(action);
+    let info = column![body(action.label())]
+        .push_maybe((!conflicts.is_empty()).then(|| {
+            let names: Vec<String> = conflicts.iter().map(|other| other.label()).collect();
+            caption(tr!("keybindings-shares-key", actions = names.join(", ")))
+                .color(Color::from(AppColorStatus::Warning))
+        }))
+        .spacing(4);
+
+    let keys = keybindings
+        .bindings(action)
+        .iter()
+        .fold(row![].spacing(6), |keys, binding| {
+            keys.push(key_pill(action, binding))
+        });
+    let keys: Element<Message> = if keybindings.bindings(action).is_empty() {
+        caption(tr!("keybindings-not-bound"))
+            .color(Color::from(AppColorForeground::SubtextTertiary))
+            .into()
+    } else {
+        keys.into()
+    };
+
+    let is_recording = state.recording == Some(action);
+    let record = if is_recording {
+        button(caption(tr!("keybindings-press-key")))
+            .on_press(Message::CancelRecording)
+            .style(button_style)
+    } else {
+        button(caption(tr!("keybindings-add-key")))
+            .on_press(Message::RecordPressed(action))
+            .style(button_secondary_style)
+    };
+
+    container(
+        row![
+            info,
+            horizontal_space(),
+            keys,
+            record.padding([6, 12]),
+            button(caption(tr!("keybindings-reset")))
+                .on_press(Message::ResetPressed(action))
+                .style(button::text)
+                .padding([6, 8]),
+        ]
+        .spacing(12)
+        .align_y(iced::Alignment::Center),
+    )
+    .width(Fill)
+    .padding(12)
+    .style(|_t| container::Style {
+        background: Some(AppColorBackground::Secondary.to_bg()),
+        border: Border {
+            radius: BORDER_RADIUS_SMALL.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+fn conflict_notice(conflict: &Conflict) -> Element<'_, Message> {
+    container(
+        row![
+            caption(tr!(
+                "keybindings-conflict",
+                key = conflict.binding.to_string(),
+                action = conflict.bound_to.label()
+            ))
+            .color(Color::from(AppColorBackground::Primary)),
+            horizontal_space(),
+            button(caption(tr!("keybindings-use-here")))
+                .on_press(Message::ReplaceConfirmed)
+                .style(button_style)
+                .padding([4, 10]),
+            button(caption(tr!("keybindings-cancel")))
+                .on_press(Message::CancelRecording)
+                .style(button_secondary_style)
+                .padding([4, 10]),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center),
+    )
+    .width(Fill)
+    .padding([8, 12])
+    .style(|_t| container::Style {
+        background: Some(AppColorStatus::Warning.to_bg()),
+        border: Border {
+            radius: BORDER_RADIUS_SMALL.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+pub fn view<'a>(state: &'a State, settings: &'a Settings) -> Element<'a, Message> {
+    let intro = if state.recording.is_some() {
+        tr!("keybindings-recording-hint")
+    } else {
+        tr!("keybindings-hint")
+    };
+
+    let actions = Action::ALL
+        .into_iter()
+        .filter(|action| action.is_available())
+        .fold(column![].spacing(8), |list, action| {
+            list.push(action_row(state, &settings.keybindings, action))
+        });
+
+    column![
+        row![
+            title(tr!("keybindings-title")),
+            horizontal_space(),
+            button(caption(tr!("keybindings-reset-all")))
+                .on_press(Message::ResetAllPressed)
+                .style(button_secondary_style)
+                .padding([6, 12]),
+        ]
+        .align_y(iced::Alignment::Center),
+        caption(intro).color(Color::from(AppColorForeground::SubtextPrimary)),
+    ]
+    .push_maybe(state.pending.as_ref().map(conflict_notice))
+    .push_maybe(
+        state
+            .error
+            .as_ref()
+            .map(|error| caption(error).color(Color::from(AppColorStatus::Failure))),
+    )
+    .push(actions)
+    .spacing(16)
+    .into()
+}
+
+pub fn update(state: &mut State, message: Message, settings: &mut Settings) {
+    match message {
+        Message::RecordPressed(action) => {
+            state.recording = Some(action);
+            state.pending = None;
+            state.error = None;
+        }
+        Message::CancelRecording => {
+            state.recording = None;
+            state.pending = None;
+        }
+        Message::KeyCaptured(binding) => {
+            let Some(action) = state.recording else {
+                return;
+            };
+            if binding.is_reserved() {
+                state.error = Some(tr!("keybindings-reserved", key = binding.to_string()));
+                return;
+            }
+            state.recording = None;
+            state.error = None;
+            let bound_to = settings
+                .keybindings
+                .actions_for(&binding)
+                .find(|other| *other != action && other.is_available());
+            match bound_to {
+                Some(bound_to) => {
+                    state.pending = Some(Conflict {
+                        action,
+                        binding,
+                        bound_to,
+                    })
+                }
+                None => settings.keybindings.bind(action, binding),
+            }
+        }
+        Message::ReplaceConfirmed => {
+            if let Some(conflict) = state.pending.take() {
+                settings.keybindings.release(&conflict.binding);
+                settings.keybindings.bind(conflict.action, conflict.binding);
+            }
+        }
+        Message::RemovePressed(action, binding) => settings.keybindings.unbind(action, &binding),
+        Message::ResetPressed(action) => settings.keybindings.reset(action),
+        Message::ResetAllPressed => {
+            state.pending = None;
+            settings.keybindings = Keybindings::default();
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{
+    colors::AppColorForeground,
+    config::Settings,
+    i18n::{self, LANGUAGES, Language, tr},
+    typography::{caption, title},
+};
+use chrono::Local;
+use std::fmt;
+
+use iced::{
+    Color, Element,
+    widget::{column, pick_list},
+};
+
+/// An entry of the language picker, `None` follows the system language.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LanguageChoice(Option<Language>);
+
+impl LanguageChoice {
+    fn all() -> Vec<LanguageChoice> {
+        std::iter::once(LanguageChoice(None))
+            .chain(
+                LANGUAGES
+                    .into_iter()
+                    .map(|language| LanguageChoice(Some(language))),
+            )
+            .collect()
+    }
+}
+
+impl fmt::Display for LanguageChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(language) => f.write_str(language.name),
+            None => {
+                let system = i18n::system_language();
+                f.write_str(&tr!("language-system", language = system.name))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    LanguageSelected(LanguageChoice),
+}
+
+pub fn view(settings: &Settings) -> Element<'_, Message> {
+    let selected = LanguageChoice(
+        settings
+            .language
+            .as_deref()
+            .and_then(|id| LANGUAGES.into_iter().find(|language| language.id == id)),
+    );
+    let today = i18n::format_date(&Local::now(), "date-long-format");
+
+    column![
+        title(tr!("language-title")),
+        pick_list(
+            LanguageChoice::all(),
+            Some(selected),
+            Message::LanguageSelected
+        )
+        .padding([8, 12]),
+        caption(tr!("language-preview", date = today)),
+        caption(tr!("language-hint")).color(Color::from(AppColorForeground::SubtextPrimary)),
+    ]
+    .spacing(16)
+    .into()
+}
+
+pub fn update(settings: &mut Settings, message: Message) {
+    match message {
+        Message::LanguageSelected(LanguageChoice(language)) => {
+            settings.language = language.map(|language| language.id.to_string());
+        }
+    }
+}
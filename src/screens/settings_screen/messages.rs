@@ -0,0 +1,68 @@
+use crate::{
+    colors::{AppColorForeground, AppColorStatus},
+    config::Settings,
+    constants::PREVIEW_PROXY_URL,
+    i18n::tr,
+    styles::button_secondary_style,
+    typography::{TypeScale, caption, label, title},
+};
+use reqwest::Url;
+
+use iced::{
+    Color, Element,
+    widget::{button, checkbox, column, row, text, text_input},
+};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    LinkPreviewsToggled(bool),
+    PreviewProxyChanged(String),
+    PreviewProxyReset,
+}
+
+pub fn view(settings: &Settings) -> Element<'_, Message> {
+    let subtext = Color::from(AppColorForeground::SubtextPrimary);
+    let invalid = Url::parse(&settings.link_preview_proxy).is_err().then(|| {
+        caption(tr!("settings-link-preview-proxy-invalid"))
+            .color(Color::from(AppColorStatus::Failure))
+    });
+    let changed = settings.link_preview_proxy != PREVIEW_PROXY_URL;
+
+    column![
+        title(tr!("settings-section-messages")),
+        column![
+            checkbox(tr!("settings-link-previews"), settings.link_previews)
+                .on_toggle(Message::LinkPreviewsToggled),
+            caption(tr!("settings-link-previews-hint")).color(subtext),
+        ]
+        .spacing(8),
+        column![
+            label(tr!("settings-link-preview-proxy")),
+            row![
+                text_input(PREVIEW_PROXY_URL, &settings.link_preview_proxy)
+                    .on_input(Message::PreviewProxyChanged)
+                    .padding([8, 12]),
+                button(
+                    text(tr!("settings-link-preview-proxy-reset")).size(TypeScale::Label.size())
+                )
+                .on_press_maybe(changed.then_some(Message::PreviewProxyReset))
+                .padding([8, 12])
+                .style(button_secondary_style),
+            ]
+            .spacing(8),
+        ]
+        .push_maybe(invalid)
+        .push(caption(tr!("settings-link-preview-proxy-hint")).color(subtext))
+        .spacing(8),
+    ]
+    .spacing(16)
+    .into()
+}
+
+pub fn update(settings: &mut Settings, message: Message) {
+    match message {
+        Message::LinkPreviewsToggled(enabled) => settings.link_previews = enabled,
+        Message::PreviewProxyChanged(proxy) => settings.link_preview_proxy = proxy,
+        Message::PreviewProxyReset => settings.link_preview_proxy = PREVIEW_PROXY_URL.to_string(),
+    }
+}
@@ -0,0 +1,268 @@
+pub mod appearance;
+pub mod devices;
+pub mod keybindings;
+pub mod language;
+pub mod messages;
+pub mod security;
+pub mod theme_editor;
+
+use crate::{
+    colors::{AppColorBackground, AppColorForeground},
+    components::directional_row,
+    config::Settings,
+    i18n::tr,
+    styles::button_secondary_style,
+    theme::{ColorScheme, ThemeEntry},
+    typography::{DISPLAY, TypeScale, body},
+};
+
+use iced::{
+    Color, Element,
+    Length::Fill,
+    Padding, Task,
+    widget::{button, column, container, scrollable, text, vertical_space},
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    pub section: Section,
+    pub security: security::State,
+    pub devices: devices::State,
+    pub theme_editor: theme_editor::State,
+    pub keybindings: keybindings::State,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Section {
+    #[default]
+    Security,
+    Devices,
+    Appearance,
+    ThemeEditor,
+    Keybindings,
+    Language,
+    Messages,
+}
+
+impl Section {
+    pub const ALL: [Section; 7] = [
+        Section::Security,
+        Section::Devices,
+        Section::Appearance,
+        Section::ThemeEditor,
+        Section::Messages,
+        Section::Keybindings,
+        Section::Language,
+    ];
+
+    pub fn label(self) -> String {
+        tr!(match self {
+            Section::Security => "settings-section-security",
+            Section::Devices => "settings-section-devices",
+            Section::Appearance => "settings-section-appearance",
+            Section::ThemeEditor => "settings-section-theme-editor",
+            Section::Keybindings => "settings-section-keybindings",
+            Section::Language => "settings-section-language",
+            Section::Messages => "settings-section-messages",
+        })
+    }
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self {
+            section: Section::Security,
+            security: security::State::new(),
+            devices: devices::State::new(),
+            theme_editor: theme_editor::State::new(),
+            keybindings: keybindings::State::new(),
+        }
+    }
+
+    /// Whether the next key press should be recorded as a keybinding instead of running
+    /// the action bound to it.
+    pub fn is_recording_key(&self) -> bool {
+        self.section == Section::Keybindings && self.keybindings.recording.is_some()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SectionSelected(Section),
+    Security(security::Message),
+    Devices(devices::Message),
+    Appearance(appearance::Message),
+    ThemeEditor(theme_editor::Message),
+    Keybindings(keybindings::Message),
+    Language(language::Message),
+    Messages(messages::Message),
+    Close,
+}
+
+impl Message {
+    /// Typed into a text input, so the settings are saved once typing pauses instead of on
+    /// every key.
+    pub fn is_typing(&self) -> bool {
+        matches!(
+            self,
+            Message::Messages(messages::Message::PreviewProxyChanged(_))
+        )
+    }
+}
+
+pub fn view<'a>(
+    state: &'a State,
+    settings: &'a Settings,
+    themes: &'a [ThemeEntry],
+    system_scheme: Option<ColorScheme>,
+) -> Element<'a, Message> {
+    let sections = Section::ALL
+        .iter()
+        .fold(column![].spacing(2), |list, &section| {
+            let is_selected = section == state.section;
+            list.push(
+                button(body(section.label()))
+                    .on_press(Message::SectionSelected(section))
+                    .width(Fill)
+                    .padding([6, 10])
+                    .style(move |theme, status| {
+                        let mut style = button::text(theme, status);
+                        if is_selected {
+                            style.background = Some(AppColorBackground::SurfacePrimary.to_bg());
+                        }
+                        style.text_color = Color::from(if is_selected {
+                            AppColorForeground::Primary
+                        } else {
+                            AppColorForeground::SubtextPrimary
+                        });
+                        style
+                    }),
+            )
+        });
+
+    let content: Element<Message> = match state.section {
+        Section::Security => security::view(&state.security).map(Message::Security),
+        Section::Devices => devices::view(&state.devices).map(Message::Devices),
+        Section::Appearance => {
+            appearance::view(settings, themes, system_scheme).map(Message::Appearance)
+        }
+        Section::ThemeEditor => {
+            theme_editor::view(&state.theme_editor, themes).map(Message::ThemeEditor)
+        }
+        Section::Keybindings => {
+            keybindings::view(&state.keybindings, settings).map(Message::Keybindings)
+        }
+        Section::Language => language::view(settings).map(Message::Language),
+        Section::Messages => messages::view(settings).map(Message::Messages),
+    };
+
+    directional_row([
+        container(
+            column![
+                text(tr!("settings-title"))
+                    .font(DISPLAY)
+                    .size(TypeScale::Body.size()),
+                sections,
+                vertical_space(),
+                button(text(tr!("settings-close")).width(Fill).center())
+                    .on_press(Message::Close)
+                    .width(Fill)
+                    .style(button_secondary_style)
+                    .padding(8),
+            ]
+            .spacing(12),
+        )
+        .width(220)
+        .height(Fill)
+        .padding(16)
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::Secondary.to_bg()),
+            ..Default::default()
+        })
+        .into(),
+        container(scrollable(container(content).max_width(740).padding(
+            Padding {
+                top: 40.0,
+                bottom: 40.0,
+                left: 40.0,
+                right: 40.0,
+            },
+        )))
+        .width(Fill)
+        .height(Fill)
+        .style(|_t| container::Style {
+            background: Some(AppColorBackground::Tertiary.to_bg()),
+            ..Default::default()
+        })
+        .into(),
+    ])
+    .width(Fill)
+    .height(Fill)
+    .into()
+}
+
+/// Loads whatever the section needs from the server when it becomes visible.
+fn load_section(section: Section) -> Task<Message> {
+    match section {
+        Section::Security => Task::done(Message::Security(security::Message::Load)),
+        Section::Devices => Task::done(Message::Devices(devices::Message::Load)),
+        Section::Appearance
+        | Section::ThemeEditor
+        | Section::Keybindings
+        | Section::Language
+        | Section::Messages => Task::none(),
+    }
+}
+
+pub fn open() -> (State, Task<Message>) {
+    let state = State::new();
+    let task = load_section(state.section);
+    (state, task)
+}
+
+/// Changes made to `settings` are saved by the caller, which also re-applies the theme
+/// when `settings` or `themes` changed.
+pub fn update(
+    state: &mut State,
+    message: Message,
+    access_token: &str,
+    settings: &mut Settings,
+    themes: &mut Vec<ThemeEntry>,
+) -> Task<Message> {
+    match message {
+        Message::SectionSelected(section) => {
+            if state.section == section {
+                return Task::none();
+            }
+            state.section = section;
+            load_section(section)
+        }
+        Message::Security(msg) => {
+            security::update(&mut state.security, msg, access_token).map(Message::Security)
+        }
+        Message::Devices(msg) => {
+            devices::update(&mut state.devices, msg, access_token).map(Message::Devices)
+        }
+        Message::Appearance(msg) => {
+            appearance::update(settings, msg);
+            Task::none()
+        }
+        Message::ThemeEditor(msg) => {
+            theme_editor::update(&mut state.theme_editor, msg, settings, themes)
+                .map(Message::ThemeEditor)
+        }
+        Message::Keybindings(msg) => {
+            keybindings::update(&mut state.keybindings, msg, settings);
+            Task::none()
+        }
+        Message::Language(msg) => {
+            language::update(settings, msg);
+            Task::none()
+        }
+        Message::Messages(msg) => {
+            messages::update(settings, msg);
+            Task::none()
+        }
+        Message::Close => Task::none(),
+    }
+}
@@ -0,0 +1,544 @@
+use crate::{
+    colors::{AppColorForeground, AppColorStatus},
+    components::styled_input,
+    constants::API_URL,
+    i18n::tr,
+    styles::{button_danger_style, button_secondary_style, button_style, container_style},
+    typography::{TypeScale, caption, code, label, title},
+    utils::{Redacted, send_empty, send_json},
+};
+use qrcode::{QrCode, render::svg as qr_svg};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use iced::{
+    Color, Element,
+    Length::Fill,
+    Task, clipboard,
+    widget::{button, column, container, row, svg, text},
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    pub status: Option<MfaStatus>,
+    pub is_loading: bool,
+    pub error: Option<String>,
+    pub enrollment: Option<Enrollment>,
+    pub code: Redacted<String>,
+    pub code_error: Option<String>,
+    pub password_action: Option<PasswordAction>,
+    pub password: Redacted<String>,
+    pub password_error: Option<String>,
+    pub backup_codes: Option<Redacted<Vec<String>>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MfaStatus {
+    pub totp_enabled: bool,
+    pub backup_codes_remaining: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TotpEnrollment {
+    pub secret: Redacted<String>,
+    pub otpauth_uri: Redacted<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BackupCodes {
+    pub backup_codes: Redacted<Vec<String>>,
+}
+
+#[derive(Serialize)]
+struct BackupCodesRequest {
+    password: String,
+    regenerate: bool,
+}
+
+/// A TOTP secret that was generated but not confirmed with a code yet.
+#[derive(Debug, Clone)]
+pub struct Enrollment {
+    pub secret: Redacted<String>,
+    pub otpauth_uri: Redacted<String>,
+    pub qr_code: svg::Handle,
+}
+
+/// Actions that require the user to type their password again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PasswordAction {
+    ViewBackupCodes,
+    RegenerateBackupCodes,
+    DisableTotp,
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Load,
+    StatusLoaded(Result<MfaStatus, String>),
+    EnableTotpPressed,
+    EnrollmentStarted(Result<TotpEnrollment, String>),
+    CodeInputChanged(String),
+    VerifyPressed,
+    TotpVerified(Result<BackupCodes, String>),
+    CancelEnrollment,
+    CopySecret,
+    PasswordActionRequested(PasswordAction),
+    PasswordInputChanged(String),
+    ConfirmPasswordAction,
+    CancelPasswordAction,
+    BackupCodesLoaded(Result<BackupCodes, String>),
+    TotpDisabled(Result<(), String>),
+    CopyBackupCodes,
+    HideBackupCodes,
+}
+
+pub fn view(state: &State) -> Element<'_, Message> {
+    let mut content = column![
+        title(tr!("security-title")),
+        caption(tr!("security-hint")).color(Color::from(AppColorForeground::SubtextPrimary)),
+    ]
+    .spacing(16);
+
+    if let Some(error) = &state.error {
+        content = content.push(caption(error).color(Color::from(AppColorStatus::Failure)));
+    }
+
+    content = match (&state.status, &state.enrollment) {
+        (None, _) => content.push(
+            caption(tr!("security-loading")).color(Color::from(AppColorForeground::SubtextPrimary)),
+        ),
+        (Some(_), Some(enrollment)) => content.push(enrollment_view(state, enrollment)),
+        (Some(status), None) if status.totp_enabled => content.push(enabled_view(state, status)),
+        (Some(_), None) => content.push(
+            button(text(tr!("security-enable")))
+                .on_press_maybe((!state.is_loading).then_some(Message::EnableTotpPressed))
+                .style(button_style)
+                .padding([8, 16]),
+        ),
+    };
+
+    if let Some(action) = state.password_action {
+        content = content.push(password_confirm_view(state, action));
+    }
+
+    if let Some(codes) = &state.backup_codes {
+        content = content.push(backup_codes_view(codes.expose()));
+    }
+
+    content.into()
+}
+
+fn enrollment_view<'a>(state: &'a State, enrollment: &'a Enrollment) -> Element<'a, Message> {
+    column![
+        label(tr!("security-scan-step")),
+        container(svg(enrollment.qr_code.clone()).width(200).height(200)).padding(8),
+        caption(tr!("security-manual-key")).color(Color::from(AppColorForeground::SubtextPrimary)),
+        row![
+            code(enrollment.secret.expose()),
+            button(caption(tr!("security-copy")))
+                .on_press(Message::CopySecret)
+                .style(button_secondary_style)
+                .padding([4, 8]),
+        ]
+        .spacing(8),
+        text(enrollment.otpauth_uri.expose())
+            .size(TypeScale::Small.size())
+            .color(Color::from(AppColorForeground::SubtextTertiary)),
+        label(tr!("security-code-step")),
+        styled_input(
+            &tr!("security-code"),
+            state.code.expose(),
+            state.code_error.as_deref(),
+            Message::CodeInputChanged,
+            None,
+            Some(true),
+            None,
+        ),
+        row![
+            button(text(tr!("security-verify")))
+                .on_press_maybe((!state.is_loading).then_some(Message::VerifyPressed))
+                .style(button_style)
+                .padding([8, 16]),
+            button(text(tr!("security-cancel")))
+                .on_press(Message::CancelEnrollment)
+                .style(button_secondary_style)
+                .padding([8, 16]),
+        ]
+        .spacing(8),
+    ]
+    .spacing(12)
+    .into()
+}
+
+fn enabled_view<'a>(state: &'a State, status: &'a MfaStatus) -> Element<'a, Message> {
+    let action = |label: String, action: PasswordAction| {
+        button(text(label))
+            .on_press_maybe(
+                (!state.is_loading && state.password_action.is_none())
+                    .then_some(Message::PasswordActionRequested(action)),
+            )
+            .padding([8, 16])
+    };
+
+    column![
+        label(tr!("security-enabled")).color(Color::from(AppColorStatus::Success)),
+        caption(tr!(
+            "security-backup-codes-remaining",
+            count = status.backup_codes_remaining
+        ))
+        .color(Color::from(AppColorForeground::SubtextPrimary)),
+        row![
+            action(
+                tr!("security-view-backup-codes"),
+                PasswordAction::ViewBackupCodes
+            )
+            .style(button_secondary_style),
+            action(
+                tr!("security-regenerate-backup-codes"),
+                PasswordAction::RegenerateBackupCodes
+            )
+            .style(button_secondary_style),
+            action(tr!("security-disable"), PasswordAction::DisableTotp).style(button_danger_style),
+        ]
+        .spacing(8),
+    ]
+    .spacing(12)
+    .into()
+}
+
+fn password_confirm_view(state: &State, action: PasswordAction) -> Element<'_, Message> {
+    let (title, confirm_label) = match action {
+        PasswordAction::ViewBackupCodes => ("security-confirm-view", "security-view"),
+        PasswordAction::RegenerateBackupCodes => {
+            ("security-confirm-regenerate", "security-regenerate")
+        }
+        PasswordAction::DisableTotp => ("security-confirm-disable", "security-disable"),
+    };
+
+    let confirm = button(text(tr!(confirm_label)))
+        .on_press_maybe((!state.is_loading).then_some(Message::ConfirmPasswordAction))
+        .padding([8, 16])
+        .style(if action == PasswordAction::DisableTotp {
+            button_danger_style
+        } else {
+            button_style
+        });
+
+    container(
+        column![
+            label(tr!(title)),
+            styled_input(
+                &tr!("login-password"),
+                state.password.expose(),
+                state.password_error.as_deref(),
+                Message::PasswordInputChanged,
+                Some(true),
+                Some(true),
+                None,
+            ),
+            row![
+                confirm,
+                button(text(tr!("security-cancel")))
+                    .on_press(Message::CancelPasswordAction)
+                    .style(button_secondary_style)
+                    .padding([8, 16]),
+            ]
+            .spacing(8),
+        ]
+        .spacing(12),
+    )
+    .padding(16)
+    .style(container_style)
+    .into()
+}
+
+fn backup_codes_view(codes: &[String]) -> Element<'_, Message> {
+    let list = codes.chunks(2).fold(column![].spacing(6), |list, pair| {
+        list.push(
+            row(pair
+                .iter()
+                .map(|backup_code| code(backup_code).width(140).into()))
+            .spacing(16),
+        )
+    });
+
+    container(
+        column![
+            label(tr!("security-backup-codes")),
+            caption(tr!("security-backup-codes-hint"))
+                .color(Color::from(AppColorForeground::SubtextPrimary)),
+            list,
+            row![
+                button(text(tr!("security-copy")))
+                    .on_press(Message::CopyBackupCodes)
+                    .style(button_secondary_style)
+                    .padding([8, 16]),
+                button(text(tr!("security-done")))
+                    .on_press(Message::HideBackupCodes)
+                    .style(button_style)
+                    .padding([8, 16]),
+            ]
+            .spacing(8),
+        ]
+        .spacing(12)
+        .width(Fill),
+    )
+    .padding(16)
+    .style(container_style)
+    .into()
+}
+
+fn qr_code_handle(data: &str) -> Result<svg::Handle, String> {
+    let code = QrCode::new(data.as_bytes())
+        .map_err(|e| tr!("security-qr-code-failed", details = e.to_string()))?;
+    let image = code
+        .render::<qr_svg::Color>()
+        .min_dimensions(200, 200)
+        .quiet_zone(true)
+        .build();
+    Ok(svg::Handle::from_memory(image.into_bytes()))
+}
+
+pub async fn fetch_status(access_token: String) -> Result<MfaStatus, String> {
+    let client = reqwest::Client::new();
+    send_json(
+        client
+            .get(format!("{API_URL}/auth/mfa"))
+            .bearer_auth(access_token),
+    )
+    .await
+}
+
+pub async fn start_enrollment(access_token: String) -> Result<TotpEnrollment, String> {
+    let client = reqwest::Client::new();
+    send_json(
+        client
+            .post(format!("{API_URL}/auth/mfa/totp"))
+            .bearer_auth(access_token),
+    )
+    .await
+}
+
+pub async fn verify_enrollment(access_token: String, code: String) -> Result<BackupCodes, String> {
+    let mut map = HashMap::new();
+    map.insert("code", code);
+
+    let client = reqwest::Client::new();
+    send_json(
+        client
+            .post(format!("{API_URL}/auth/mfa/totp/verify"))
+            .bearer_auth(access_token)
+            .json(&map),
+    )
+    .await
+}
+
+pub async fn fetch_backup_codes(
+    access_token: String,
+    password: String,
+    regenerate: bool,
+) -> Result<BackupCodes, String> {
+    let client = reqwest::Client::new();
+    send_json(
+        client
+            .post(format!("{API_URL}/auth/mfa/backup-codes"))
+            .bearer_auth(access_token)
+            .json(&BackupCodesRequest {
+                password,
+                regenerate,
+            }),
+    )
+    .await
+}
+
+pub async fn disable_totp(access_token: String, password: String) -> Result<(), String> {
+    let mut map = HashMap::new();
+    map.insert("password", password);
+
+    let client = reqwest::Client::new();
+    send_empty(
+        client
+            .post(format!("{API_URL}/auth/mfa/totp/disable"))
+            .bearer_auth(access_token)
+            .json(&map),
+    )
+    .await
+}
+
+pub fn update(state: &mut State, message: Message, access_token: &str) -> Task<Message> {
+    match message {
+        Message::Load => {
+            state.error = None;
+            Task::perform(
+                fetch_status(access_token.to_string()),
+                Message::StatusLoaded,
+            )
+        }
+        Message::StatusLoaded(result) => {
+            match result {
+                Ok(status) => state.status = Some(status),
+                Err(e) => state.error = Some(e),
+            }
+            Task::none()
+        }
+        Message::EnableTotpPressed => {
+            state.is_loading = true;
+            state.error = None;
+            Task::perform(
+                start_enrollment(access_token.to_string()),
+                Message::EnrollmentStarted,
+            )
+        }
+        Message::EnrollmentStarted(result) => {
+            state.is_loading = false;
+            match result.and_then(|enrollment| {
+                qr_code_handle(enrollment.otpauth_uri.expose()).map(|qr_code| Enrollment {
+                    secret: enrollment.secret,
+                    otpauth_uri: enrollment.otpauth_uri,
+                    qr_code,
+                })
+            }) {
+                Ok(enrollment) => {
+                    state.enrollment = Some(enrollment);
+                    state.code = Redacted::default();
+                    state.code_error = None;
+                }
+                Err(e) => state.error = Some(e),
+            }
+            Task::none()
+        }
+        Message::CodeInputChanged(value) => {
+            state.code = Redacted::new(value);
+            state.code_error = None;
+            Task::none()
+        }
+        Message::VerifyPressed => {
+            let code = state.code.expose().replace(' ', "");
+            if code.len() != 6 || !code.chars().all(|c| c.is_ascii_digit()) {
+                state.code_error = Some(tr!("security-code-invalid"));
+                return Task::none();
+            }
+            state.is_loading = true;
+            Task::perform(
+                verify_enrollment(access_token.to_string(), code),
+                Message::TotpVerified,
+            )
+        }
+        Message::TotpVerified(result) => {
+            state.is_loading = false;
+            match result {
+                Ok(codes) => {
+                    state.enrollment = None;
+                    state.code = Redacted::default();
+                    state.status = Some(MfaStatus {
+                        totp_enabled: true,
+                        backup_codes_remaining: codes.backup_codes.expose().len() as u32,
+                    });
+                    state.backup_codes = Some(codes.backup_codes);
+                }
+                Err(e) => state.code_error = Some(e),
+            }
+            Task::none()
+        }
+        Message::CancelEnrollment => {
+            state.enrollment = None;
+            state.code = Redacted::default();
+            state.code_error = None;
+            Task::none()
+        }
+        Message::CopySecret => match &state.enrollment {
+            Some(enrollment) => clipboard::write(enrollment.secret.expose().clone()),
+            None => Task::none(),
+        },
+        Message::PasswordActionRequested(action) => {
+            state.password_action = Some(action);
+            state.password = Redacted::default();
+            state.password_error = None;
+            state.backup_codes = None;
+            Task::none()
+        }
+        Message::PasswordInputChanged(value) => {
+            state.password = Redacted::new(value);
+            state.password_error = None;
+            Task::none()
+        }
+        Message::ConfirmPasswordAction => {
+            let Some(action) = state.password_action else {
+                return Task::none();
+            };
+            if state.password.expose().is_empty() {
+                state.password_error = Some(tr!("field-required"));
+                return Task::none();
+            }
+            state.is_loading = true;
+            let token = access_token.to_string();
+            let password = state.password.expose().clone();
+            match action {
+                PasswordAction::ViewBackupCodes => Task::perform(
+                    fetch_backup_codes(token, password, false),
+                    Message::BackupCodesLoaded,
+                ),
+                PasswordAction::RegenerateBackupCodes => Task::perform(
+                    fetch_backup_codes(token, password, true),
+                    Message::BackupCodesLoaded,
+                ),
+                PasswordAction::DisableTotp => {
+                    Task::perform(disable_totp(token, password), Message::TotpDisabled)
+                }
+            }
+        }
+        Message::CancelPasswordAction => {
+            state.password_action = None;
+            state.password = Redacted::default();
+            state.password_error = None;
+            Task::none()
+        }
+        Message::BackupCodesLoaded(result) => {
+            state.is_loading = false;
+            match result {
+                Ok(codes) => {
+                    let regenerated =
+                        state.password_action == Some(PasswordAction::RegenerateBackupCodes);
+                    if let Some(status) = state.status.as_mut().filter(|_| regenerated) {
+                        status.backup_codes_remaining = codes.backup_codes.expose().len() as u32;
+                    }
+                    state.password_action = None;
+                    state.password = Redacted::default();
+                    state.backup_codes = Some(codes.backup_codes);
+                }
+                Err(e) => state.password_error = Some(e),
+            }
+            Task::none()
+        }
+        Message::TotpDisabled(result) => {
+            state.is_loading = false;
+            match result {
+                Ok(()) => {
+                    state.password_action = None;
+                    state.password = Redacted::default();
+                    state.backup_codes = None;
+                    state.status = Some(MfaStatus {
+                        totp_enabled: false,
+                        backup_codes_remaining: 0,
+                    });
+                }
+                Err(e) => state.password_error = Some(e),
+            }
+            Task::none()
+        }
+        Message::CopyBackupCodes => match &state.backup_codes {
+            Some(codes) => clipboard::write(codes.expose().join("\n")),
+            None => Task::none(),
+        },
+        Message::HideBackupCodes => {
+            state.backup_codes = None;
+            Task::none()
+        }
+    }
+}
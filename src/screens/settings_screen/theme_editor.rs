@@ -0,0 +1,628 @@
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorMain, AppColorStatus},
+    components::styled_input,
+    config::Settings,
+    constants::{BORDER_RADIUS, BORDER_RADIUS_FULL, BORDER_RADIUS_SMALL},
+    i18n::tr,
+    styles::{button_secondary_style, button_style},
+    theme::{self, ColorToken, Palette, ThemeEntry},
+    typography::{TypeScale, body, caption, code, label, title},
+};
+use std::path::PathBuf;
+
+use iced::{
+    Border, Color, Element,
+    Length::Fill,
+    Task, Theme,
+    widget::{
+        Text, button, checkbox, column, container, horizontal_space, pick_list, row, slider, text,
+        vertical_space,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct State {
+    /// The palette being edited. It only becomes a theme once saved.
+    pub draft: Palette,
+    pub selected: ColorToken,
+    pub hex_input: String,
+    pub hex_error: Option<String>,
+    pub is_busy: bool,
+    pub notice: Option<Result<String, String>>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl State {
+    /// Starts from whichever palette is active so tweaking the current look is one click away.
+    pub fn new() -> Self {
+        let draft = theme::with_active(Palette::clone);
+        let selected = ColorToken::Main(AppColorMain::Primary);
+        Self {
+            hex_input: hex(draft.get(selected)),
+            draft,
+            selected,
+            hex_error: None,
+            is_busy: false,
+            notice: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Channel {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    BaseSelected(ThemeEntry),
+    NameChanged(String),
+    DarkToggled(bool),
+    TokenSelected(ColorToken),
+    ChannelChanged(Channel, u8),
+    HexInputChanged(String),
+    SavePressed,
+    Saved(Result<ThemeEntry, String>),
+    ExportPressed,
+    Exported(Result<Option<PathBuf>, String>),
+    ImportPressed,
+    Imported(Result<Option<Palette>, String>),
+}
+
+fn hex(color: Color) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn swatch<'a>(color: Color, size: u16) -> Element<'a, Message> {
+    container("")
+        .width(size)
+        .height(size)
+        .style(move |_t| container::Style {
+            background: Some(color.into()),
+            border: Border {
+                radius: BORDER_RADIUS_SMALL.into(),
+                width: 1.0,
+                color: Color::from(AppColorBackground::SurfaceSecondary),
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+fn token_list(state: &State) -> Element<'_, Message> {
+    let mut list = column![].spacing(2);
+    let mut group = None;
+    for token in ColorToken::all() {
+        if group != Some(token.group()) {
+            let name = token.group();
+            group = Some(name.clone());
+            list = list.push(
+                container(caption(name).color(Color::from(AppColorForeground::SubtextSecondary)))
+                    .padding([6, 0]),
+            );
+        }
+        let is_selected = token == state.selected;
+        let color = state.draft.get(token);
+        list = list.push(
+            button(
+                row![
+                    swatch(color, 16),
+                    label(token.label()),
+                    horizontal_space(),
+                    code(hex(color))
+                        .size(TypeScale::Caption.size())
+                        .color(Color::from(AppColorForeground::SubtextPrimary)),
+                ]
+                .spacing(8),
+            )
+            .on_press(Message::TokenSelected(token))
+            .width(Fill)
+            .padding([4, 8])
+            .style(move |theme, status| {
+                let mut style = button::text(theme, status);
+                if is_selected {
+                    style.background = Some(AppColorBackground::SurfacePrimary.to_bg());
+                }
+                style.text_color = Color::from(AppColorForeground::Primary);
+                style
+            }),
+        );
+    }
+    list.width(260).into()
+}
+
+fn color_editor(state: &State) -> Element<'_, Message> {
+    let [r, g, b, _] = state.draft.get(state.selected).into_rgba8();
+    let channel = |name: &'static str, channel: Channel, value: u8| {
+        row![
+            caption(name).width(16),
+            slider(0..=255u8, value, move |value| {
+                Message::ChannelChanged(channel, value)
+            }),
+            caption(value.to_string()).width(28),
+        ]
+        .spacing(8)
+    };
+
+    column![
+        row![
+            swatch(state.draft.get(state.selected), 48),
+            column![
+                body(state.selected.label()),
+                caption(state.selected.group())
+                    .color(Color::from(AppColorForeground::SubtextPrimary)),
+            ]
+            .spacing(2),
+        ]
+        .spacing(12),
+        channel("R", Channel::Red, r),
+        channel("G", Channel::Green, g),
+        channel("B", Channel::Blue, b),
+        styled_input(
+            &tr!("theme-editor-hex"),
+            &state.hex_input,
+            state.hex_error.as_deref(),
+            Message::HexInputChanged,
+            None,
+            None,
+            None,
+        ),
+    ]
+    .spacing(12)
+    .width(Fill)
+    .into()
+}
+
+fn contrast_report(palette: &Palette) -> Element<'_, Message> {
+    let issues = palette.contrast_issues();
+    if issues.is_empty() {
+        return caption(tr!("theme-editor-contrast-ok"))
+            .color(Color::from(AppColorStatus::Success))
+            .into();
+    }
+
+    issues
+        .into_iter()
+        .fold(column![].spacing(4), |list, issue| {
+            list.push(
+                caption(tr!(
+                    "theme-editor-contrast-issue",
+                    label = tr!(issue.label),
+                    ratio = format!("{:.2}", issue.ratio),
+                    minimum = format!("{:.1}", issue.minimum)
+                ))
+                .color(Color::from(AppColorStatus::Warning)),
+            )
+        })
+        .into()
+}
+
+fn fill(color: Color) -> impl Fn(&Theme) -> container::Style {
+    move |_t| container::Style {
+        background: Some(color.into()),
+        border: Border {
+            radius: BORDER_RADIUS.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn tinted<'a>(content: impl text::IntoFragment<'a>, color: Color, size: u16) -> Text<'a> {
+    body(content).size(size).color(color)
+}
+
+fn preview_guild<'a>(color: Color) -> Element<'a, Message> {
+    container("")
+        .width(28)
+        .height(28)
+        .style(move |_t| container::Style {
+            background: Some(color.into()),
+            border: Border {
+                radius: BORDER_RADIUS_FULL.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+fn preview_message<'a>(
+    palette: &Palette,
+    author: &'a str,
+    author_color: AppColorMain,
+    lines: Vec<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    column![
+        row![
+            tinted(author, palette.main(author_color), 12),
+            tinted(
+                tr!("theme-editor-preview-time"),
+                palette.foreground(AppColorForeground::SubtextTertiary),
+                10
+            ),
+        ]
+        .spacing(8),
+        column(lines).spacing(2),
+    ]
+    .spacing(2)
+    .into()
+}
+
+/// A miniature `app_screen` drawn with the draft's colors instead of the active palette.
+fn preview(palette: &Palette) -> Element<'_, Message> {
+    let foreground = |color| palette.foreground(color);
+    let background = |color| palette.background(color);
+
+    let guilds = container(
+        column![
+            preview_guild(palette.main(AppColorMain::Primary)),
+            preview_guild(palette.main(AppColorMain::AccentPrimary)),
+            preview_guild(palette.main(AppColorMain::AccentSecondary)),
+            preview_guild(background(AppColorBackground::SurfacePrimary)),
+        ]
+        .spacing(8),
+    )
+    .padding(8)
+    .height(Fill)
+    .style(fill(background(AppColorBackground::Primary)));
+
+    let channels = container(
+        column![
+            tinted(
+                tr!("theme-editor-preview-category"),
+                foreground(AppColorForeground::SubtextSecondary),
+                10
+            ),
+            container(tinted(
+                "# welcome",
+                foreground(AppColorForeground::Primary),
+                12
+            ))
+            .width(Fill)
+            .padding([2, 6])
+            .style(fill(background(AppColorBackground::SurfacePrimary))),
+            container(tinted(
+                "# random",
+                foreground(AppColorForeground::SubtextPrimary),
+                12
+            ))
+            .padding([2, 6]),
+            container(tinted(
+                "# help",
+                foreground(AppColorForeground::SubtextPrimary),
+                12
+            ))
+            .padding([2, 6]),
+        ]
+        .spacing(4),
+    )
+    .width(130)
+    .height(Fill)
+    .padding(8)
+    .style(fill(background(AppColorBackground::Secondary)));
+
+    let composer = row![
+        container(tinted(
+            tr!("theme-editor-preview-composer"),
+            foreground(AppColorForeground::SubtextTertiary),
+            12
+        ))
+        .width(Fill)
+        .padding(8)
+        .style(fill(background(AppColorBackground::SurfaceTertiary))),
+        container(tinted(
+            tr!("theme-editor-preview-send"),
+            background(AppColorBackground::Primary),
+            12,
+        ))
+        .padding(8)
+        .style(fill(palette.main(AppColorMain::Secondary))),
+    ]
+    .spacing(8);
+
+    let chat = container(
+        column![
+            container(tinted(
+                tr!("theme-editor-preview-warning"),
+                palette.status(AppColorStatus::Warning),
+                11,
+            ))
+            .width(Fill)
+            .padding([4, 8])
+            .style(fill(background(AppColorBackground::SurfacePrimary))),
+            preview_message(
+                palette,
+                "potato",
+                AppColorMain::AccentPrimary,
+                vec![
+                    tinted(
+                        tr!("theme-editor-preview-question"),
+                        foreground(AppColorForeground::Primary),
+                        12,
+                    )
+                    .into(),
+                ],
+            ),
+            preview_message(
+                palette,
+                "tater",
+                AppColorMain::AccentSecondary,
+                vec![
+                    tinted(
+                        tr!("theme-editor-preview-answer"),
+                        foreground(AppColorForeground::Secondary),
+                        12,
+                    )
+                    .into(),
+                    tinted(
+                        "https://example.com/themes",
+                        palette.main(AppColorMain::Secondary),
+                        12,
+                    )
+                    .into(),
+                ],
+            ),
+            tinted(
+                tr!("theme-editor-preview-failure"),
+                palette.status(AppColorStatus::Failure),
+                11
+            ),
+            tinted(
+                tr!("theme-editor-preview-success"),
+                palette.status(AppColorStatus::Success),
+                11
+            ),
+            vertical_space(),
+            composer,
+        ]
+        .spacing(10),
+    )
+    .width(Fill)
+    .height(Fill)
+    .padding(12)
+    .style(fill(background(AppColorBackground::Tertiary)));
+
+    container(row![guilds, channels, chat])
+        .height(280)
+        .width(Fill)
+        .style(fill(background(AppColorBackground::Primary)))
+        .into()
+}
+
+pub fn view<'a>(state: &'a State, themes: &'a [ThemeEntry]) -> Element<'a, Message> {
+    let notice = state.notice.as_ref().map(|notice| {
+        let (content, color) = match notice {
+            Ok(message) => (message.as_str(), AppColorStatus::Success),
+            Err(message) => (message.as_str(), AppColorStatus::Failure),
+        };
+        caption(content).color(Color::from(color))
+    });
+
+    let actions = row![
+        button(text(tr!("theme-editor-save")))
+            .on_press_maybe((!state.is_busy).then_some(Message::SavePressed))
+            .style(button_style)
+            .padding([8, 16]),
+        button(text(tr!("theme-editor-export")))
+            .on_press_maybe((!state.is_busy).then_some(Message::ExportPressed))
+            .style(button_secondary_style)
+            .padding([8, 16]),
+        button(text(tr!("theme-editor-import")))
+            .on_press_maybe((!state.is_busy).then_some(Message::ImportPressed))
+            .style(button_secondary_style)
+            .padding([8, 16]),
+    ]
+    .spacing(8);
+
+    column![
+        title(tr!("theme-editor-title")),
+        row![
+            column![
+                label(tr!("theme-editor-start-from")),
+                pick_list(themes, None::<ThemeEntry>, Message::BaseSelected)
+                    .placeholder(tr!("theme-editor-pick-theme"))
+                    .padding([8, 12]),
+            ]
+            .spacing(8),
+            styled_input(
+                &tr!("theme-editor-name"),
+                &state.draft.name,
+                None,
+                Message::NameChanged,
+                None,
+                Some(true),
+                None,
+            ),
+        ]
+        .spacing(16),
+        checkbox(tr!("theme-editor-dark"), state.draft.dark).on_toggle(Message::DarkToggled),
+        row![token_list(state), color_editor(state)].spacing(24),
+        column![
+            label(tr!("theme-editor-contrast")),
+            contrast_report(&state.draft)
+        ]
+        .spacing(8),
+        column![label(tr!("theme-editor-preview")), preview(&state.draft)].spacing(8),
+        actions,
+    ]
+    .push_maybe(notice)
+    .spacing(16)
+    .into()
+}
+
+fn select(state: &mut State, token: ColorToken) {
+    state.selected = token;
+    state.hex_input = hex(state.draft.get(token));
+    state.hex_error = None;
+}
+
+async fn export_theme(palette: Palette) -> Result<Option<PathBuf>, String> {
+    let contents = palette.to_toml()?;
+    let Some(file) = rfd::AsyncFileDialog::new()
+        .set_title(tr!("theme-editor-export-title"))
+        .set_file_name(format!("{}.toml", theme::theme_id(&palette.name)))
+        .add_filter(tr!("theme-editor-file-type"), &["toml"])
+        .save_file()
+        .await
+    else {
+        return Ok(None);
+    };
+    file.write(contents.as_bytes()).await.map_err(|e| {
+        tr!(
+            "theme-editor-write-failed",
+            path = file.path().display().to_string(),
+            details = e.to_string()
+        )
+    })?;
+    Ok(Some(file.path().to_path_buf()))
+}
+
+async fn import_theme() -> Result<Option<Palette>, String> {
+    let Some(file) = rfd::AsyncFileDialog::new()
+        .set_title(tr!("theme-editor-import-title"))
+        .add_filter(tr!("theme-editor-file-type"), &["toml"])
+        .pick_file()
+        .await
+    else {
+        return Ok(None);
+    };
+    let contents = String::from_utf8(file.read().await)
+        .map_err(|_| tr!("theme-editor-not-text", name = file.file_name()))?;
+    Palette::parse(&contents).map(Some)
+}
+
+/// Saving a theme adds it to `themes` and selects it for its light or dark slot.
+pub fn update(
+    state: &mut State,
+    message: Message,
+    settings: &mut Settings,
+    themes: &mut Vec<ThemeEntry>,
+) -> Task<Message> {
+    match message {
+        Message::BaseSelected(entry) => {
+            state.draft = entry.palette;
+            if entry.bundled {
+                state.draft.name = tr!("theme-editor-edited-name", name = state.draft.name.clone());
+            }
+            state.notice = None;
+            let token = state.selected;
+            select(state, token);
+            Task::none()
+        }
+        Message::NameChanged(name) => {
+            state.draft.name = name;
+            Task::none()
+        }
+        Message::DarkToggled(dark) => {
+            state.draft.dark = dark;
+            Task::none()
+        }
+        Message::TokenSelected(token) => {
+            select(state, token);
+            Task::none()
+        }
+        Message::ChannelChanged(channel, value) => {
+            let [mut r, mut g, mut b, _] = state.draft.get(state.selected).into_rgba8();
+            match channel {
+                Channel::Red => r = value,
+                Channel::Green => g = value,
+                Channel::Blue => b = value,
+            }
+            let color = Color::from_rgb8(r, g, b);
+            state.draft.set(state.selected, color);
+            state.hex_input = hex(color);
+            state.hex_error = None;
+            Task::none()
+        }
+        Message::HexInputChanged(value) => {
+            match Color::parse(value.trim()) {
+                Some(color) => {
+                    state.draft.set(state.selected, color);
+                    state.hex_error = None;
+                }
+                None => state.hex_error = Some(tr!("theme-editor-hex-invalid")),
+            }
+            state.hex_input = value;
+            Task::none()
+        }
+        Message::SavePressed => {
+            if state.draft.name.trim().is_empty() {
+                state.notice = Some(Err(tr!("theme-editor-name-required")));
+                return Task::none();
+            }
+            state.is_busy = true;
+            state.notice = None;
+            Task::perform(theme::save_user_theme(state.draft.clone()), Message::Saved)
+        }
+        Message::Saved(result) => {
+            state.is_busy = false;
+            match result {
+                Ok(entry) => {
+                    if entry.palette.dark {
+                        settings.dark_theme = entry.id.clone();
+                    } else {
+                        settings.light_theme = entry.id.clone();
+                    }
+                    state.notice = Some(Ok(tr!(
+                        "theme-editor-saved",
+                        name = entry.palette.name.clone()
+                    )));
+                    match themes.iter_mut().find(|theme| theme.id == entry.id) {
+                        Some(existing) => *existing = entry,
+                        None => themes.push(entry),
+                    }
+                }
+                Err(e) => state.notice = Some(Err(e)),
+            }
+            Task::none()
+        }
+        Message::ExportPressed => {
+            state.is_busy = true;
+            state.notice = None;
+            Task::perform(export_theme(state.draft.clone()), Message::Exported)
+        }
+        Message::Exported(result) => {
+            state.is_busy = false;
+            state.notice = match result {
+                Ok(Some(path)) => Some(Ok(tr!(
+                    "theme-editor-exported",
+                    path = path.display().to_string()
+                ))),
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            };
+            Task::none()
+        }
+        Message::ImportPressed => {
+            state.is_busy = true;
+            state.notice = None;
+            Task::perform(import_theme(), Message::Imported)
+        }
+        Message::Imported(result) => {
+            state.is_busy = false;
+            match result {
+                Ok(Some(palette)) => {
+                    state.notice = Some(Ok(tr!(
+                        "theme-editor-imported",
+                        name = palette.name.clone()
+                    )));
+                    state.draft = palette;
+                    let token = state.selected;
+                    select(state, token);
+                }
+                Ok(None) => {}
+                Err(e) => state.notice = Some(Err(e)),
+            }
+            Task::none()
+        }
+    }
+}
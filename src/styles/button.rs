@@ -4,7 +4,7 @@ use iced::{
 };
 
 use crate::{
-    colors::{AppColorBackground, AppColorMain},
+    colors::{AppColorBackground, AppColorForeground, AppColorMain, AppColorStatus},
     constants::BORDER_RADIUS_SMALL,
 };
 
@@ -25,3 +25,39 @@ pub fn button_style(_theme: &Theme, status: Status) -> button::Style {
         shadow: Shadow::default(),
     }
 }
+
+pub fn button_secondary_style(_theme: &Theme, status: Status) -> button::Style {
+    button::Style {
+        background: Some(Background::Color(match status {
+            Status::Active => Color::from(AppColorBackground::SurfaceSecondary),
+            Status::Hovered => Color::from(AppColorBackground::SurfaceTertiary),
+            Status::Pressed => Color::from(AppColorBackground::SurfaceTertiary).scale_alpha(0.85),
+            Status::Disabled => Color::from(AppColorBackground::SurfaceSecondary).scale_alpha(0.5),
+        })),
+        border: Border {
+            radius: BORDER_RADIUS_SMALL.into(),
+            width: 0.0,
+            color: Color::TRANSPARENT,
+        },
+        text_color: Color::from(AppColorForeground::Primary),
+        shadow: Shadow::default(),
+    }
+}
+
+pub fn button_danger_style(_theme: &Theme, status: Status) -> button::Style {
+    button::Style {
+        background: Some(Background::Color(match status {
+            Status::Active => Color::from(AppColorStatus::Failure),
+            Status::Hovered => Color::from(AppColorStatus::Failure).scale_alpha(0.85),
+            Status::Pressed => Color::from(AppColorStatus::Failure).scale_alpha(0.7),
+            Status::Disabled => Color::from(AppColorStatus::Failure).scale_alpha(0.35),
+        })),
+        border: Border {
+            radius: BORDER_RADIUS_SMALL.into(),
+            width: 0.0,
+            color: Color::TRANSPARENT,
+        },
+        text_color: Color::from(AppColorBackground::Primary),
+        shadow: Shadow::default(),
+    }
+}
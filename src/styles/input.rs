@@ -8,7 +8,9 @@ use crate::{
     constants::BORDER_RADIUS,
 };
 
-pub fn input_style(_theme: &Theme, _status: Status) -> text_input::Style {
+pub fn input_style(_theme: &Theme, status: Status) -> text_input::Style {
+    let value = Color::from(AppColorForeground::Secondary);
+
     text_input::Style {
         background: Background::Color(Color::from(AppColorBackground::Tertiary)),
         border: Border {
@@ -18,7 +20,10 @@ pub fn input_style(_theme: &Theme, _status: Status) -> text_input::Style {
         },
         icon: Color::TRANSPARENT,
         placeholder: Color::from(AppColorForeground::SubtextTertiary).scale_alpha(0.8),
-        value: AppColorForeground::Secondary.into(),
+        value: match status {
+            Status::Disabled => value.scale_alpha(0.5),
+            _ => value,
+        },
         selection: Color::from(AppColorMain::Primary).scale_alpha(0.2),
     }
 }
@@ -2,6 +2,6 @@ pub mod button;
 pub mod container;
 pub mod input;
 
-pub use button::button_style;
+pub use button::{button_danger_style, button_secondary_style, button_style};
 pub use container::container_style;
 pub use input::input_style;
@@ -0,0 +1,135 @@
+use iced::Color;
+
+use super::Palette;
+
+/// WCAG 2.1 AA minimum for body text.
+pub const AA_NORMAL: f32 = 4.5;
+/// WCAG 2.1 AA minimum for large text and secondary hints.
+pub const AA_LARGE: f32 = 3.0;
+
+/// A foreground/background combination the styles in `styles/` actually draw.
+pub struct ContrastPair {
+    /// The Fluent ID of what's drawn with the pair.
+    pub label: &'static str,
+    pub foreground: fn(&Palette) -> Color,
+    pub background: fn(&Palette) -> Color,
+    pub minimum: f32,
+}
+
+pub const PAIRS: [ContrastPair; 12] = [
+    ContrastPair {
+        label: "contrast-text-on-window",
+        foreground: |p| p.foreground.primary.0,
+        background: |p| p.background.tertiary.0,
+        minimum: AA_NORMAL,
+    },
+    ContrastPair {
+        label: "contrast-text-on-sidebar",
+        foreground: |p| p.foreground.primary.0,
+        background: |p| p.background.primary.0,
+        minimum: AA_NORMAL,
+    },
+    ContrastPair {
+        label: "contrast-input-text",
+        foreground: |p| p.foreground.secondary.0,
+        background: |p| p.background.tertiary.0,
+        minimum: AA_NORMAL,
+    },
+    ContrastPair {
+        label: "contrast-tertiary-text",
+        foreground: |p| p.foreground.tertiary.0,
+        background: |p| p.background.tertiary.0,
+        minimum: AA_NORMAL,
+    },
+    ContrastPair {
+        label: "contrast-subtext-on-sidebar",
+        foreground: |p| p.foreground.subtext_primary.0,
+        background: |p| p.background.primary.0,
+        minimum: AA_NORMAL,
+    },
+    ContrastPair {
+        label: "contrast-hints",
+        foreground: |p| p.foreground.subtext_secondary.0,
+        background: |p| p.background.tertiary.0,
+        minimum: AA_LARGE,
+    },
+    ContrastPair {
+        label: "contrast-placeholders",
+        foreground: |p| p.foreground.subtext_tertiary.0,
+        background: |p| p.background.tertiary.0,
+        minimum: AA_LARGE,
+    },
+    ContrastPair {
+        label: "contrast-links",
+        foreground: |p| p.main.secondary.0,
+        background: |p| p.background.tertiary.0,
+        minimum: AA_NORMAL,
+    },
+    ContrastPair {
+        label: "contrast-error-messages",
+        foreground: |p| p.status.failure.0,
+        background: |p| p.background.tertiary.0,
+        minimum: AA_NORMAL,
+    },
+    ContrastPair {
+        label: "contrast-primary-button",
+        foreground: |p| p.background.primary.0,
+        background: |p| p.main.secondary.0,
+        minimum: AA_NORMAL,
+    },
+    ContrastPair {
+        label: "contrast-danger-button",
+        foreground: |p| p.background.primary.0,
+        background: |p| p.status.failure.0,
+        minimum: AA_NORMAL,
+    },
+    ContrastPair {
+        label: "contrast-secondary-button",
+        foreground: |p| p.foreground.primary.0,
+        background: |p| p.background.surface_secondary.0,
+        minimum: AA_NORMAL,
+    },
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastIssue {
+    pub label: &'static str,
+    pub ratio: f32,
+    pub minimum: f32,
+}
+
+fn linear(channel: f32) -> f32 {
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Relative luminance as defined by WCAG, from 0 (black) to 1 (white).
+pub fn relative_luminance(color: Color) -> f32 {
+    0.2126 * linear(color.r) + 0.7152 * linear(color.g) + 0.0722 * linear(color.b)
+}
+
+/// Contrast ratio between two colors, from 1:1 to 21:1.
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+impl Palette {
+    /// Every pair in [`PAIRS`] that falls below its minimum contrast.
+    pub fn contrast_issues(&self) -> Vec<ContrastIssue> {
+        PAIRS
+            .iter()
+            .filter_map(|pair| {
+                let ratio = contrast_ratio((pair.foreground)(self), (pair.background)(self));
+                (ratio < pair.minimum).then_some(ContrastIssue {
+                    label: pair.label,
+                    ratio,
+                    minimum: pair.minimum,
+                })
+            })
+            .collect()
+    }
+}
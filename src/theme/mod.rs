@@ -0,0 +1,213 @@
+pub mod contrast;
+pub mod palette;
+pub mod system;
+
+pub use palette::{ColorToken, Palette};
+
+use iced::Theme;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    path::PathBuf,
+    sync::{LazyLock, PoisonError, RwLock},
+};
+
+use crate::{config::config_dir, i18n::tr};
+
+pub const DEFAULT_DARK_THEME: &str = "mocha";
+pub const DEFAULT_LIGHT_THEME: &str = "daylight";
+/// Used instead of the light and dark themes while high contrast is turned on.
+pub const HIGH_CONTRAST_THEME: &str = "high-contrast";
+
+const BUNDLED: [(&str, &str); 6] = [
+    ("daylight", include_str!("../../palettes/daylight.toml")),
+    (
+        "high-contrast",
+        include_str!("../../palettes/high-contrast.toml"),
+    ),
+    ("latte", include_str!("../../palettes/latte.toml")),
+    ("frappe", include_str!("../../palettes/frappe.toml")),
+    ("macchiato", include_str!("../../palettes/macchiato.toml")),
+    ("mocha", include_str!("../../palettes/mocha.toml")),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorScheme {
+    Light,
+    #[default]
+    Dark,
+}
+
+impl ColorScheme {
+    pub const ALL: [ColorScheme; 2] = [ColorScheme::Light, ColorScheme::Dark];
+
+    pub fn default_theme(self) -> &'static str {
+        match self {
+            ColorScheme::Light => DEFAULT_LIGHT_THEME,
+            ColorScheme::Dark => DEFAULT_DARK_THEME,
+        }
+    }
+}
+
+impl fmt::Display for ColorScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ColorScheme::Light => tr!("appearance-scheme-light"),
+            ColorScheme::Dark => tr!("appearance-scheme-dark"),
+        };
+        f.write_str(&label)
+    }
+}
+
+/// The palette the color enums in `colors.rs` resolve through. Views run right after
+/// every update, so swapping it re-renders the whole app with the new colors. It can't ride
+/// along in the iced [`Theme`], which only holds iced's own six colors, and most colors are
+/// picked in views that never see the theme. [`activate`] keeps the two in step.
+static ACTIVE: LazyLock<RwLock<Palette>> = LazyLock::new(|| {
+    let mocha = Palette::parse(include_str!("../../palettes/mocha.toml"));
+    RwLock::new(mocha.expect("bundled palettes are valid"))
+});
+
+pub fn with_active<R>(f: impl FnOnce(&Palette) -> R) -> R {
+    f(&ACTIVE.read().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Makes `palette` the active one and returns the iced [`Theme`] built from it, so built-in
+/// widgets (scrollbars, pick lists…) match it.
+pub fn activate(palette: Palette) -> Theme {
+    let theme = Theme::custom(palette.name.clone(), palette.to_iced());
+    *ACTIVE.write().unwrap_or_else(PoisonError::into_inner) = palette;
+    theme
+}
+
+/// A palette the user can pick, identified by its file name without extension.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeEntry {
+    pub id: String,
+    pub palette: Palette,
+    pub bundled: bool,
+}
+
+impl fmt::Display for ThemeEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.bundled {
+            write!(f, "{}", self.palette.name)
+        } else {
+            f.write_str(&tr!(
+                "appearance-theme-custom",
+                name = self.palette.name.clone()
+            ))
+        }
+    }
+}
+
+pub fn bundled_themes() -> Vec<ThemeEntry> {
+    BUNDLED
+        .iter()
+        .map(|(id, contents)| ThemeEntry {
+            id: id.to_string(),
+            palette: Palette::parse(contents).expect("bundled palettes are valid"),
+            bundled: true,
+        })
+        .collect()
+}
+
+pub fn themes_dir() -> Option<PathBuf> {
+    Some(config_dir()?.join("themes"))
+}
+
+/// Reads every `*.toml` palette in the themes directory. Files that fail to parse are skipped.
+pub async fn load_user_themes() -> Vec<ThemeEntry> {
+    let Some(dir) = themes_dir() else {
+        return Vec::new();
+    };
+    let Ok(mut entries) = tokio::fs::read_dir(&dir).await else {
+        return Vec::new();
+    };
+
+    let mut themes = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().is_none_or(|extension| extension != "toml") {
+            continue;
+        }
+        let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if BUNDLED.iter().any(|(bundled, _)| *bundled == id) {
+            tracing::warn!(
+                id,
+                "User theme has the same name as a bundled theme, skipping"
+            );
+            continue;
+        }
+        let palette = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => Palette::parse(&contents),
+            Err(e) => Err(e.to_string()),
+        };
+        match palette {
+            Ok(palette) => {
+                for issue in palette.contrast_issues() {
+                    tracing::warn!(
+                        id,
+                        pair = issue.label,
+                        ratio = issue.ratio,
+                        minimum = issue.minimum,
+                        "User theme has low contrast"
+                    );
+                }
+                themes.push(ThemeEntry {
+                    id: id.to_string(),
+                    palette,
+                    bundled: false,
+                })
+            }
+            Err(e) => tracing::warn!(path = %path.display(), error = %e, "Skipping user theme"),
+        }
+    }
+    themes.sort_by(|a, b| a.palette.name.cmp(&b.palette.name));
+    themes
+}
+
+/// Turns a palette name into the file name it is saved under in the themes directory.
+pub fn theme_id(name: &str) -> String {
+    let slug = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-");
+    let slug = if slug.is_empty() {
+        "custom".to_string()
+    } else {
+        slug
+    };
+    if BUNDLED.iter().any(|(bundled, _)| *bundled == slug) {
+        format!("{slug}-custom")
+    } else {
+        slug
+    }
+}
+
+/// Writes the palette into the themes directory, replacing a theme with the same id.
+pub async fn save_user_theme(palette: Palette) -> Result<ThemeEntry, String> {
+    let dir = themes_dir().ok_or("No config directory on this system")?;
+    let id = theme_id(&palette.name);
+    let contents = palette.to_toml()?;
+
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(format!("{id}.toml"));
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    tracing::info!(id, "Saved user theme");
+    Ok(ThemeEntry {
+        id,
+        palette,
+        bundled: false,
+    })
+}
@@ -0,0 +1,238 @@
+use iced::{Color, theme};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    colors::{AppColorBackground, AppColorForeground, AppColorMain, AppColorStatus},
+    i18n::tr,
+};
+
+/// A [`Color`] written as `#rrggbb` (or `#rrggbbaa`) in palette files.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct HexColor(pub Color);
+
+impl TryFrom<String> for HexColor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Color::parse(&value)
+            .map(HexColor)
+            .ok_or_else(|| format!("`{}` is not a hex color", value))
+    }
+}
+
+impl From<HexColor> for String {
+    fn from(value: HexColor) -> Self {
+        let [r, g, b, a] = value.0.into_rgba8();
+        if a == u8::MAX {
+            format!("#{r:02x}{g:02x}{b:02x}")
+        } else {
+            format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MainColors {
+    pub primary: HexColor,
+    pub secondary: HexColor,
+    pub tertiary: HexColor,
+    pub accent_primary: HexColor,
+    pub accent_secondary: HexColor,
+    pub accent_tertiary: HexColor,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusColors {
+    pub success: HexColor,
+    pub warning: HexColor,
+    pub failure: HexColor,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackgroundColors {
+    pub primary: HexColor,
+    pub secondary: HexColor,
+    pub tertiary: HexColor,
+    pub surface_primary: HexColor,
+    pub surface_secondary: HexColor,
+    pub surface_tertiary: HexColor,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForegroundColors {
+    pub primary: HexColor,
+    pub secondary: HexColor,
+    pub tertiary: HexColor,
+    pub subtext_primary: HexColor,
+    pub subtext_secondary: HexColor,
+    pub subtext_tertiary: HexColor,
+}
+
+/// Addresses any semantic color in a [`Palette`], for code that edits them generically.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorToken {
+    Main(AppColorMain),
+    Status(AppColorStatus),
+    Background(AppColorBackground),
+    Foreground(AppColorForeground),
+}
+
+impl ColorToken {
+    /// Every token, grouped the same way as the palette file.
+    pub fn all() -> impl Iterator<Item = ColorToken> {
+        AppColorMain::ALL
+            .into_iter()
+            .map(ColorToken::Main)
+            .chain(
+                AppColorBackground::ALL
+                    .into_iter()
+                    .map(ColorToken::Background),
+            )
+            .chain(
+                AppColorForeground::ALL
+                    .into_iter()
+                    .map(ColorToken::Foreground),
+            )
+            .chain(AppColorStatus::ALL.into_iter().map(ColorToken::Status))
+    }
+
+    pub fn group(self) -> String {
+        tr!(match self {
+            ColorToken::Main(_) => "color-group-main",
+            ColorToken::Status(_) => "color-group-status",
+            ColorToken::Background(_) => "color-group-background",
+            ColorToken::Foreground(_) => "color-group-foreground",
+        })
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            ColorToken::Main(color) => color.label(),
+            ColorToken::Status(color) => color.label(),
+            ColorToken::Background(color) => color.label(),
+            ColorToken::Foreground(color) => color.label(),
+        }
+    }
+}
+
+/// Every semantic color from `colors.rs`, as stored in a palette file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Palette {
+    pub name: String,
+    pub dark: bool,
+    pub main: MainColors,
+    pub status: StatusColors,
+    pub background: BackgroundColors,
+    pub foreground: ForegroundColors,
+}
+
+impl Palette {
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        toml::from_str(contents).map_err(|e| format!("Invalid palette: {}", e))
+    }
+
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string_pretty(self).map_err(|e| format!("Failed to serialize palette: {}", e))
+    }
+
+    pub fn main(&self, color: AppColorMain) -> Color {
+        match color {
+            AppColorMain::Primary => self.main.primary.0,
+            AppColorMain::Secondary => self.main.secondary.0,
+            AppColorMain::Tertiary => self.main.tertiary.0,
+            AppColorMain::AccentPrimary => self.main.accent_primary.0,
+            AppColorMain::AccentSecondary => self.main.accent_secondary.0,
+            AppColorMain::AccentTertiary => self.main.accent_tertiary.0,
+        }
+    }
+
+    pub fn status(&self, color: AppColorStatus) -> Color {
+        match color {
+            AppColorStatus::Success => self.status.success.0,
+            AppColorStatus::Warning => self.status.warning.0,
+            AppColorStatus::Failure => self.status.failure.0,
+        }
+    }
+
+    pub fn background(&self, color: AppColorBackground) -> Color {
+        match color {
+            AppColorBackground::Primary => self.background.primary.0,
+            AppColorBackground::Secondary => self.background.secondary.0,
+            AppColorBackground::Tertiary => self.background.tertiary.0,
+            AppColorBackground::SurfacePrimary => self.background.surface_primary.0,
+            AppColorBackground::SurfaceSecondary => self.background.surface_secondary.0,
+            AppColorBackground::SurfaceTertiary => self.background.surface_tertiary.0,
+        }
+    }
+
+    pub fn foreground(&self, color: AppColorForeground) -> Color {
+        match color {
+            AppColorForeground::Primary => self.foreground.primary.0,
+            AppColorForeground::Secondary => self.foreground.secondary.0,
+            AppColorForeground::Tertiary => self.foreground.tertiary.0,
+            AppColorForeground::SubtextPrimary => self.foreground.subtext_primary.0,
+            AppColorForeground::SubtextSecondary => self.foreground.subtext_secondary.0,
+            AppColorForeground::SubtextTertiary => self.foreground.subtext_tertiary.0,
+        }
+    }
+
+    pub fn get(&self, token: ColorToken) -> Color {
+        match token {
+            ColorToken::Main(color) => self.main(color),
+            ColorToken::Status(color) => self.status(color),
+            ColorToken::Background(color) => self.background(color),
+            ColorToken::Foreground(color) => self.foreground(color),
+        }
+    }
+
+    pub fn set(&mut self, token: ColorToken, color: Color) {
+        let slot = match token {
+            ColorToken::Main(AppColorMain::Primary) => &mut self.main.primary,
+            ColorToken::Main(AppColorMain::Secondary) => &mut self.main.secondary,
+            ColorToken::Main(AppColorMain::Tertiary) => &mut self.main.tertiary,
+            ColorToken::Main(AppColorMain::AccentPrimary) => &mut self.main.accent_primary,
+            ColorToken::Main(AppColorMain::AccentSecondary) => &mut self.main.accent_secondary,
+            ColorToken::Main(AppColorMain::AccentTertiary) => &mut self.main.accent_tertiary,
+            ColorToken::Status(AppColorStatus::Success) => &mut self.status.success,
+            ColorToken::Status(AppColorStatus::Warning) => &mut self.status.warning,
+            ColorToken::Status(AppColorStatus::Failure) => &mut self.status.failure,
+            ColorToken::Background(AppColorBackground::Primary) => &mut self.background.primary,
+            ColorToken::Background(AppColorBackground::Secondary) => &mut self.background.secondary,
+            ColorToken::Background(AppColorBackground::Tertiary) => &mut self.background.tertiary,
+            ColorToken::Background(AppColorBackground::SurfacePrimary) => {
+                &mut self.background.surface_primary
+            }
+            ColorToken::Background(AppColorBackground::SurfaceSecondary) => {
+                &mut self.background.surface_secondary
+            }
+            ColorToken::Background(AppColorBackground::SurfaceTertiary) => {
+                &mut self.background.surface_tertiary
+            }
+            ColorToken::Foreground(AppColorForeground::Primary) => &mut self.foreground.primary,
+            ColorToken::Foreground(AppColorForeground::Secondary) => &mut self.foreground.secondary,
+            ColorToken::Foreground(AppColorForeground::Tertiary) => &mut self.foreground.tertiary,
+            ColorToken::Foreground(AppColorForeground::SubtextPrimary) => {
+                &mut self.foreground.subtext_primary
+            }
+            ColorToken::Foreground(AppColorForeground::SubtextSecondary) => {
+                &mut self.foreground.subtext_secondary
+            }
+            ColorToken::Foreground(AppColorForeground::SubtextTertiary) => {
+                &mut self.foreground.subtext_tertiary
+            }
+        };
+        *slot = HexColor(color);
+    }
+
+    /// The subset iced needs to style its built-in widgets to match.
+    pub fn to_iced(&self) -> theme::Palette {
+        theme::Palette {
+            background: self.background.tertiary.0,
+            text: self.foreground.primary.0,
+            primary: self.main.secondary.0,
+            success: self.status.success.0,
+            danger: self.status.failure.0,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use iced::Subscription;
+
+use super::ColorScheme;
+
+/// Follows the desktop's preferred color scheme. Emits `None` when the desktop has no
+/// preference, and nothing at all when the preference can't be read.
+pub fn watch() -> Subscription<Option<ColorScheme>> {
+    #[cfg(target_os = "linux")]
+    {
+        Subscription::run(portal::color_scheme)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Subscription::none()
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod portal {
+    use ashpd::desktop::settings::{ColorScheme as PortalScheme, Settings};
+    use iced::{
+        futures::{SinkExt, Stream, StreamExt},
+        stream,
+    };
+
+    use super::ColorScheme;
+
+    fn convert(scheme: PortalScheme) -> Option<ColorScheme> {
+        match scheme {
+            PortalScheme::PreferDark => Some(ColorScheme::Dark),
+            PortalScheme::PreferLight => Some(ColorScheme::Light),
+            PortalScheme::NoPreference => None,
+        }
+    }
+
+    /// Reads `org.freedesktop.appearance color-scheme` from the settings portal and
+    /// keeps listening for changes to it.
+    pub fn color_scheme() -> impl Stream<Item = Option<ColorScheme>> {
+        stream::channel(10, |mut output| async move {
+            let settings = match Settings::new().await {
+                Ok(settings) => settings,
+                Err(e) => {
+                    tracing::info!(error = %e, "Settings portal unavailable, not following the system appearance");
+                    return;
+                }
+            };
+
+            match settings.color_scheme().await {
+                Ok(scheme) => {
+                    let _ = output.send(convert(scheme)).await;
+                }
+                Err(e) => tracing::info!(error = %e, "Failed to read the system color scheme"),
+            }
+
+            let mut changes = match settings.receive_color_scheme_changed().await {
+                Ok(changes) => changes.boxed(),
+                Err(e) => {
+                    tracing::info!(error = %e, "Not listening for system color scheme changes");
+                    return;
+                }
+            };
+            while let Some(scheme) = changes.next().await {
+                tracing::debug!(?scheme, "System color scheme changed");
+                let _ = output.send(convert(scheme)).await;
+            }
+        })
+    }
+}
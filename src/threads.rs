@@ -0,0 +1,159 @@
+use chrono::{DateTime, Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    constants::API_URL,
+    i18n::tr,
+    screens::app_screen::Composer,
+    utils::{send_empty, send_json},
+};
+
+/// How long a thread may go without messages before it's archived.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AutoArchive {
+    Hour,
+    #[default]
+    Day,
+    ThreeDays,
+    Week,
+}
+
+impl AutoArchive {
+    pub const ALL: [AutoArchive; 4] = [
+        AutoArchive::Hour,
+        AutoArchive::Day,
+        AutoArchive::ThreeDays,
+        AutoArchive::Week,
+    ];
+
+    fn minutes(self) -> i64 {
+        match self {
+            AutoArchive::Hour => 60,
+            AutoArchive::Day => 24 * 60,
+            AutoArchive::ThreeDays => 3 * 24 * 60,
+            AutoArchive::Week => 7 * 24 * 60,
+        }
+    }
+}
+
+impl fmt::Display for AutoArchive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            AutoArchive::Hour => tr!("thread-archive-hour"),
+            AutoArchive::Day => tr!("thread-archive-day"),
+            AutoArchive::ThreeDays => tr!("thread-archive-three-days"),
+            AutoArchive::Week => tr!("thread-archive-week"),
+        };
+        f.write_str(&label)
+    }
+}
+
+/// A side conversation started from a message. Its messages are kept with the channel's,
+/// marked with the thread's ID.
+#[derive(Debug, Clone)]
+pub struct Thread {
+    pub id: String,
+    /// `None` for a thread that isn't on the server, started from a message that isn't.
+    pub channel_id: Option<String>,
+    /// The message the thread was started from.
+    pub parent_id: String,
+    pub name: String,
+    pub archived: bool,
+    pub auto_archive: AutoArchive,
+    /// When the last message was posted, or the thread was started.
+    pub last_activity: DateTime<Local>,
+    /// Messages posted while the thread wasn't open.
+    pub unread: usize,
+    /// What's being written in the thread's composer.
+    pub composer: Composer,
+}
+
+impl Thread {
+    pub fn new(
+        id: String,
+        channel_id: Option<String>,
+        parent_id: String,
+        name: String,
+        auto_archive: AutoArchive,
+    ) -> Self {
+        Self {
+            id,
+            channel_id,
+            parent_id,
+            name,
+            archived: false,
+            auto_archive,
+            last_activity: Local::now(),
+            unread: 0,
+            composer: Composer::default(),
+        }
+    }
+
+    /// Whether the thread has gone quiet for longer than its [`AutoArchive`].
+    pub fn is_idle(&self, now: DateTime<Local>) -> bool {
+        now - self.last_activity > TimeDelta::minutes(self.auto_archive.minutes())
+    }
+}
+
+#[derive(Deserialize)]
+struct StartedThread {
+    id: String,
+}
+
+#[derive(Serialize)]
+struct StartThreadRequest {
+    name: String,
+    auto_archive_duration: i64,
+}
+
+#[derive(Serialize)]
+struct SaveThreadRequest {
+    archived: bool,
+    auto_archive_duration: i64,
+}
+
+/// Starts a thread from a message on the server, returns the thread's ID.
+pub async fn start_thread(
+    access_token: String,
+    channel_id: String,
+    message_id: String,
+    name: String,
+    auto_archive: AutoArchive,
+) -> Result<String, String> {
+    let client = reqwest::Client::new();
+    let started: StartedThread = send_json(
+        client
+            .post(format!(
+                "{API_URL}/channels/{channel_id}/messages/{message_id}/threads"
+            ))
+            .bearer_auth(access_token)
+            .json(&StartThreadRequest {
+                name,
+                auto_archive_duration: auto_archive.minutes(),
+            }),
+    )
+    .await?;
+    Ok(started.id)
+}
+
+/// Archives or unarchives a thread and sets how soon it archives itself.
+pub async fn save_thread(
+    access_token: String,
+    thread_id: String,
+    archived: bool,
+    auto_archive: AutoArchive,
+) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    send_empty(
+        client
+            .patch(format!("{API_URL}/channels/{thread_id}"))
+            .bearer_auth(access_token)
+            .json(&SaveThreadRequest {
+                archived,
+                auto_archive_duration: auto_archive.minutes(),
+            }),
+    )
+    .await
+}
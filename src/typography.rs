@@ -0,0 +1,220 @@
+use iced::{
+    Font,
+    font::Weight,
+    widget::{Text, text, text::IntoFragment},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    sync::{LazyLock, PoisonError, RwLock},
+};
+
+use crate::i18n::tr;
+
+/// The display face used for screen and section headings.
+pub const DISPLAY: Font = Font::with_name("Rubik Mono One");
+pub const ROBOTO: Font = Font::with_name("Roboto");
+
+pub const DEFAULT_BASE_SIZE: u16 = 14;
+pub const MIN_BASE_SIZE: u16 = 10;
+pub const MAX_BASE_SIZE: u16 = 22;
+
+/// Steps of the type scale. Sizes are relative to the base font size from the settings so
+/// changing it resizes every text style together.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TypeScale {
+    Heading,
+    Title,
+    Body,
+    Label,
+    Caption,
+    Small,
+}
+
+impl TypeScale {
+    fn ratio(self) -> f32 {
+        match self {
+            TypeScale::Heading => 24.0 / 14.0,
+            TypeScale::Title => 18.0 / 14.0,
+            TypeScale::Body => 1.0,
+            TypeScale::Label => 13.0 / 14.0,
+            TypeScale::Caption => 12.0 / 14.0,
+            TypeScale::Small => 10.0 / 14.0,
+        }
+    }
+
+    pub fn size(self) -> f32 {
+        (f32::from(active().base_size) * self.ratio()).round()
+    }
+}
+
+/// Every font file registered with iced at startup.
+pub const FONTS: [&[u8]; 33] = [
+    include_bytes!("../fonts/RubikMonoOne-Regular.ttf"),
+    include_bytes!("../fonts/static/Roboto-Thin.ttf"),
+    include_bytes!("../fonts/static/Roboto-ThinItalic.ttf"),
+    include_bytes!("../fonts/static/Roboto-ExtraLight.ttf"),
+    include_bytes!("../fonts/static/Roboto-ExtraLightItalic.ttf"),
+    include_bytes!("../fonts/static/Roboto-Light.ttf"),
+    include_bytes!("../fonts/static/Roboto-LightItalic.ttf"),
+    include_bytes!("../fonts/static/Roboto-Regular.ttf"),
+    include_bytes!("../fonts/static/Roboto-Italic.ttf"),
+    include_bytes!("../fonts/static/Roboto-Medium.ttf"),
+    include_bytes!("../fonts/static/Roboto-MediumItalic.ttf"),
+    include_bytes!("../fonts/static/Roboto-SemiBold.ttf"),
+    include_bytes!("../fonts/static/Roboto-SemiBoldItalic.ttf"),
+    include_bytes!("../fonts/static/Roboto-Bold.ttf"),
+    include_bytes!("../fonts/static/Roboto-BoldItalic.ttf"),
+    include_bytes!("../fonts/static/Roboto-ExtraBold.ttf"),
+    include_bytes!("../fonts/static/Roboto-ExtraBoldItalic.ttf"),
+    include_bytes!("../fonts/static/Roboto-Black.ttf"),
+    include_bytes!("../fonts/static/Roboto-BlackItalic.ttf"),
+    include_bytes!("../fonts/static/Roboto_Condensed-Regular.ttf"),
+    include_bytes!("../fonts/static/Roboto_Condensed-Italic.ttf"),
+    include_bytes!("../fonts/static/Roboto_Condensed-Medium.ttf"),
+    include_bytes!("../fonts/static/Roboto_Condensed-MediumItalic.ttf"),
+    include_bytes!("../fonts/static/Roboto_Condensed-SemiBold.ttf"),
+    include_bytes!("../fonts/static/Roboto_Condensed-Bold.ttf"),
+    include_bytes!("../fonts/static/Roboto_Condensed-BoldItalic.ttf"),
+    include_bytes!("../fonts/static/Roboto_SemiCondensed-Regular.ttf"),
+    include_bytes!("../fonts/static/Roboto_SemiCondensed-Italic.ttf"),
+    include_bytes!("../fonts/static/Roboto_SemiCondensed-Medium.ttf"),
+    include_bytes!("../fonts/static/Roboto_SemiCondensed-MediumItalic.ttf"),
+    include_bytes!("../fonts/static/Roboto_SemiCondensed-SemiBold.ttf"),
+    include_bytes!("../fonts/static/Roboto_SemiCondensed-Bold.ttf"),
+    include_bytes!("../fonts/static/Roboto_SemiCondensed-BoldItalic.ttf"),
+];
+
+/// A font family the user can pick in the appearance settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FontChoice {
+    Roboto,
+    RobotoCondensed,
+    RobotoSemiCondensed,
+    RubikMono,
+    SystemSans,
+    SystemMono,
+}
+
+impl FontChoice {
+    pub const PROPORTIONAL: [FontChoice; 4] = [
+        FontChoice::Roboto,
+        FontChoice::RobotoCondensed,
+        FontChoice::RobotoSemiCondensed,
+        FontChoice::SystemSans,
+    ];
+    pub const MONOSPACE: [FontChoice; 2] = [FontChoice::SystemMono, FontChoice::RubikMono];
+
+    pub fn font(self) -> Font {
+        match self {
+            FontChoice::Roboto => ROBOTO,
+            FontChoice::RobotoCondensed => Font::with_name("Roboto Condensed"),
+            FontChoice::RobotoSemiCondensed => Font::with_name("Roboto SemiCondensed"),
+            FontChoice::RubikMono => DISPLAY,
+            FontChoice::SystemSans => Font::DEFAULT,
+            FontChoice::SystemMono => Font::MONOSPACE,
+        }
+    }
+}
+
+impl fmt::Display for FontChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            FontChoice::Roboto => "Roboto".to_string(),
+            FontChoice::RobotoCondensed => "Roboto Condensed".to_string(),
+            FontChoice::RobotoSemiCondensed => "Roboto SemiCondensed".to_string(),
+            FontChoice::RubikMono => "Rubik Mono One".to_string(),
+            FontChoice::SystemSans => tr!("appearance-font-system-sans"),
+            FontChoice::SystemMono => tr!("appearance-font-system-mono"),
+        };
+        f.write_str(&label)
+    }
+}
+
+/// The fonts the semantic text styles below resolve to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Typography {
+    pub ui: FontChoice,
+    pub message: FontChoice,
+    pub mono: FontChoice,
+    pub base_size: u16,
+}
+
+impl Default for Typography {
+    fn default() -> Self {
+        Self {
+            ui: FontChoice::Roboto,
+            message: FontChoice::Roboto,
+            mono: FontChoice::SystemMono,
+            base_size: DEFAULT_BASE_SIZE,
+        }
+    }
+}
+
+/// Like the active palette in `theme`, read by every style helper during `view`.
+static ACTIVE: LazyLock<RwLock<Typography>> = LazyLock::new(|| RwLock::new(Typography::default()));
+
+fn active() -> Typography {
+    *ACTIVE.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub fn set_active(typography: Typography) {
+    *ACTIVE.write().unwrap_or_else(PoisonError::into_inner) = typography;
+}
+
+pub fn ui_font() -> Font {
+    active().ui.font()
+}
+
+pub fn message_font() -> Font {
+    active().message.font()
+}
+
+pub fn mono_font() -> Font {
+    active().mono.font()
+}
+
+/// Screen titles, e.g. "Log in".
+pub fn heading<'a>(content: impl IntoFragment<'a>) -> Text<'a> {
+    text(content).font(DISPLAY).size(TypeScale::Heading.size())
+}
+
+/// Section titles inside a screen, e.g. "Devices".
+pub fn title<'a>(content: impl IntoFragment<'a>) -> Text<'a> {
+    text(content).font(DISPLAY).size(TypeScale::Title.size())
+}
+
+pub fn body<'a>(content: impl IntoFragment<'a>) -> Text<'a> {
+    text(content).font(ui_font()).size(TypeScale::Body.size())
+}
+
+/// Form field names and other short labels.
+pub fn label<'a>(content: impl IntoFragment<'a>) -> Text<'a> {
+    text(content)
+        .font(Font {
+            weight: Weight::Medium,
+            ..ui_font()
+        })
+        .size(TypeScale::Label.size())
+}
+
+/// Hints, links and errors under or beside the main content.
+pub fn caption<'a>(content: impl IntoFragment<'a>) -> Text<'a> {
+    text(content)
+        .font(ui_font())
+        .size(TypeScale::Caption.size())
+}
+
+/// The body of a chat message.
+pub fn message<'a>(content: impl IntoFragment<'a>) -> Text<'a> {
+    text(content)
+        .font(message_font())
+        .size(TypeScale::Body.size())
+}
+
+pub fn code<'a>(content: impl IntoFragment<'a>) -> Text<'a> {
+    text(content)
+        .font(mono_font())
+        .size(TypeScale::Label.size())
+}
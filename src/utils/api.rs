@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use reqwest::{RequestBuilder, Response, StatusCode, header::RETRY_AFTER};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::Instrument;
+use turbo::errors::ResponseError;
+
+use crate::i18n::{self, tr};
+
+/// Used when the server rate limits us without saying for how long.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(30);
+/// Used when the server locks the account without saying for how long.
+const DEFAULT_LOCKOUT: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Clone, Error)]
+pub enum ApiError {
+    #[error("{}", tr!("error-network", details = .0.as_str()))]
+    Network(String),
+    #[error("{}", tr!("error-rate-limited"))]
+    RateLimited { retry_after: Duration },
+    #[error("{message}")]
+    Locked {
+        message: String,
+        retry_after: Duration,
+    },
+    #[error("{0}")]
+    Response(String),
+}
+
+impl ApiError {
+    /// How long the server asked us to wait before trying again, if it did.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ApiError::RateLimited { retry_after } | ApiError::Locked { retry_after, .. } => {
+                Some(*retry_after)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Reads `Retry-After` as either a number of seconds or an HTTP date.
+fn parse_retry_after(res: &Response) -> Option<Duration> {
+    let value = res.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&Utc) - Utc::now()).to_std().ok()
+}
+
+async fn error_from_response(res: Response) -> ApiError {
+    let status = res.status();
+    tracing::debug!(%status, "Request was rejected");
+    let retry_after = parse_retry_after(&res);
+    let message = res
+        .json::<ResponseError>()
+        .await
+        .map(|body| i18n::server_error(&body.error.code.to_string(), &body.error.message));
+
+    match status {
+        StatusCode::TOO_MANY_REQUESTS => ApiError::RateLimited {
+            retry_after: retry_after.unwrap_or(DEFAULT_RETRY_AFTER),
+        },
+        StatusCode::LOCKED => ApiError::Locked {
+            message: message.unwrap_or_else(|_| tr!("error-account-locked")),
+            retry_after: retry_after.unwrap_or(DEFAULT_LOCKOUT),
+        },
+        _ => ApiError::Response(
+            message.unwrap_or_else(|e| tr!("error-unreadable-error", details = e.to_string())),
+        ),
+    }
+}
+
+/// Sends the request inside a span named after its method and path. The query string
+/// is left out of the span since it may carry tokens.
+async fn execute(request: RequestBuilder) -> Result<Response, ApiError> {
+    let (client, request) = request.build_split();
+    let request = request.map_err(|e| ApiError::Network(e.to_string()))?;
+    let span = tracing::info_span!(
+        "http_request",
+        method = %request.method(),
+        path = %request.url().path(),
+    );
+
+    async move {
+        let started = std::time::Instant::now();
+        let res = client.execute(request).await.map_err(|e| {
+            tracing::warn!(error = %e, "Request failed");
+            ApiError::Network(e.to_string())
+        })?;
+        tracing::debug!(
+            status = %res.status(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Response received"
+        );
+        Ok(res)
+    }
+    .instrument(span)
+    .await
+}
+
+/// Sends the request and parses the body as `T`, keeping rate limit and lockout details on failure.
+pub async fn try_send_json<T: DeserializeOwned>(request: RequestBuilder) -> Result<T, ApiError> {
+    let res = execute(request).await?;
+
+    if res.status().is_success() {
+        res.json::<T>().await.map_err(|e| {
+            ApiError::Response(tr!("error-unreadable-response", details = e.to_string()))
+        })
+    } else {
+        Err(error_from_response(res).await)
+    }
+}
+
+/// Same as [`try_send_json`] for endpoints that answer with an empty body.
+pub async fn try_send_empty(request: RequestBuilder) -> Result<(), ApiError> {
+    let res = execute(request).await?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(error_from_response(res).await)
+    }
+}
+
+/// Sends the request and parses the body as `T`, or the server's error message on failure.
+pub async fn send_json<T: DeserializeOwned>(request: RequestBuilder) -> Result<T, String> {
+    try_send_json(request).await.map_err(|e| e.to_string())
+}
+
+/// Same as [`send_json`] for endpoints that answer with an empty body.
+pub async fn send_empty(request: RequestBuilder) -> Result<(), String> {
+    try_send_empty(request).await.map_err(|e| e.to_string())
+}
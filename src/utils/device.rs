@@ -0,0 +1,14 @@
+/// Human readable name of this machine, sent on login so sessions can be told apart.
+pub fn device_name() -> String {
+    let name = whoami::devicename();
+    if name.trim().is_empty() {
+        "Unknown device".to_string()
+    } else {
+        name
+    }
+}
+
+/// Operating system and architecture, e.g. "Fedora Linux 42 (x86_64)".
+pub fn platform() -> String {
+    format!("{} ({})", whoami::distro(), std::env::consts::ARCH)
+}
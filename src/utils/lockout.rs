@@ -0,0 +1,32 @@
+use std::time::{Duration, Instant};
+
+use crate::i18n::tr;
+
+/// Countdown started when the server rate limits or locks us out of a form.
+#[derive(Debug, Clone, Copy)]
+pub struct Lockout {
+    until: Instant,
+    remaining: Duration,
+}
+
+impl Lockout {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            until: Instant::now() + duration,
+            remaining: duration,
+        }
+    }
+
+    /// Updates the remaining time and returns `false` once the lockout is over.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        self.remaining = self.until.saturating_duration_since(now);
+        !self.remaining.is_zero()
+    }
+
+    /// Button label such as "Try again in 1:05".
+    pub fn label(&self) -> String {
+        let seconds = self.remaining.as_secs() + u64::from(self.remaining.subsec_nanos() > 0);
+        let time = format!("{}:{:02}", seconds / 60, seconds % 60);
+        tr!("lockout-retry", time = time)
+    }
+}
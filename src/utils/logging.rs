@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::{EnvFilter, fmt, prelude::*};
+
+/// Used when `RUST_LOG` is not set.
+const DEFAULT_FILTER: &str = "warn,fictional_potato=info";
+const MAX_LOG_FILES: usize = 7;
+
+fn env_filter() -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER))
+}
+
+/// `$XDG_STATE_HOME/fictional-potato/logs`, or the platform equivalent.
+fn log_dir() -> Option<PathBuf> {
+    let dir = dirs::state_dir()
+        .or_else(dirs::data_local_dir)?
+        .join("fictional-potato")
+        .join("logs");
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
+
+/// Logs to stdout and to a daily rolling file. Keep the returned guard alive until
+/// exit, dropping it flushes and closes the file writer.
+pub fn init_logging() -> Option<WorkerGuard> {
+    let appender = log_dir().and_then(|dir| {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix("fictional-potato")
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(dir)
+            .ok()
+    });
+    let (file_layer, guard) = match appender {
+        Some(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            let layer = fmt::layer()
+                .with_ansi(false)
+                .with_writer(writer)
+                .with_filter(env_filter());
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(fmt::layer().with_filter(env_filter()))
+        .with(file_layer)
+        .init();
+
+    if guard.is_none() {
+        tracing::warn!("Could not open the log directory, logging to stdout only");
+    }
+    guard
+}
@@ -1,5 +1,15 @@
+pub mod api;
+pub mod device;
+pub mod lockout;
+pub mod logging;
+pub mod redacted;
 pub mod secure_storage;
+pub mod session;
 
-pub use secure_storage::{
-    get_token_from_secure_storage, get_user_with_token, set_token_from_secure_storage,
-};
+pub use api::{ApiError, send_empty, send_json, try_send_json};
+pub use device::{device_name, platform};
+pub use lockout::Lockout;
+pub use logging::init_logging;
+pub use redacted::Redacted;
+pub use secure_storage::{get_user_with_token, set_token_from_secure_storage};
+pub use session::Session;
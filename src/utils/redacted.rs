@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use validator::ValidateLength;
+
+/// Wraps secrets such as tokens and passwords so they never show up in `Debug` output or logs.
+/// Use [`Redacted::expose`] where the actual value is needed. It serializes as the value itself,
+/// which validator keeps in its errors, see [`crate::i18n::field_error`].
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Redacted<T>(T);
+
+impl<T> Redacted<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Redacted<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> fmt::Debug for Redacted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[redacted]")
+    }
+}
+
+impl<T: ValidateLength<u64>> ValidateLength<u64> for Redacted<T> {
+    fn length(&self) -> Option<u64> {
+        self.0.length()
+    }
+}
@@ -1,5 +1,11 @@
 use keyring::Entry;
-use turbo::{auth::AuthResponse, errors::ResponseError, types::PublicUser};
+use std::fmt;
+use turbo::auth::AuthResponse;
+
+use crate::{
+    constants::API_URL,
+    utils::{Session, send_json},
+};
 
 pub enum SecureStoreError {
     EntryCreation(keyring::Error),
@@ -8,56 +14,54 @@ pub enum SecureStoreError {
     CredentialDeletion(keyring::Error),
 }
 
+impl fmt::Display for SecureStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecureStoreError::EntryCreation(e) => write!(f, "Failed to open the keyring: {e}"),
+            SecureStoreError::CredentialRetrieving(e) => write!(f, "Failed to read the token: {e}"),
+            SecureStoreError::CredentialWriting(e) => write!(f, "Failed to save the token: {e}"),
+            SecureStoreError::CredentialDeletion(e) => write!(f, "Failed to delete the token: {e}"),
+        }
+    }
+}
+
 pub fn get_token_from_secure_storage() -> Result<String, SecureStoreError> {
-    let entry = Entry::new("fictional-potato", "refresh_token")
-        .map_err(|e| SecureStoreError::EntryCreation(e))?;
+    let entry =
+        Entry::new("fictional-potato", "refresh_token").map_err(SecureStoreError::EntryCreation)?;
     let token = entry
         .get_password()
-        .map_err(|e| SecureStoreError::CredentialRetrieving(e))?;
+        .map_err(SecureStoreError::CredentialRetrieving)?;
     Ok(token)
 }
 
 pub fn set_token_from_secure_storage(token: Option<String>) -> Result<(), SecureStoreError> {
-    let entry = Entry::new("fictional-potato", "refresh_token")
-        .map_err(|e| SecureStoreError::EntryCreation(e))?;
-    if token.is_none() {
-        entry
+    let entry =
+        Entry::new("fictional-potato", "refresh_token").map_err(SecureStoreError::EntryCreation)?;
+    match token {
+        Some(token) => entry
+            .set_password(&token)
+            .map_err(SecureStoreError::CredentialWriting),
+        None => entry
             .delete_credential()
-            .map_err(|e| SecureStoreError::CredentialDeletion(e))?;
-    } else {
-        entry
-            .set_password(token.unwrap().as_str())
-            .map_err(|e| SecureStoreError::CredentialWriting(e))?;
+            .map_err(SecureStoreError::CredentialDeletion),
     }
-    Ok(())
 }
 
-pub async fn get_user_with_token() -> Result<PublicUser, String> {
-    println!("Attempting to get user from a refresh_token");
-    let token = get_token_from_secure_storage().map_err(|e| "No!")?;
+#[tracing::instrument]
+pub async fn get_user_with_token() -> Result<Session, String> {
+    tracing::info!("Attempting to get user from a refresh_token");
+    let token = get_token_from_secure_storage().map_err(|e| {
+        tracing::info!(error = %e, "No refresh token stored");
+        "No refresh token stored"
+    })?;
 
     let client = reqwest::Client::new();
-    let res = client
-        .post("http://localhost:8080/auth/refresh")
-        .bearer_auth(token)
-        .send()
-        .await
-        .map_err(|e| format!("Network error: {}", e))?;
-
-    if res.status().is_success() {
-        let success_body = res
-            .json::<AuthResponse>()
-            .await
-            .map_err(|e| format!("Failed to parse successful login: {}", e))?;
-
-        println!("res: {success_body:?}");
-        set_token_from_secure_storage(Some(success_body.refresh_token)).map_err(|e| "Errrrrr")?;
-        Ok(success_body.user)
-    } else {
-        let error_body = res
-            .json::<ResponseError>()
-            .await
-            .map_err(|e| format!("Failed to parse error response: {}", e))?;
-        Err(error_body.error.message)
-    }
+    let auth = send_json::<AuthResponse>(
+        client
+            .post(format!("{API_URL}/auth/refresh"))
+            .bearer_auth(token),
+    )
+    .await?;
+
+    Session::store(auth)
 }
@@ -0,0 +1,24 @@
+use turbo::{auth::AuthResponse, types::PublicUser};
+
+use crate::utils::{Redacted, set_token_from_secure_storage};
+
+/// The logged in user along with the access token used for authenticated requests.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub user: PublicUser,
+    pub access_token: Redacted<String>,
+}
+
+impl Session {
+    /// Saves the refresh token to secure storage and keeps the rest of the response.
+    pub fn store(auth: AuthResponse) -> Result<Self, String> {
+        set_token_from_secure_storage(Some(auth.refresh_token)).map_err(|e| {
+            tracing::warn!(error = %e, "Failed to save the refresh token");
+            "Failed to save the refresh token".to_string()
+        })?;
+        Ok(Self {
+            user: auth.user,
+            access_token: Redacted::new(auth.access_token),
+        })
+    }
+}